@@ -1,33 +1,73 @@
 //! Build script to read the skinset table and champ lanes table at compile time and generate rust code that can be
 //! used in the web app.  
+//!
+//! With the `riot-data` feature, the official skinsets are read from Riot's Data Dragon and CommunityDragon JSON
+//! (vendored in [RIOT_DATA_DIR]) instead of being scraped from the wiki's official skinset table. Neither has lane
+//! data or the community skinsets, so those still come from the wiki tables.
 
-use scraper::{Html, Selector};
+use champ_names::{champ_key, normalize_champ_name, CHAMP_ALIASES};
+use data_metadata::{format_date_long, DataMetadata};
+use data_tables::{parse_lanes_file, parse_riot_data, parse_skinsets_file, SkinsetTable};
+use anyhow::Context;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashSet},
     env,
-    fs::File,
+    fs::{self, File},
     io::{BufWriter, Write},
     path::Path,
 };
 
+/// Share the champ name normalization with the web app.
+#[path = "src/champ_names.rs"]
+mod champ_names;
+
+/// Share the data metadata format with the `update-data` tool. The build script doesn't need all of it.
+#[path = "src/data_metadata.rs"]
+#[allow(dead_code)]
+mod data_metadata;
+
+/// Share the table parsing with the native data tools.
+#[path = "src/data_tables.rs"]
+mod data_tables;
+
 /// Include the wiki sets table from https://leagueoflegends.fandom.com/wiki/Champion_skin/Skin_themes.
-#[allow(unused)]
 const WIKI_SETS_TABLE: &'static str = include_str!("assets/wiki-sets-table.html");
 
 /// Include the official sets table from https://leagueoflegends.fandom.com/wiki/Champion_skin/Skin_themes.
-#[allow(unused)]
 const OFFICIAL_SETS_TABLE: &'static str = include_str!("assets/official-sets-table.html");
 
-/// Select which table to use.
-const SKINSETS_HTML: &'static str = WIKI_SETS_TABLE;
-
 /// Lane table from https://leagueoflegends.fandom.com/wiki/List_of_champions_by_draft_position.
 const LANES_HTML: &'static str = include_str!("assets/champ-lanes-table.html");
 
+/// Metadata written by the `update-data` tool alongside the tables.
+const METADATA_JSON: &str = include_str!("assets/data-metadata.json");
+
+/// Changes from the last data update, saved by the `data-diff` tool. This is optional, so it's read at build time
+/// instead of being included.
+const DATA_CHANGES_PATH: &str = "assets/data-changes.json";
+
+/// Directory of the Riot JSON data read with the `riot-data` feature, relative to the crate root. Vendor these files
+/// into it:
+///
+/// - `championFull.json` from Data Dragon (`https://ddragon.leagueoflegends.com/cdn/<version>/data/en_US/championFull.json`).
+/// - `skins.json`, `skinlines.json`, and (optionally) `universes.json` from CommunityDragon
+///   (`https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default/v1/`). Data Dragon doesn't
+///   say which skin line each skin is in, so `skins.json` is needed to link skins to skin lines.
+///
+/// Set the `RIOT_DATA_DIR` environment variable to read them from somewhere else, e.g. the small fixture in
+/// `tests/fixtures/riot` (`RIOT_DATA_DIR=tests/fixtures/riot cargo build --features riot-data`).
+const RIOT_DATA_DIR: &str = "assets/riot";
+
 /// This build script generates a file that stores build data for reference in the web interface.
 fn main() -> anyhow::Result<()> {
     // Re-run if this script changes.
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/champ_names.rs");
+    println!("cargo:rerun-if-changed=src/data_metadata.rs");
+    println!("cargo:rerun-if-changed=src/data_tables.rs");
+    // Re-run when the data tables are updated.
+    println!("cargo:rerun-if-changed=assets");
+    println!("cargo:rerun-if-env-changed=RIOT_DATA_DIR");
 
     // We manually track the data acquired time for now.
     let out_dir = env::var_os("OUT_DIR").expect("OUT_DIR is defined");
@@ -63,6 +103,50 @@ fn main() -> anyhow::Result<()> {
         )
     )?;
 
+    // Write the date the data is from, so that the web app can show it.
+    let metadata: DataMetadata = serde_json::from_str(METADATA_JSON)?;
+    let Some(source_date_long) = format_date_long(&metadata.source_date) else {
+        anyhow::bail!("the data metadata has an invalid source date {:?} (expected YYYY-MM-DD)", metadata.source_date);
+    };
+
+    writeln!(
+        &mut writer,
+        "{}",
+        unindent::unindent(&format!(
+            r#"
+        /// The date the data tables were saved from their source pages, as `YYYY-MM-DD`.
+        pub const DATA_SOURCE_DATE: &str = "{}";
+
+        /// [DATA_SOURCE_DATE] formatted for display (e.g. "December 8th, 2023").
+        pub const DATA_SOURCE_DATE_LONG: &str = "{source_date_long}";
+    "#,
+            metadata.source_date
+        ))
+    )?;
+
+    // Embed the changes from the last data update, if there are any, so that the web app can show what's new. The
+    // JSON is checked here so that a bad file fails the build instead of being silently hidden by the web app.
+    let data_changes = match fs::read_to_string(DATA_CHANGES_PATH) {
+        Ok(json) => {
+            serde_json::from_str::<serde_json::Value>(&json)
+                .with_context(|| format!("{DATA_CHANGES_PATH} isn't valid JSON"))?;
+            format!("Some({:?})", json.trim())
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => "None".to_owned(),
+        Err(err) => return Err(err).with_context(|| format!("failed to read {DATA_CHANGES_PATH}")),
+    };
+
+    writeln!(
+        &mut writer,
+        "{}",
+        unindent::unindent(&format!(
+            r#"
+        /// The changes from the last data update (as saved by the `data-diff` tool), if there are any.
+        pub const DATA_CHANGES_JSON: Option<&str> = {data_changes};
+    "#
+        ))
+    )?;
+
     writeln!(
         &mut writer,
         "{}",
@@ -76,9 +160,15 @@ fn main() -> anyhow::Result<()> {
 
     
     // Parse the lane data, sort it, and add to the generated file.
-    let mut lane_data = parse_lanes_file();
+    let mut lane_data = parse_lanes_file(LANES_HTML);
     // Sort the lane data by champ name. 
     lane_data.sort_by_key(|(champ_name, _)| champ_name.clone());
+    // Keep a sorted list of the champ names so that we can index into the lane data later. 
+    let champs_sorted: Vec<String> = lane_data.iter().map(|(champ_name, _)| champ_name.clone()).collect();
+
+    // Map the normalized name and aliases of every champ to their index in the lane data, so that other tables can 
+    // be joined to this one by name.
+    let champ_lookup = make_champ_lookup(&champs_sorted)?;
     
     // Convert to a const-string. 
     let lane_data_const_string = lane_data
@@ -98,11 +188,52 @@ fn main() -> anyhow::Result<()> {
     // Write lane data
     writeln!(&mut writer, "\t{lane_data_const_string}\n];")?;
 
-    // Parse skinset data.
-    let (champs_to_skinsets, all_skinsets) = parse_skinsets_file();
-    // Make a sorted list of all the skinset names. 
-    let mut skinsets_sorted: Vec<String> = all_skinsets.into_iter().collect();
+    // Format the champ name lookup table. 
+    let champ_lookup_data = champ_lookup
+        .iter()
+        .map(|(normalized_name, champ_index)| format!("(r#\"{normalized_name}\"#, {champ_index})"))
+        .collect::<Vec<String>>()
+        .join(",\n\t");
+
+    // Write the champ name lookup table.
+    writeln!(
+        &mut writer,
+        "{}\t{champ_lookup_data}\n];",
+        unindent::unindent(
+            r#"
+        /// Map of every normalized champ name and alias to the champ's index into [LANE_DATA], sorted by name.
+        pub const CHAMP_NAME_LOOKUP: &'static [(&'static str, usize)] = &[
+    "#
+        )
+    )?;
+
+    // Parse the skinset data from both tables. Each table is compiled into its own dataset so that the web app can 
+    // switch between them at runtime.
+    let sources: [(&str, SkinsetTable); 2] = [
+        ("WIKI", parse_skinsets_file(WIKI_SETS_TABLE)),
+        (
+            "OFFICIAL",
+            if cfg!(feature = "riot-data") {
+                parse_riot_skinlines()?
+            } else {
+                parse_skinsets_file(OFFICIAL_SETS_TABLE)
+            },
+        ),
+    ];
+
+    // Join each skinset table to the lane table by champ name, failing the build with a report of every mismatch.
+    let aligned_champ_skinsets: Vec<Vec<HashSet<String>>> = sources
+        .iter()
+        .map(|(source_name, table)| align_champ_skinsets(source_name, &champs_sorted, &champ_lookup, table))
+        .collect::<anyhow::Result<_>>()?;
+
+    // Make a sorted list of all the skinset names in either table, so that skinset IDs are shared between datasets. 
+    let mut skinsets_sorted: Vec<String> = sources
+        .iter()
+        .flat_map(|(_, table)| table.all_skinsets.iter().cloned())
+        .collect();
     skinsets_sorted.sort();
+    skinsets_sorted.dedup();
     
     // Format the skinset list. 
     let all_skinset_data = skinsets_sorted
@@ -120,43 +251,150 @@ fn main() -> anyhow::Result<()> {
         "{}\t{all_skinset_data}\n];",
         unindent::unindent(
             r#"
-        /// List of all skinsets parsed from skinset HTML table at compile time. 
+        /// List of all skinsets parsed from both skinset HTML tables at compile time. 
         pub const ALL_SKINSET_NAMES: &'static [&'static str] = &[
     "#
         )
     )?;
     
-    // Convert the champ->skinset map to a list of all the groups of skinsets for each champ (a list of lists of indices
-    // into the skinset list). 
-    let mut sorted_champs_skinsets_map: Vec<(String, HashSet<String>)> = champs_to_skinsets.into_iter().collect();
-    // Sort by champ name. 
-    sorted_champs_skinsets_map.sort_by_key(|(champ_name, _)| champ_name.clone());
-
-    // Iterate over this sorted list stripping out the champ name and replacing the list of skinsets with a list of indices.
-    let skinset_index_table: Vec<Vec<usize>> = sorted_champs_skinsets_map
-        .into_iter()
-        .map(|(_, skinsets)| {
-            skinsets.into_iter()
-                .map(|skinset| skinsets_sorted.binary_search(&skinset).unwrap())
-                .collect()
-        })
+    // Make a sorted, deduplicated list of all the universe names in either table.
+    let mut universes_sorted: Vec<String> = sources
+        .iter()
+        .flat_map(|(_, table)| table.skinset_universes.values().cloned())
         .collect();
+    universes_sorted.sort();
+    universes_sorted.dedup();
+
+    // Format the universe list.
+    let all_universe_data = universes_sorted
+        .iter()
+        .map(|universe| format!("r##\"{universe}\"##"))
+        .collect::<Vec<String>>()
+        .join(",\n\t");
 
-    // Format out the body of the skinset map data.
-    let skinset_map_data = skinset_index_table
+    // Add all universe data to file.
+    writeln!(
+        &mut writer,
+        "{}\t{all_universe_data}\n];",
+        unindent::unindent(
+            r#"
+        /// List of all skin universes parsed from both skinset HTML tables at compile time. 
+        pub const ALL_UNIVERSE_NAMES: &'static [&'static str] = &[
+    "#
+        )
+    )?;
+
+    // Determine how many 64-bit words we need to have one bit per skinset.
+    let mask_words: usize = skinsets_sorted.len().div_ceil(64);
+
+    // Write the width of the skinset bitsets so that the web app can size its mask type, and the struct used to store 
+    // each dataset.
+    writeln!(
+        &mut writer,
+        "{}",
+        unindent::unindent(&format!(
+            r#"
+        /// The number of 64-bit words in each skinset bitset (one bit per skinset, in [ALL_SKINSET_NAMES] order). 
+        pub const SKINSET_MASK_WORDS: usize = {mask_words};
+
+        /// The skinset data parsed from one of the skinset HTML tables.
+        pub struct SkinsetData {{
+            /// Bitset of the skinsets that are in this table. 
+            pub skinsets: [u64; SKINSET_MASK_WORDS],
+            /// Map of all champs (in [LANE_DATA] order) to a bitset of the skinsets they have. 
+            pub champs_to_skinsets: &'static [[u64; SKINSET_MASK_WORDS]],
+            /// The index into [ALL_UNIVERSE_NAMES] of the universe each skinset (in [ALL_SKINSET_NAMES] order) is part
+            /// of. Skinsets missing from this table use their universe from the other table.
+            pub skinset_universes: &'static [usize],
+            /// The bit set in the last field of [SKIN_DATA] for skins in this table. 
+            pub skin_flag: u8,
+        }}
+    "#
+        ))
+    )?;
+
+    // Make a map of every skin in either table to the flags of the tables it's in.
+    let mut skins: BTreeMap<(usize, usize, String), u8> = BTreeMap::new();
+
+    for (source_index, ((source_name, table), champ_skinsets)) in sources.iter().zip(&aligned_champ_skinsets).enumerate() {
+        // Get the flag for skins in this table.
+        let skin_flag: u8 = 1 << source_index;
+
+        // Make a bitset of the skinsets in this table.
+        let table_skinsets = format_mask(
+            table.all_skinsets.iter().map(|skinset| skinsets_sorted.binary_search(skinset).unwrap()),
+            mask_words,
+        );
+
+        // Map each skinset (in sorted order) to the index of its universe, falling back to the other tables for
+        // skinsets missing from this one.
+        let skinset_universe_data = skinsets_sorted
+            .iter()
+            .map(|skinset| {
+                let universe = table
+                    .skinset_universes
+                    .get(skinset)
+                    .or_else(|| sources.iter().find_map(|(_, other)| other.skinset_universes.get(skinset)))
+                    .unwrap();
+
+                universes_sorted.binary_search(universe).unwrap().to_string()
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        // Iterate over the skinsets of each champ (in lane data order) replacing the list of skinsets with a bitset of
+        // their indices.
+        let skinset_map_data = champ_skinsets
+            .iter()
+            .map(|skinsets| {
+                format_mask(skinsets.iter().map(|skinset| skinsets_sorted.binary_search(skinset).unwrap()), mask_words)
+            })
+            .collect::<Vec<String>>()
+            .join(",\n\t\t");
+
+        // Write this table's dataset.
+        writeln!(
+            &mut writer,
+            "{}\t\t{skinset_map_data}\n\t],\n\tskinset_universes: &[{skinset_universe_data}],\n\tskin_flag: {skin_flag},\n}};\n",
+            unindent::unindent(&format!(
+                r#"
+            /// Skinset data parsed from the {} skinset table at compile time. 
+            pub const {source_name}_SKINSETS: SkinsetData = SkinsetData {{
+                skinsets: {table_skinsets},
+                champs_to_skinsets: &[
+        "#,
+                source_name.to_lowercase()
+            ))
+        )?;
+
+        // Add this table's skins to the map of all skins, replacing champ and skinset names with their indices.
+        for (champ, skinset, skin_name) in table.skins.iter() {
+            // Every champ here was already checked against the lane table by [align_champ_skinsets].
+            let champ_index = champ_lookup[&champ_key(champ)];
+            let skinset_index = skinsets_sorted.binary_search(skinset).unwrap();
+            *skins.entry((champ_index, skinset_index, skin_name.clone())).or_default() |= skin_flag;
+        }
+    }
+
+    // Format out the skin data -- the map is already sorted by champ, then skinset, then name so that the skins for a
+    // champ in a skinset are contiguous.
+    let skin_data = skins
         .into_iter()
-        .map(|skinset_list| format!("&{skinset_list:?}"))
+        .map(|((champ_index, skinset_index, skin_name), flags)| {
+            format!("({champ_index}, {skinset_index}, r##\"{skin_name}\"##, {flags:#04b})")
+        })
         .collect::<Vec<String>>()
         .join(",\n\t");
 
-    // Finally write the skinset map.
+    // Write the skin table.
     writeln!(
         &mut writer,
-        "{}\t{skinset_map_data}\n];",
+        "{}\t{skin_data}\n];",
         unindent::unindent(
             r#"
-        /// Map of all champ names to skinsets. 
-        pub const CHAMPS_TO_SKINSETS: &'static [&'static [usize]] = &[
+        /// List of every skin in a skinset as (index into [LANE_DATA], index into [ALL_SKINSET_NAMES], skin name, 
+        /// flags of the tables the skin is listed in), sorted by champ then skinset. 
+        pub const SKIN_DATA: &'static [(usize, usize, &'static str, u8)] = &[
     "#
         )
     )?;
@@ -168,122 +406,145 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Parse the skinsets file from html and return a map from champ name -> skinsets
-/// and a set of all the skinset names.
-///
-/// Adapted from original runtime version.
-fn parse_skinsets_file() -> (HashMap<String, HashSet<String>>, HashSet<String>) {
-    // Parse the fragment we're useing.
-    let fragment = Html::parse_fragment(SKINSETS_HTML);
-    // Make a selector to get rows out of the table.
-    let rows_selector: Selector = Selector::parse("tr").expect("rows selector good");
-    // Make a selector to find champs from a row element.
-    let champs_selector: Selector = Selector::parse("li > span").expect("champ selector good");
-    // Make a selector to find the set name from a row ref.
-    let set_name_selector: Selector =
-        Selector::parse("th:last-of-type").expect("set name selector good");
-    // Make an iterator to go over all the rows of the skinset table, skipping the header row.
-    let row_iter = fragment.select(&rows_selector).skip(1);
-    // Make the champ-skinset map to populate
-    let mut champ_to_skinset_map: HashMap<String, HashSet<String>> = HashMap::new();
-    // Make set of all skinsets to store and pass out too.
-    let mut set_of_all_skinsets: HashSet<String> = HashSet::new();
-
-    // Iterate over all the rows of the table.
-    for row_ref in row_iter {
-        // Get the set name.
-        let set_name: String = row_ref
-            .select(&set_name_selector)
-            .next()
-            .expect("finds set name")
-            .text()
-            .collect::<String>();
-
-        // Insert/upsert into the set of all skinsets.
-        set_of_all_skinsets.insert(set_name.clone());
-
-        // Get an iterator over all the champ names in this set.
-        let champs_iter = row_ref.select(&champs_selector).map(|champ_el_ref| {
-            champ_el_ref
-                // Get the referenced element
-                .value()
-                // Read the 'data-champion' attribute
-                .attr("data-champion")
-                // Crash if it's not there.
-                .expect("champion name available")
-                // Convert to owned string
-                .to_owned()
-        });
-
-        // Add all of the champ-skinset mappings into the map.
-        for champ in champs_iter {
-            champ_to_skinset_map
-                // Get the map entry for this champ.
-                .entry(champ)
-                // Make a new empty one if not recognized.
-                .or_default()
-                // Add the set name to this champ's list.
-                .insert(set_name.clone());
+/// Get the directory of the Riot JSON data, which can be moved with the `RIOT_DATA_DIR` environment variable.
+fn riot_data_dir() -> String {
+    env::var("RIOT_DATA_DIR").unwrap_or_else(|_| RIOT_DATA_DIR.to_owned())
+}
+
+/// Read a file from the Riot JSON data directory.
+fn read_riot_file(file_name: &str) -> anyhow::Result<String> {
+    let path = Path::new(&riot_data_dir()).join(file_name);
+
+    fs::read_to_string(&path)
+        .with_context(|| format!("the riot-data feature needs {} (see RIOT_DATA_DIR in build.rs)", path.display()))
+}
+
+/// Read the official skin lines from the Riot JSON data, in the same shape as a parsed skinset table.
+fn parse_riot_skinlines() -> anyhow::Result<SkinsetTable> {
+    // The universes file is optional.
+    let universes_json = if Path::new(&riot_data_dir()).join("universes.json").exists() {
+        Some(read_riot_file("universes.json")?)
+    } else {
+        None
+    };
+
+    parse_riot_data(
+        &read_riot_file("championFull.json")?,
+        &read_riot_file("skins.json")?,
+        &read_riot_file("skinlines.json")?,
+        universes_json.as_deref(),
+    )
+}
+
+/// Make a map from the normalized name and aliases of each champ to their index in the (sorted) champ names from the 
+/// lane table. Fails if two champs or aliases normalize to the same name, or an alias is for a champ that doesn't 
+/// exist. 
+fn make_champ_lookup(champs_sorted: &[String]) -> anyhow::Result<BTreeMap<String, usize>> {
+    // Start with the names from the lane table.
+    let mut champ_lookup: BTreeMap<String, usize> = BTreeMap::new();
+
+    for (champ_index, champ) in champs_sorted.iter().enumerate() {
+        if let Some(other_index) = champ_lookup.insert(normalize_champ_name(champ), champ_index) {
+            anyhow::bail!("lane table champs {champ:?} and {:?} have the same normalized name", champs_sorted[other_index]);
+        }
+    }
+
+    // Add the aliases.
+    for (alias, canonical) in CHAMP_ALIASES {
+        let champ_index = *champ_lookup
+            .get(&normalize_champ_name(canonical))
+            .ok_or_else(|| anyhow::anyhow!("alias {alias:?} is for {canonical:?}, which isn't in the lane table"))?;
+
+        // Aliases can repeat a champ's own name, but must not shadow a different champ.
+        match champ_lookup.insert(normalize_champ_name(alias), champ_index) {
+            Some(other_index) if other_index != champ_index => {
+                anyhow::bail!("alias {alias:?} for {canonical:?} clashes with {:?}", champs_sorted[other_index])
+            }
+            _ => {}
         }
     }
 
-    (champ_to_skinset_map, set_of_all_skinsets)
+    Ok(champ_lookup)
 }
 
-/// Parse the lanes table file from html and return a map from champ name -> lanes.
+/// Join a skinset table to the (sorted) champ names from the lane table by normalized name or alias, returning the 
+/// skinsets of each champ in the same order as the lane table.
 ///
-/// Adapted from original runtime version.
-fn parse_lanes_file() -> Vec<(String, Vec<&'static str>)> {
-    // Make map to populate.
-    let mut champ_to_lanes_map = Vec::new();
-    // Load the table fragment into a scrapable document.
-    let fragment: Html = Html::parse_fragment(LANES_HTML);
-    // Make a selector to get rows out of the table.
-    let rows_selector: Selector = Selector::parse("tbody > tr").expect("rows selector good");
-    // Make a selector to get columns out of a row element.
-    let cols_selector: Selector = Selector::parse("td").expect("cols selector good");
-
-    // Iterate over every row in the table.
-    for row_ref in fragment.select(&rows_selector) {
-        // Assume the children of the row are (in order):
-        // Champ name, top, jungle, mid, bot lane, support, unused.
-
-        // Make an iterator over column elements that takes the exact number we want.
-        let mut cols_iterator = row_ref
-            .select(&cols_selector)
-            // Ignore the unused column.
-            .take(6);
-
-        // Take the champ name from the iterator.
-        let champ_name: String = cols_iterator
-            .next()
-            .expect("Finds champ name element")
-            .value()
-            .attr("data-sort-value")
-            .expect("Finds champ name attribute")
-            .to_owned();
-
-        // Make a list of lanes to populate by iterating over the rest of the columns.
-        let mut lanes: Vec<&'static str> = Vec::with_capacity(5);
-        // Iterate over the remaining columns to populate bitflags.
-        for (index, col) in cols_iterator.enumerate() {
-            if col.value().attr("data-sort-value").is_some() {
-                match index {
-                    0 => lanes.push("Top"),
-                    1 => lanes.push("Jungle"),
-                    2 => lanes.push("Mid"),
-                    3 => lanes.push("Bot"),
-                    4 => lanes.push("Support"),
-                    // Unreachable because we limit the number of <td> tags in the iterator using take()
-                    _ => unreachable!(),
-                }
+/// Champs in the skinset table that are not in the lane table would otherwise shift or drop data silently, so they 
+/// are returned as an error listing every mismatched name. Champs in the lane table without any skinsets just get a 
+/// build warning, since new champs often have no skins in a set yet.
+fn align_champ_skinsets(
+    source_name: &str,
+    champs_sorted: &[String],
+    champ_lookup: &BTreeMap<String, usize>,
+    table: &SkinsetTable,
+) -> anyhow::Result<Vec<HashSet<String>>> {
+    // Collect the skinsets of each champ in the lane table, and any champs that aren't in it.
+    let mut champ_skinsets: Vec<Option<HashSet<String>>> = vec![None; champs_sorted.len()];
+    let mut unknown_champs: Vec<&String> = Vec::new();
+
+    for (champ, skinsets) in table.champ_to_skinsets.iter() {
+        match champ_lookup.get(&champ_key(champ)) {
+            // Two spellings of the same champ in one table get merged.
+            Some(champ_index) => {
+                champ_skinsets[*champ_index].get_or_insert_with(HashSet::new).extend(skinsets.iter().cloned())
             }
+            None => unknown_champs.push(champ),
         }
+    }
+
+    if !unknown_champs.is_empty() {
+        unknown_champs.sort();
+
+        // List the lane table champs missing from the skinset table too, since a mismatch is usually a spelling
+        // difference between the two tables (which can be fixed with an alias in `src/champ_names.rs`).
+        let missing_champs: Vec<&String> = champs_sorted
+            .iter()
+            .zip(&champ_skinsets)
+            .filter(|(_, skinsets)| skinsets.is_none())
+            .map(|(champ, _)| champ)
+            .collect();
 
-        // Add the champ and their lanes to the map. We use insert rather than upsert here because we assume there
-        // are no duplicates in the table.
-        champ_to_lanes_map.push((champ_name, lanes));
+        anyhow::bail!(
+            "the {} skinset table doesn't match the lane table:\n  \
+            champs in the skinset table but not the lane table: {unknown_champs:?}\n  \
+            champs in the lane table but not the skinset table: {missing_champs:?}",
+            source_name.to_lowercase()
+        );
     }
 
-    champ_to_lanes_map
+    // Get the skinsets for each champ in the lane table, warning about champs with none.
+    let champ_skinsets = champs_sorted
+        .iter()
+        .zip(champ_skinsets)
+        .map(|(champ, skinsets)| match skinsets {
+            Some(skinsets) => skinsets,
+            None => {
+                println!("cargo:warning={champ} has no skinsets in the {} skinset table", source_name.to_lowercase());
+                HashSet::new()
+            }
+        })
+        .collect();
+
+    Ok(champ_skinsets)
+}
+
+/// Format a list of skinset indices as a rust array literal of bitset words.
+fn format_mask(indices: impl Iterator<Item = usize>, mask_words: usize) -> String {
+    // Start with an empty bitset.
+    let mut mask = vec![0u64; mask_words];
+
+    // Set the bit for each skinset.
+    for index in indices {
+        mask[index / 64] |= 1 << (index % 64);
+    }
+
+    // Format each word as a hex literal.
+    let words = mask
+        .into_iter()
+        .map(|word| format!("{word:#018x}"))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("[{words}]")
 }
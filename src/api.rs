@@ -0,0 +1,481 @@
+//! Small JSON API over HTTP, so that comps can be found programmatically (e.g. by chat bots or spreadsheet scripts).
+//!
+//! - `GET /champions` lists every champ with their default lanes.
+//! - `GET /skinsets?source=wiki|official` lists every skinset in a skinset table, with its universe and champs.
+//! - `POST /comps?limit=N` takes a team file (in the same format the web frontend exports) and returns the comps it can
+//!   play, ranked by their score (see [crate::scoring]) using the team file's favourite skinsets and score weights. If
+//!   the team file only needs some of the players to share a skinset, comps with more players in theme rank first.
+//!   In ARAM and Arena, lanes are ignored (and returned as `null`). In Arena, each duo is searched and limited on its
+//!   own, and each comp says which duo it's for. Team files set up for two teams have to use `/matchups` instead.
+//! - `POST /matchups?limit=N` takes a team file with up to 10 players, splits them into two teams and returns the best
+//!   pairs of team comps, ranked by their total score. The team file's team setup decides whether the two teams have to
+//!   be on different or rival skinsets.
+//!
+//! Limits can be at most [MAX_COMP_LIMIT], and request bodies at most [MAX_BODY_SIZE] bytes. Errors are returned as
+//! `{"error": "..."}` with a 4xx status.
+
+use crate::constants::{ChampId, Lane, SkinsetId, SkinsetMask, SkinsetSource, UniverseId};
+use crate::persist::{ImportProblem, PlayerRecord, SavedState};
+use crate::scoring::{CompScore, Scorer};
+use crate::solver::{self, Comp, CompThemes, MatchMode};
+use crate::teams::{TeamComp, TeamPlanner, TeamSetup, TeamThemes};
+use derive_more::Display;
+use serde::Serialize;
+use serde_json::json;
+use std::io::Read;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// The number of comps returned by `POST /comps` (or matchups returned by `POST /matchups`) if the request doesn't set a
+/// limit.
+pub const DEFAULT_COMP_LIMIT: usize = 100;
+
+/// The largest limit a request can set.
+pub const MAX_COMP_LIMIT: usize = 10_000;
+
+/// The largest request body accepted, in bytes. Team files are much smaller than this.
+pub const MAX_BODY_SIZE: usize = 1 << 20;
+
+/// Reasons a request can fail.
+#[derive(Clone, Debug, Display, PartialEq, Eq)]
+enum ApiError {
+    /// There's no endpoint at the requested path.
+    #[display(fmt = "no endpoint at {_0}")]
+    NotFound(String),
+
+    /// The endpoint doesn't support the request method.
+    #[display(fmt = "{_0} isn't supported by {_1}")]
+    MethodNotAllowed(Method, String),
+
+    /// The request's query or body couldn't be used.
+    #[display(fmt = "{_0}")]
+    BadRequest(String),
+
+    /// The request's body is larger than [MAX_BODY_SIZE].
+    #[display(fmt = "the request body is over the maximum of {MAX_BODY_SIZE} bytes")]
+    PayloadTooLarge,
+}
+
+impl ApiError {
+    /// Get the HTTP status code for this error.
+    fn status(&self) -> u16 {
+        match self {
+            ApiError::NotFound(_) => 404,
+            ApiError::MethodNotAllowed(..) => 405,
+            ApiError::BadRequest(_) => 400,
+            ApiError::PayloadTooLarge => 413,
+        }
+    }
+}
+
+/// A champ, as listed by `GET /champions`.
+#[derive(Serialize)]
+struct ChampRecord {
+    /// The name of the champ.
+    name: &'static str,
+    /// The lanes the champ is played in according to the wiki.
+    default_lanes: Vec<Lane>,
+}
+
+/// A skinset, as listed by `GET /skinsets`.
+#[derive(Serialize)]
+struct SkinsetRecord {
+    /// The name of the skinset.
+    name: &'static str,
+    /// The name of the universe the skinset is in.
+    universe: &'static str,
+    /// The names of the champs with a skin in the skinset.
+    champs: Vec<&'static str>,
+}
+
+/// The comps found by `POST /comps`.
+#[derive(Serialize)]
+struct CompsResponse {
+    /// The entries of the team file that were skipped because they don't match the current data.
+    problems: Vec<String>,
+    /// The total number of comps found for every team, including ones past the limit.
+    total: usize,
+    /// Whether [RankedComp::shared] lists skinsets or universes.
+    match_mode: MatchMode,
+    /// The best comps for each team in turn, in rank order.
+    comps: Vec<RankedComp>,
+}
+
+/// A comp returned by `POST /comps`.
+#[derive(Serialize)]
+struct RankedComp {
+    /// The index of the team the comp is for. This is always 0, except in Arena where each duo is a team.
+    team: usize,
+    /// The champ and lane for each player.
+    picks: Vec<PickRecord>,
+    /// The names of the skinsets (or universes) shared by at least some of the players.
+    shared: Vec<&'static str>,
+    /// The number of players in the largest group sharing a skinset.
+    themed_players: usize,
+    /// The comp's score.
+    score: CompScore,
+}
+
+/// The matchups planned by `POST /matchups`.
+#[derive(Serialize)]
+struct MatchupsResponse {
+    /// The entries of the team file that were skipped because they don't match the current data.
+    problems: Vec<String>,
+    /// Whether [TeamRecord::themes] lists skinsets or universes.
+    match_mode: MatchMode,
+    /// How the two teams' skinsets had to relate to each other.
+    team_themes: TeamThemes,
+    /// The best matchups, in rank order.
+    matchups: Vec<MatchupRecord>,
+}
+
+/// A matchup returned by `POST /matchups`.
+#[derive(Serialize)]
+struct MatchupRecord {
+    /// The comps of the two teams.
+    teams: Vec<TeamRecord>,
+    /// The sum of the two teams' scores.
+    total: f64,
+}
+
+/// One team's comp in a matchup.
+#[derive(Serialize)]
+struct TeamRecord {
+    /// The champ and lane for each player on the team.
+    picks: Vec<PickRecord>,
+    /// The names of the skinsets (or universes) the team themes on.
+    themes: Vec<&'static str>,
+    /// The team comp's score.
+    score: CompScore,
+}
+
+/// A player's pick in a comp.
+#[derive(Serialize)]
+struct PickRecord {
+    /// The name of the player (or their number if they don't have one).
+    player: String,
+    /// The name of the champ.
+    champ: &'static str,
+    /// The lane the champ is played in, or `None` in game modes without lanes.
+    lane: Option<Lane>,
+    /// Whether the champ doesn't share a skinset with enough of the others.
+    off_theme: bool,
+}
+
+/// Answer requests to a server until it shuts down.
+pub fn serve(server: &Server) {
+    for mut request in server.incoming_requests() {
+        let (status, body) = match route(&mut request) {
+            Ok(body) => (200, body),
+            Err(err) => (err.status(), json!({ "error": err.to_string() })),
+        };
+
+        let content_type = Header::from_bytes("Content-Type", "application/json").expect("valid header");
+        let response = Response::from_string(body.to_string()).with_status_code(status).with_header(content_type);
+
+        if let Err(err) = request.respond(response) {
+            log::warn!("Failed to send response: {err}");
+        }
+    }
+}
+
+/// Dispatch a request to its endpoint.
+fn route(request: &mut Request) -> Result<serde_json::Value, ApiError> {
+    let url = request.url().to_owned();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+
+    match (request.method(), path) {
+        (Method::Get, "/champions") => Ok(list_champions()),
+        (Method::Get, "/skinsets") => list_skinsets(query),
+        (Method::Post, "/comps") => find_comps(query, &read_body(request)?),
+        (Method::Post, "/matchups") => plan_matchups(query, &read_body(request)?),
+
+        (method, "/champions" | "/skinsets" | "/comps" | "/matchups") => {
+            Err(ApiError::MethodNotAllowed(method.clone(), path.to_owned()))
+        }
+        _ => Err(ApiError::NotFound(path.to_owned())),
+    }
+}
+
+/// Read the whole body of a request, up to [MAX_BODY_SIZE] bytes.
+fn read_body(request: &mut Request) -> Result<String, ApiError> {
+    if request.body_length().is_some_and(|length| length > MAX_BODY_SIZE) {
+        return Err(ApiError::PayloadTooLarge);
+    }
+
+    // Bodies without a length are only read one byte past the maximum, to tell whether they're over it.
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_SIZE as u64 + 1)
+        .read_to_string(&mut body)
+        .map_err(|err| ApiError::BadRequest(format!("couldn't read the request body ({err})")))?;
+
+    if body.len() > MAX_BODY_SIZE {
+        return Err(ApiError::PayloadTooLarge);
+    }
+
+    Ok(body)
+}
+
+/// Get the `limit` parameter of a query string, or the default limit if it isn't set. Limits over [MAX_COMP_LIMIT] are
+/// rejected.
+fn limit_param(query: &str) -> Result<usize, ApiError> {
+    let limit = match query_param(query, "limit") {
+        None => DEFAULT_COMP_LIMIT,
+        Some(limit) => limit.parse().map_err(|_| ApiError::BadRequest(format!("invalid limit {limit:?}")))?,
+    };
+
+    if limit > MAX_COMP_LIMIT {
+        return Err(ApiError::BadRequest(format!("limit {limit} is over the maximum of {MAX_COMP_LIMIT}")));
+    }
+
+    Ok(limit)
+}
+
+/// Get the names of the skinsets (or universes, in universe mode) in a set.
+fn theme_names(skinsets: SkinsetMask, match_mode: MatchMode, source: SkinsetSource) -> Vec<&'static str> {
+    match match_mode {
+        MatchMode::Skinset => skinsets.iter().map(SkinsetId::skinset_name).collect(),
+        MatchMode::Universe => skinsets.universes(source).into_iter().map(UniverseId::universe_name).collect(),
+    }
+}
+
+/// Get the name of each player, or their number if they don't have one.
+fn player_names(players: &[PlayerRecord]) -> Vec<String> {
+    players
+        .iter()
+        .enumerate()
+        .map(|(index, player)| player.name.clone().unwrap_or_else(|| format!("Player {}", index + 1)))
+        .collect()
+}
+
+/// Get the value of a parameter in a query string.
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// `GET /champions`
+fn list_champions() -> serde_json::Value {
+    let champs: Vec<ChampRecord> = ChampId::iter_all()
+        .map(|champ_id| ChampRecord {
+            name: champ_id.champ_name(),
+            default_lanes: champ_id.default_lanes().iter().collect(),
+        })
+        .collect();
+
+    json!(champs)
+}
+
+/// `GET /skinsets`
+fn list_skinsets(query: &str) -> Result<serde_json::Value, ApiError> {
+    let source = match query_param(query, "source") {
+        None | Some("wiki") => SkinsetSource::Wiki,
+        Some("official") => SkinsetSource::Official,
+        Some(other) => return Err(ApiError::BadRequest(format!("unknown skinset source {other:?}"))),
+    };
+
+    let skinsets: Vec<SkinsetRecord> = source
+        .skinset_mask()
+        .iter()
+        .map(|skinset_id| SkinsetRecord {
+            name: skinset_id.skinset_name(),
+            universe: skinset_id.universe(source).universe_name(),
+            champs: ChampId::iter_all()
+                .filter(|champ_id| champ_id.skinset_mask(source).contains(skinset_id))
+                .map(ChampId::champ_name)
+                .collect(),
+        })
+        .collect();
+
+    Ok(json!(skinsets))
+}
+
+/// Parse a request body as a team file.
+fn parse_team_file(body: &str) -> Result<(SavedState, Vec<ImportProblem>), ApiError> {
+    SavedState::from_team_file(body)
+        .map_err(|err| ApiError::BadRequest(format!("the body isn't a valid team file ({err})")))
+}
+
+/// `POST /comps`
+fn find_comps(query: &str, body: &str) -> Result<serde_json::Value, ApiError> {
+    let limit = limit_param(query)?;
+    let (saved_state, problems) = parse_team_file(body)?;
+
+    let SavedState {
+        players,
+        skinsets_excluded,
+        match_mode,
+        skinset_source,
+        favourite_skinsets,
+        score_weights,
+        min_preference_tier,
+        min_themed_players,
+        game_mode,
+        team_setup,
+        ..
+    } = saved_state;
+
+    // Two teams are planned together, which only `/matchups` does.
+    if let TeamSetup::TwoTeams(_) = team_setup {
+        return Err(ApiError::BadRequest("the team file is set up for two teams, so use /matchups to plan them".into()));
+    }
+
+    // Rank comps by their largest themed group and then by score, best first. The sort is stable, so ties are kept in
+    // the order the solver found them in.
+    let rank = |best: &mut Vec<(CompScore, Comp, CompThemes)>| {
+        best.sort_by(|(a, _, a_themes), (b, _, b_themes)| {
+            b_themes.largest_group().cmp(&a_themes.largest_group()).then_with(|| b.total.total_cmp(&a.total))
+        });
+        best.truncate(limit);
+    };
+
+    let player_names = player_names(&players);
+    let mut total: usize = 0;
+    let mut comps = Vec::new();
+
+    // Each team (the whole team, or each duo in Arena) is searched and ranked on its own.
+    for (team_index, team) in game_mode.teams(players.len()).into_iter().enumerate() {
+        let team_players = &players[team.clone()];
+
+        // Only search the champs in the tiers the team file searches.
+        let mut owned_pools: Vec<Vec<_>> =
+            team_players.iter().map(|player| player.champ_pool(min_preference_tier)).collect();
+
+        if !game_mode.has_lanes() {
+            solver::ignore_lanes(&mut owned_pools).map_err(|err| ApiError::BadRequest(err.to_string()))?;
+        }
+
+        let champ_pools: Vec<&[(ChampId, _)]> = owned_pools.iter().map(Vec::as_slice).collect();
+
+        let scorer = Scorer {
+            players: team_players,
+            favourite_skinsets,
+            match_mode,
+            skinset_source,
+            game_mode,
+            weights: score_weights,
+        };
+
+        // Only keep the best comps found so far, so that large searches don't have to hold every comp in memory.
+        let mut best = Vec::new();
+
+        let team_comps =
+            solver::resolve_themed_comps(&champ_pools, skinsets_excluded, match_mode, skinset_source, min_themed_players)
+                .map_err(|err| ApiError::BadRequest(err.to_string()))?;
+
+        for (comp, themes) in team_comps {
+            total += 1;
+            best.push((scorer.score(&comp, themes.shared()), comp, themes));
+
+            if best.len() >= limit.max(1).saturating_mul(2) {
+                rank(&mut best);
+            }
+        }
+
+        rank(&mut best);
+
+        // Resolve the names of everything in the best comps.
+        let team_names = &player_names[team];
+
+        comps.extend(best.into_iter().map(|(score, comp, themes)| RankedComp {
+            team: team_index,
+            picks: comp
+                .into_iter()
+                .zip(team_names)
+                .enumerate()
+                .map(|(index, ((champ_id, lane), player))| PickRecord {
+                    player: player.clone(),
+                    champ: champ_id.champ_name(),
+                    lane: game_mode.has_lanes().then_some(lane),
+                    off_theme: !themes.is_in_theme(index),
+                })
+                .collect(),
+            shared: theme_names(themes.shared(), match_mode, skinset_source),
+            themed_players: themes.largest_group(),
+            score,
+        }));
+    }
+
+    Ok(json!(CompsResponse {
+        problems: problems.iter().map(ToString::to_string).collect(),
+        total,
+        match_mode,
+        comps,
+    }))
+}
+
+/// `POST /matchups`
+fn plan_matchups(query: &str, body: &str) -> Result<serde_json::Value, ApiError> {
+    let limit = limit_param(query)?;
+    let (saved_state, problems) = parse_team_file(body)?;
+
+    let SavedState {
+        players,
+        skinsets_excluded,
+        match_mode,
+        skinset_source,
+        favourite_skinsets,
+        score_weights,
+        min_preference_tier,
+        game_mode,
+        team_setup,
+        ..
+    } = saved_state;
+
+    // A team file for one team can still be split in two, with the teams on any skinsets.
+    let team_themes = match team_setup {
+        TeamSetup::OneTeam => TeamThemes::Any,
+        TeamSetup::TwoTeams(team_themes) => team_themes,
+    };
+
+    let planner = TeamPlanner {
+        players: &players,
+        skinsets_excluded,
+        match_mode,
+        skinset_source,
+        game_mode,
+        min_preference_tier,
+        favourite_skinsets,
+        score_weights,
+        team_themes,
+    };
+
+    // Resolve the names of everything in the best matchups.
+    let player_names = player_names(&players);
+
+    let team_record = |team: TeamComp| TeamRecord {
+        picks: team
+            .players
+            .iter()
+            .zip(team.comp)
+            .map(|(player, (champ_id, lane))| PickRecord {
+                player: player_names[*player].clone(),
+                champ: champ_id.champ_name(),
+                lane: game_mode.has_lanes().then_some(lane),
+                off_theme: false,
+            })
+            .collect(),
+        themes: theme_names(team.themes, match_mode, skinset_source),
+        score: team.score,
+    };
+
+    let matchups = planner
+        .plan(limit)
+        .into_iter()
+        .map(|matchup| MatchupRecord {
+            teams: matchup.teams.into_iter().map(team_record).collect(),
+            total: matchup.total,
+        })
+        .collect();
+
+    Ok(json!(MatchupsResponse {
+        problems: problems.iter().map(ToString::to_string).collect(),
+        match_mode,
+        team_themes,
+        matchups,
+    }))
+}
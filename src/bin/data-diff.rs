@@ -0,0 +1,44 @@
+//! Command line tool to compare two snapshots of the data tables in `assets/` (e.g. a copy of the old tables and the
+//! ones just written by `update-data`) and report what changed as Markdown.
+//!
+//! With `--save`, the changes are also written to the new snapshot, where the build script picks them up so that the
+//! web app can show them.
+
+use anyhow::Context;
+use clap::Parser;
+use league_skinset_finder::data_diff::{DataDiff, Snapshot};
+use std::path::PathBuf;
+
+/// Report the changes between two snapshots of the data tables as Markdown.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// The directory with the old data tables.
+    #[arg(long, value_name = "DIR")]
+    old: PathBuf,
+
+    /// The directory with the new data tables.
+    #[arg(long, value_name = "DIR", default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/assets"))]
+    new: PathBuf,
+
+    /// Also save the changes in the new directory, so that the web app shows them once it's rebuilt.
+    #[arg(long)]
+    save: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let old = Snapshot::read(&args.old).with_context(|| format!("failed to read the snapshot in {}", args.old.display()))?;
+    let new = Snapshot::read(&args.new).with_context(|| format!("failed to read the snapshot in {}", args.new.display()))?;
+
+    let diff = DataDiff::compare(&old, &new);
+    print!("{}", diff.to_markdown());
+
+    if args.save {
+        let changes_path = diff.save(&args.new)?;
+        eprintln!("Saved the changes to {}. Rebuild to show them in the web app.", changes_path.display());
+    }
+
+    Ok(())
+}
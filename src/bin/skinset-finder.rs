@@ -0,0 +1,411 @@
+//! Command line tool to find the team comps in a team file (exported from the web frontend) that share skinsets.
+
+use anyhow::{anyhow, Context};
+use clap::{Parser, ValueEnum};
+use league_skinset_finder::constants::{ChampId, Lane, SkinsetId, SkinsetMask, SkinsetSource, UniverseId};
+use league_skinset_finder::persist::{PreferenceTier, SavedState};
+use league_skinset_finder::solver::{self, GameMode, MatchMode};
+use serde::Serialize;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Find League of Legends team comps that share skinsets.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// The team file to find comps for, as exported from the web frontend. Use `-` to read it from stdin.
+    team_file: PathBuf,
+
+    /// How to print the comps.
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+
+    /// Exclude a skinset, in addition to the ones the team file excludes. Can be repeated.
+    #[arg(short = 'x', long = "exclude", value_name = "SKINSET")]
+    excluded: Vec<String>,
+
+    /// Search a skinset that the team file excludes. Can be repeated.
+    #[arg(short, long = "include", value_name = "SKINSET")]
+    included: Vec<String>,
+
+    /// Ignore the skinsets the team file excludes (skinsets passed to --exclude are still excluded).
+    #[arg(long)]
+    include_all: bool,
+
+    /// The game mode to find comps for, instead of the team file's. Lanes are ignored in ARAM and Arena, and in Arena
+    /// the players are paired up in order into duos that are each searched on their own.
+    #[arg(short, long, value_enum)]
+    game_mode: Option<GameModeArg>,
+
+    /// Match comps by skinset or by universe, instead of the team file's match mode.
+    #[arg(short, long, value_enum)]
+    match_mode: Option<MatchModeArg>,
+
+    /// The skinset table to search, instead of the team file's.
+    #[arg(short, long, value_enum)]
+    source: Option<SourceArg>,
+
+    /// Only search champs in this preference tier or a more preferred one, instead of the team file's minimum tier.
+    #[arg(short = 't', long, value_enum)]
+    min_tier: Option<TierArg>,
+
+    /// Also find comps where only this many of the players share a skinset, instead of the team file's minimum. The
+    /// other players are marked as off-theme.
+    #[arg(short = 'k', long, value_name = "PLAYERS")]
+    min_themed: Option<usize>,
+
+    /// Print at most this many comps (for each duo, in Arena).
+    #[arg(short, long)]
+    limit: Option<usize>,
+}
+
+/// Output formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// A human-readable table.
+    Table,
+    /// A JSON document.
+    Json,
+    /// Comma-separated values, with a champ and lane column for each player.
+    Csv,
+}
+
+/// Command line names for [GameMode].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum GameModeArg {
+    /// Summoner's Rift, with one player per lane.
+    SummonersRift,
+    /// ARAM, without lanes.
+    Aram,
+    /// Arena, without lanes.
+    Arena,
+}
+
+impl From<GameModeArg> for GameMode {
+    fn from(arg: GameModeArg) -> Self {
+        match arg {
+            GameModeArg::SummonersRift => GameMode::SummonersRift,
+            GameModeArg::Aram => GameMode::Aram,
+            GameModeArg::Arena => GameMode::Arena,
+        }
+    }
+}
+
+/// Command line names for [MatchMode].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum MatchModeArg {
+    /// Every champ has a skin in the same skinset.
+    Skinset,
+    /// Every champ has a skin in the same universe.
+    Universe,
+}
+
+impl From<MatchModeArg> for MatchMode {
+    fn from(arg: MatchModeArg) -> Self {
+        match arg {
+            MatchModeArg::Skinset => MatchMode::Skinset,
+            MatchModeArg::Universe => MatchMode::Universe,
+        }
+    }
+}
+
+/// Command line names for [SkinsetSource].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum SourceArg {
+    /// The community-maintained skinsets from the wiki.
+    Wiki,
+    /// Riot's official skin lines.
+    Official,
+}
+
+impl From<SourceArg> for SkinsetSource {
+    fn from(arg: SourceArg) -> Self {
+        match arg {
+            SourceArg::Wiki => SkinsetSource::Wiki,
+            SourceArg::Official => SkinsetSource::Official,
+        }
+    }
+}
+
+/// Command line names for [PreferenceTier].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum TierArg {
+    /// Only the players' mains.
+    Main,
+    /// Mains and champs the players are comfortable on.
+    Comfortable,
+    /// Every champ.
+    Willing,
+}
+
+impl From<TierArg> for PreferenceTier {
+    fn from(arg: TierArg) -> Self {
+        match arg {
+            TierArg::Main => PreferenceTier::Main,
+            TierArg::Comfortable => PreferenceTier::Comfortable,
+            TierArg::Willing => PreferenceTier::Willing,
+        }
+    }
+}
+
+/// The results of a search, as printed in JSON.
+#[derive(Serialize)]
+struct Report {
+    /// Whether [CompRecord::shared] lists skinsets or universes.
+    match_mode: MatchMode,
+    /// The comps found for each team that was searched -- the whole team, or each duo in Arena.
+    teams: Vec<TeamReport>,
+}
+
+/// The comps found for one team.
+#[derive(Serialize)]
+struct TeamReport {
+    /// The name of each player on the team, in the same order as each comp's picks.
+    players: Vec<String>,
+    /// The comps found.
+    comps: Vec<CompRecord>,
+}
+
+/// A comp found by the solver, with names resolved for printing.
+#[derive(Serialize)]
+struct CompRecord {
+    /// The champ and lane for each player.
+    picks: Vec<PickRecord>,
+    /// The names of the skinsets (or universes) shared by at least some of the players.
+    shared: Vec<&'static str>,
+    /// The number of players in the largest group sharing a skinset.
+    themed_players: usize,
+}
+
+/// A player's champ and lane in a comp.
+#[derive(Serialize)]
+struct PickRecord {
+    /// The name of the champ.
+    champ: &'static str,
+    /// The lane the champ is played in, or `None` in game modes without lanes.
+    lane: Option<Lane>,
+    /// Whether the champ doesn't share a skinset with enough of the others.
+    off_theme: bool,
+}
+
+/// Look up a skinset passed on the command line by its exact name.
+fn parse_skinset(name: &str) -> anyhow::Result<SkinsetId> {
+    SkinsetId::from_name(name).ok_or_else(|| anyhow!("unknown skinset {name:?}"))
+}
+
+/// Read the team file, from stdin if the path is `-`.
+fn read_team_file(path: &Path) -> anyhow::Result<String> {
+    if path.as_os_str() == "-" {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents).context("failed to read the team file from stdin")?;
+        Ok(contents)
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+    }
+}
+
+/// Quote a CSV field if it contains anything that would need escaping.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Print each team in the report, headed by the duo it's for if there's more than one team.
+fn write_teams(
+    out: &mut impl Write,
+    report: &Report,
+    write_team: impl Fn(&mut dyn Write, MatchMode, &TeamReport) -> io::Result<()>,
+) -> io::Result<()> {
+    for (index, team) in report.teams.iter().enumerate() {
+        if report.teams.len() > 1 {
+            if index > 0 {
+                writeln!(out)?;
+            }
+
+            writeln!(out, "Duo {}", index + 1)?;
+        }
+
+        write_team(out, report.match_mode, team)?;
+    }
+
+    Ok(())
+}
+
+/// Print a team's comps as a table, with columns padded to line up.
+fn write_table(out: &mut dyn Write, match_mode: MatchMode, report: &TeamReport) -> io::Result<()> {
+    let shared_header = match match_mode {
+        MatchMode::Skinset => "Shared skinsets",
+        MatchMode::Universe => "Shared universes",
+    };
+
+    // Build every row's cells first so that the column widths can be measured.
+    let header: Vec<String> = report.players.iter().cloned().chain([shared_header.to_owned()]).collect();
+    let rows: Vec<Vec<String>> = report
+        .comps
+        .iter()
+        .map(|comp| {
+            comp.picks
+                .iter()
+                .map(|pick| {
+                    let off_theme = if pick.off_theme { " (off-theme)" } else { "" };
+                    match pick.lane {
+                        Some(lane) => format!("{} {lane}{off_theme}", pick.champ),
+                        None => format!("{}{off_theme}", pick.champ),
+                    }
+                })
+                .chain([comp.shared.join(", ")])
+                .collect()
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    for row in std::iter::once(&header).chain(&rows) {
+        let cells: Vec<String> = row.iter().zip(&widths).map(|(cell, width)| format!("{cell:width$}")).collect();
+        writeln!(out, "{}", cells.join(" | ").trim_end())?;
+    }
+
+    Ok(())
+}
+
+/// Print a team's comps as CSV, with a champ and lane column for each player.
+fn write_csv(out: &mut dyn Write, match_mode: MatchMode, report: &TeamReport) -> io::Result<()> {
+    let shared_header = match match_mode {
+        MatchMode::Skinset => "shared skinsets",
+        MatchMode::Universe => "shared universes",
+    };
+
+    let header: Vec<String> = report
+        .players
+        .iter()
+        .flat_map(|player| [format!("{player} champ"), format!("{player} lane")])
+        .chain([shared_header.to_owned()])
+        .collect();
+
+    writeln!(out, "{}", header.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","))?;
+
+    for comp in &report.comps {
+        let fields: Vec<String> = comp
+            .picks
+            .iter()
+            .flat_map(|pick| [pick.champ.to_owned(), pick.lane.map(|lane| lane.to_string()).unwrap_or_default()])
+            .chain([comp.shared.join("; ")])
+            .collect();
+
+        writeln!(out, "{}", fields.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","))?;
+    }
+
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    // Import the team file, warning about any entries that don't match the current data.
+    let json = read_team_file(&args.team_file)?;
+    let (saved_state, problems) = SavedState::from_team_file(&json).context("failed to import the team file")?;
+
+    for problem in &problems {
+        eprintln!("warning: {problem}");
+    }
+
+    // Apply the command line options over the team file's.
+    let game_mode = args.game_mode.map(GameMode::from).unwrap_or(saved_state.game_mode);
+    let match_mode = args.match_mode.map(MatchMode::from).unwrap_or(saved_state.match_mode);
+    let skinset_source = args.source.map(SkinsetSource::from).unwrap_or(saved_state.skinset_source);
+    let min_tier = args.min_tier.map(PreferenceTier::from).unwrap_or(saved_state.min_preference_tier);
+    let min_themed_players = args.min_themed.or(saved_state.min_themed_players);
+
+    let mut skinsets_excluded = if args.include_all { SkinsetMask::EMPTY } else { saved_state.skinsets_excluded };
+
+    for name in &args.included {
+        skinsets_excluded.remove(parse_skinset(name)?);
+    }
+
+    for name in &args.excluded {
+        skinsets_excluded.insert(parse_skinset(name)?);
+    }
+
+    let player_names: Vec<String> = saved_state
+        .players
+        .iter()
+        .enumerate()
+        .map(|(index, player)| player.name.clone().unwrap_or_else(|| format!("Player {}", index + 1)))
+        .collect();
+
+    let mut teams = Vec::new();
+
+    // Find the comps for each team (the whole team, or each duo in Arena), resolving the names of everything in them.
+    for team in game_mode.teams(saved_state.players.len()) {
+        let mut owned_pools: Vec<Vec<_>> =
+            saved_state.players[team.clone()].iter().map(|player| player.champ_pool(min_tier)).collect();
+
+        if !game_mode.has_lanes() {
+            solver::ignore_lanes(&mut owned_pools)?;
+        }
+
+        let champ_pools: Vec<&[(ChampId, _)]> = owned_pools.iter().map(Vec::as_slice).collect();
+
+        // Comps with more players in theme come first.
+        let comps: Vec<CompRecord> =
+            solver::resolve_themed_comps(&champ_pools, skinsets_excluded, match_mode, skinset_source, min_themed_players)?
+                .take(args.limit.unwrap_or(usize::MAX))
+                .map(|(comp, themes)| {
+                    let shared = themes.shared();
+
+                    CompRecord {
+                        picks: comp
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, (champ_id, lane))| PickRecord {
+                                champ: champ_id.champ_name(),
+                                lane: game_mode.has_lanes().then_some(lane),
+                                off_theme: !themes.is_in_theme(index),
+                            })
+                            .collect(),
+                        shared: match match_mode {
+                            MatchMode::Skinset => shared.iter().map(SkinsetId::skinset_name).collect(),
+                            MatchMode::Universe => shared
+                                .universes(skinset_source)
+                                .into_iter()
+                                .map(UniverseId::universe_name)
+                                .collect(),
+                        },
+                        themed_players: themes.largest_group(),
+                    }
+                })
+                .collect();
+
+        teams.push(TeamReport { players: player_names[team].to_vec(), comps });
+    }
+
+    let report = Report { match_mode, teams };
+
+    if report.teams.iter().all(|team| team.comps.is_empty()) {
+        eprintln!("No comps found");
+    }
+
+    let mut out = io::stdout().lock();
+
+    match args.format {
+        Format::Table => write_teams(&mut out, &report, write_table)?,
+        Format::Csv => write_teams(&mut out, &report, write_csv)?,
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut out, &report)?;
+            writeln!(out)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+//! Local HTTP server for the JSON API in [league_skinset_finder::api].
+
+use anyhow::anyhow;
+use clap::Parser;
+use league_skinset_finder::api;
+use tiny_http::Server;
+
+/// Serve a JSON API for finding League of Legends team comps that share skinsets.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// The address to listen on.
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    address: String,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let server = Server::http(&args.address).map_err(|err| anyhow!("failed to listen on {}: {err}", args.address))?;
+    eprintln!("Listening on http://{}", server.server_addr());
+
+    api::serve(&server);
+    Ok(())
+}
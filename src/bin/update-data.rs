@@ -0,0 +1,211 @@
+//! Command line tool to refresh the data tables in `assets/` from saved copies of the wiki pages they come from.
+//!
+//! Save these pages from the browser (the whole page, not just the table) and pass them in:
+//!
+//! - <https://leagueoflegends.fandom.com/wiki/Champion_skin/Skin_themes> for the wiki and official skinset tables.
+//! - <https://leagueoflegends.fandom.com/wiki/List_of_champions_by_draft_position> for the champ lanes table.
+//!
+//! Each table is cut out of its page and re-serialized on its own, so the asset files only change when the table
+//! does. The changes from the old tables are saved next to them (replacing the ones from the last update), so that
+//! the web app can show what's new. Then rebuild to regenerate the data compiled into the app.
+
+use anyhow::{bail, Context};
+use clap::Parser;
+use league_skinset_finder::data_diff::{DataDiff, Snapshot};
+use league_skinset_finder::data_metadata::{format_date_long, parse_date, DataMetadata, METADATA_PATH};
+use scraper::{ElementRef, Html, Selector};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The header cells of the skinset tables.
+const SKINSET_TABLE_HEADERS: [&str; 3] = ["Universe Name", "Set Name", "Skins"];
+
+/// Regenerate the data tables in `assets/` from saved wiki pages.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// The saved "Champion skin/Skin themes" page.
+    #[arg(long, value_name = "HTML_FILE")]
+    skin_themes: PathBuf,
+
+    /// The saved "List of champions by draft position" page.
+    #[arg(long, value_name = "HTML_FILE")]
+    draft_positions: PathBuf,
+
+    /// The date the pages were saved, as YYYY-MM-DD. Defaults to today (in UTC).
+    #[arg(long)]
+    date: Option<String>,
+
+    /// The directory to write the data tables to.
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/assets"))]
+    assets_dir: PathBuf,
+}
+
+/// Get today's date in UTC as YYYY-MM-DD.
+fn today() -> String {
+    let days = SystemTime::now().duration_since(UNIX_EPOCH).expect("clock is after 1970").as_secs() / 86_400;
+
+    // Convert days since 1970-01-01 to a civil date (Howard Hinnant's `civil_from_days`), shifting the epoch to
+    // 0000-03-01 so that leap days fall at the end of each 400 year era.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = era * 400 + year_of_era + (month <= 2) as u64;
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Read and parse a saved page.
+fn read_page(path: &Path) -> anyhow::Result<Html> {
+    let html = std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(Html::parse_document(&html))
+}
+
+/// Get the trimmed text of an element.
+fn text_of(element: ElementRef) -> String {
+    element.text().collect::<String>().trim().to_owned()
+}
+
+/// Get the text of the header cells in the first row of a table.
+fn header_texts(table: ElementRef) -> Vec<String> {
+    let row_selector = Selector::parse("tr").expect("row selector good");
+    let header_selector = Selector::parse("th").expect("header selector good");
+
+    table
+        .select(&row_selector)
+        .next()
+        .map(|row| row.select(&header_selector).map(text_of).collect())
+        .unwrap_or_default()
+}
+
+/// Find the two skinset tables in the skin themes page, returning the (wiki, official) tables.
+///
+/// Tables are told apart by the nearest heading before them -- the official skin lines are under a heading that
+/// mentions them, and the community skinsets aren't. If the headings don't tell them apart, the community table is
+/// assumed to come first, as it does on the page.
+fn find_skinset_tables(page: &Html) -> anyhow::Result<(ElementRef<'_>, ElementRef<'_>)> {
+    // Walk the page in order, keeping track of the latest heading.
+    let mut heading = String::new();
+    let mut tables: Vec<(String, ElementRef)> = Vec::new();
+
+    for element in page.root_element().descendants().filter_map(ElementRef::wrap) {
+        match element.value().name() {
+            "h1" | "h2" | "h3" | "h4" => heading = text_of(element),
+            "table" if header_texts(element) == SKINSET_TABLE_HEADERS => tables.push((heading.clone(), element)),
+            _ => {}
+        }
+    }
+
+    let [(first_heading, first), (second_heading, second)] = tables.as_slice() else {
+        bail!("expected 2 skinset tables in the skin themes page, found {}", tables.len());
+    };
+
+    let is_official = |heading: &str| heading.to_lowercase().contains("official");
+
+    let (wiki, official) = match (is_official(first_heading), is_official(second_heading)) {
+        (true, false) => (*second, *first),
+        (false, true) => (*first, *second),
+        _ => {
+            eprintln!(
+                "warning: couldn't tell the skinset tables apart by their headings ({first_heading:?} and \
+                {second_heading:?}), so the first is used as the wiki table"
+            );
+            (*first, *second)
+        }
+    };
+
+    Ok((wiki, official))
+}
+
+/// Find the champ lanes table in the draft positions page.
+fn find_lanes_table(page: &Html) -> anyhow::Result<ElementRef<'_>> {
+    let table_selector = Selector::parse("table").expect("table selector good");
+
+    page.select(&table_selector)
+        .find(|table| header_texts(*table).first().map(String::as_str) == Some("Champion"))
+        .context("couldn't find the champion lanes table in the draft positions page")
+}
+
+/// Check that a table has at least one row after its header, so an empty table is never written.
+fn check_has_rows(table: ElementRef, name: &str) -> anyhow::Result<()> {
+    let row_selector = Selector::parse("tr").expect("row selector good");
+    let row_count = table.select(&row_selector).count();
+
+    if row_count < 2 {
+        bail!("the {name} table has no rows");
+    }
+
+    println!("Found the {name} table ({} rows)", row_count - 1);
+    Ok(())
+}
+
+/// Write a table to a file in the assets directory.
+fn write_table(assets_dir: &Path, file_name: &str, table: ElementRef) -> anyhow::Result<()> {
+    let path = assets_dir.join(file_name);
+    std::fs::write(&path, format!("{}\n", table.html())).with_context(|| format!("failed to write {}", path.display()))
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    // Check the date before doing anything else.
+    let source_date = args.date.unwrap_or_else(today);
+    if parse_date(&source_date).is_none() {
+        bail!("invalid date {source_date:?} (expected YYYY-MM-DD)");
+    }
+
+    // Find the tables in the pages.
+    let skin_themes = read_page(&args.skin_themes)?;
+    let (wiki_table, official_table) = find_skinset_tables(&skin_themes)?;
+    check_has_rows(wiki_table, "wiki skinset")?;
+    check_has_rows(official_table, "official skinset")?;
+
+    let draft_positions = read_page(&args.draft_positions)?;
+    let lanes_table = find_lanes_table(&draft_positions)?;
+    check_has_rows(lanes_table, "champion lanes")?;
+
+    // Read the old tables to compare the new ones to. Without them, the changes from the last update would be shown
+    // under the new date, so they get removed instead.
+    let old_snapshot = Snapshot::read(&args.assets_dir)
+        .map_err(|err| eprintln!("Not saving the data changes, since the old tables can't be read: {err:#}"))
+        .ok();
+
+    // Write the tables and the metadata.
+    write_table(&args.assets_dir, "wiki-sets-table.html", wiki_table)?;
+    write_table(&args.assets_dir, "official-sets-table.html", official_table)?;
+    write_table(&args.assets_dir, "champ-lanes-table.html", lanes_table)?;
+
+    let metadata_file = Path::new(METADATA_PATH).file_name().expect("metadata path has a file name");
+    let metadata_path = args.assets_dir.join(metadata_file);
+    let metadata = DataMetadata { source_date };
+    std::fs::write(&metadata_path, format!("{}\n", serde_json::to_string_pretty(&metadata)?))
+        .with_context(|| format!("failed to write {}", metadata_path.display()))?;
+
+    match old_snapshot {
+        Some(old_snapshot) => {
+            let new_snapshot = Snapshot::read(&args.assets_dir).context("failed to read the new tables")?;
+            let diff = DataDiff::compare(&old_snapshot, &new_snapshot);
+            print!("{}", diff.to_markdown());
+            println!("Saved the changes to {}.", diff.save(&args.assets_dir)?.display());
+        }
+
+        None => {
+            if DataDiff::remove_saved(&args.assets_dir)? {
+                println!("Removed the changes from the last update.");
+            }
+        }
+    }
+
+    println!(
+        "Updated the data tables in {} (from {}). Rebuild to use the new data.",
+        args.assets_dir.display(),
+        format_date_long(&metadata.source_date).expect("date was checked"),
+    );
+
+    Ok(())
+}
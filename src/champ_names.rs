@@ -0,0 +1,61 @@
+//! Champion name normalization, shared between the build script (to join the data tables by champ name) and the web
+//! app (to look champs up by name).
+
+/// Alternate spellings of champ names, as (alias, canonical name from the lane table). Names that only differ by case,
+/// spacing, or punctuation don't need an entry here since [normalize_champ_name] already handles them.
+pub const CHAMP_ALIASES: &[(&str, &str)] = &[
+    // Riot's internal name for Wukong, used by Data Dragon.
+    ("MonkeyKing", "Wukong"),
+    ("Nunu", "Nunu & Willump"),
+    ("Nunu and Willump", "Nunu & Willump"),
+    ("Willump", "Nunu & Willump"),
+    ("Renata", "Renata Glasc"),
+    ("Mundo", "Dr. Mundo"),
+];
+
+/// Normalize a champ name so that names only differing by case, spacing, or punctuation (e.g. "Kog'Maw", "KogMaw",
+/// and "kog maw") compare equal.
+pub fn normalize_champ_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Get the key that identifies a champ across tables: their normalized name, with aliases resolved to the canonical
+/// name so that e.g. "MonkeyKing" and "Wukong" get the same key.
+pub fn champ_key(name: &str) -> String {
+    let normalized = normalize_champ_name(name);
+
+    CHAMP_ALIASES
+        .iter()
+        .find(|(alias, _)| normalize_champ_name(alias) == normalized)
+        .map(|(_, canonical)| normalize_champ_name(canonical))
+        .unwrap_or(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_case_spacing_and_punctuation() {
+        for name in ["Kog'Maw", "KogMaw", "kog maw", "KOG-MAW"] {
+            assert_eq!(normalize_champ_name(name), "kogmaw");
+        }
+
+        assert_eq!(normalize_champ_name("Nunu & Willump"), "nunuwillump");
+        assert_eq!(normalize_champ_name("Dr. Mundo"), "drmundo");
+    }
+
+    #[test]
+    fn resolves_aliases_to_the_canonical_name() {
+        assert_eq!(champ_key("MonkeyKing"), champ_key("Wukong"));
+        assert_eq!(champ_key("monkey king"), "wukong");
+        assert_eq!(champ_key("Nunu and Willump"), champ_key("Nunu & Willump"));
+        assert_eq!(champ_key("Mundo"), "drmundo");
+
+        // Names that aren't aliases are just normalized.
+        assert_eq!(champ_key("Kog'Maw"), "kogmaw");
+    }
+}
@@ -4,10 +4,16 @@
 mod app;
 mod button;
 mod checkbox;
+mod lane_overrides;
 mod link;
-// mod player;
-// mod results_table;
+mod matchup_table;
+mod player;
+mod preset_manager;
+mod results_table;
+mod search_options;
 mod skinset_list;
+mod team_file;
+mod whats_new;
 
 /// Re-export the App itself. 
 pub use app::App;
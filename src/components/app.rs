@@ -1,63 +1,28 @@
 //! The root App component that contains the entirety of the frontend. 
 //! 
 
-use crate::constants::{Lane, SkinsetId, ChampId};
+use crate::constants::{Lane, SkinsetId, ChampId, SkinsetMask, SkinsetSource, DATA_SOURCE_DATE_LONG};
 use enumflags2::BitFlags;
+use super::lane_overrides::LaneOverridesEditor;
 use super::link::Link;
+use super::matchup_table::MatchupTable;
 use super::button::Button;
-// use super::player::Player;
-// use super::results_table::ResultsTable;
-use serde::{Deserialize, Serialize};
+use super::player::Player;
+use super::preset_manager::PresetManager;
+use super::results_table::ResultsTable;
+use super::search_options::SearchOptions;
+use super::team_file::TeamFile;
+use crate::lane_overrides::LaneOverrides;
+use crate::persist::{ImportProblem, PlayerRecord, PreferenceTier, SavedState, SchemaVersion};
+use crate::presets::{BlacklistPreset, ChampPoolPreset, Presets};
+use crate::scoring::ScoreWeights;
+use gloo_file::{callbacks::FileReader, Blob};
+use crate::solver::{GameMode, MatchMode};
+use crate::teams::TeamSetup;
 use super::skinset_list::SkinsetList;
+use super::whats_new::WhatsNew;
 use yew::prelude::*;
-// use yew_icons::{Icon, IconId};
-
-/// State persisted for each player in the frontend.
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct PlayerRecord {
-    /// Player name (optional -- resolve with player number otherwise).
-    pub name: Option<AttrValue>,
-
-    /// List of champs and what lanes for them. This is in the order that they're in in the UI. 
-    pub champs: Vec<(ChampId, BitFlags<Lane>)>,
-}
-
-impl PlayerRecord {
-    /// Create a new player with a given number and otherwise empty fields.
-    pub fn new() -> Self {
-        Self {
-            name: None,
-            champs: Vec::with_capacity(170),
-        }
-    }
-
-    /// Update the lanes for a champ for this player. 
-    fn update_champ(&mut self, champ_id: ChampId, lanes: BitFlags<Lane>) {
-        // Find the champ id to update if they're in this list already. 
-        self.champs
-            .iter()
-            .enumerate()
-            .find(|(_, (iter_champ_id, _))| *iter_champ_id == champ_id)
-            .map(|(index, _)| index)
-            // Once the index is found, update the lanes for the champ. 
-            .map(|index| self.champs[index].1 = lanes);
-    }
-
-    /// Remove a champ on this player.
-    fn remove_champ(&mut self, champ_id: ChampId) {
-        // Remove the champ if they already were in the list. 
-        let remove_index = self.champs
-            .iter()
-            .enumerate()
-            .find(|(_, (iter_champ_id, _))| *iter_champ_id == champ_id)
-            .map(|(index, _)| index);
-        
-        if let Some(index) = remove_index {
-            // Just use remove here to maintain order.
-            self.champs.remove(index);
-        }
-    }
-}
+use yew_icons::{Icon, IconId};
 
 /// Messages that can be passed to the top-level app.
 #[derive(Debug)]
@@ -86,37 +51,293 @@ pub enum AppMsg {
         skinset_id: SkinsetId,
     },
 
-    // /// Add or update a champ for a player. 
-    // UpsertChampOnPlayer {
-    //     /// The index of the player to update
-    //     player_index: usize, 
-    //     /// The champ ID to update.
-    //     champ_id: ChampId,
-    //     /// The new lanes for the champ (or default lanes). 
-    //     lanes: BitFlags<Lane>
-    // },
-
-    // /// Remove a champion from a player. 
-    // RemoveChampFromPlayer {
-    //     /// The index of the player to update
-    //     player_index: usize,
-    //     /// The champ id to remove. 
-    //     champ_id: ChampId,
-    // },
-
-    // /// Toggle the exclusion of a skinset by id. 
-    // ToggleSkinsetExclusion {
-    //     skinset_id: SkinsetId
-    // }
+    /// Toggle whether a skinset is pinned as a favourite. 
+    ToggleFavouriteSkinset {
+        /// The id of the skinset to toggle. 
+        skinset_id: SkinsetId,
+    },
+
+    /// Add a champ to a player, with their lanes after any lane overrides. 
+    AddChampToPlayer {
+        /// The index of the player to update.
+        player_index: usize,
+        /// The champ to add.
+        champ_id: ChampId,
+    },
+
+    /// Add or update a champ for a player. 
+    UpsertChampOnPlayer {
+        /// The index of the player to update
+        player_index: usize, 
+        /// The champ ID to update.
+        champ_id: ChampId,
+        /// The new lanes for the champ (or default lanes). 
+        lanes: BitFlags<Lane>
+    },
+
+    /// Change how much a player wants to play one of their champs. 
+    SetChampTier {
+        /// The index of the player to update.
+        player_index: usize,
+        /// The champ to update.
+        champ_id: ChampId,
+        /// The new preference tier for the champ.
+        tier: PreferenceTier,
+    },
+
+    /// Swap out one of a player's champs for a different champ. 
+    ReplaceChampOnPlayer {
+        /// The index of the player to update.
+        player_index: usize,
+        /// The champ currently selected.
+        old_champ_id: ChampId,
+        /// The champ to replace them with.
+        new_champ_id: ChampId,
+    },
+
+    /// Remove a champion from a player. 
+    RemoveChampFromPlayer {
+        /// The index of the player to update
+        player_index: usize,
+        /// The champ id to remove. 
+        champ_id: ChampId,
+    },
+
+    /// Change what comps have to share to be considered themed. 
+    SetMatchMode {
+        /// The new match mode.
+        match_mode: MatchMode,
+    },
+
+    /// Change which skinset table comps are searched with. 
+    SetSkinsetSource {
+        /// The new skinset table.
+        skinset_source: SkinsetSource,
+    },
+
+    /// Change the least preferred tier of champ that comps are searched with. 
+    SetMinPreferenceTier {
+        /// The new minimum tier.
+        min_preference_tier: PreferenceTier,
+    },
+
+    /// Change whether the players are planned as one team or split into two. 
+    SetTeamSetup {
+        /// The new team setup.
+        team_setup: TeamSetup,
+    },
+
+    /// Change the game mode comps are found for. 
+    SetGameMode {
+        /// The new game mode.
+        game_mode: GameMode,
+    },
+
+    /// Change the fewest players that have to share a skinset in each comp. 
+    SetMinThemedPlayers {
+        /// The new minimum, or `None` if every player has to share a skinset.
+        min_themed_players: Option<usize>,
+    },
+
+    /// Change how much each part of a comp's score counts. 
+    SetScoreWeights {
+        /// The new weights.
+        score_weights: ScoreWeights,
+    },
+
+    /// Override the lanes a champ is added to players with. 
+    SetLaneOverride {
+        /// The champ to override.
+        champ_id: ChampId,
+        /// The lanes to add them with.
+        lanes: BitFlags<Lane>,
+    },
+
+    /// Remove a champ's lane override, so that they're added with their default lanes again. 
+    RemoveLaneOverride {
+        /// The champ whose override to remove.
+        champ_id: ChampId,
+    },
+
+    /// Reset the players and search options to their defaults. 
+    ResetToDefaults,
+
+    /// Hide the notice shown when the saved state couldn't be loaded. 
+    DismissLoadError,
+
+    /// Start reading a team file to import. 
+    ImportTeamFile {
+        /// The file chosen to import.
+        file: web_sys::File,
+    },
+
+    /// A team file being imported has been read. 
+    TeamFileRead {
+        /// The contents of the file, or the error reading it.
+        contents: Result<String, String>,
+    },
+
+    /// Hide the list of problems from the last import. 
+    DismissImportProblems,
+
+    /// Replace a player's champs with a champ pool preset. 
+    ApplyChampPool {
+        /// The index of the player to update.
+        player_index: usize,
+        /// The champ pool to use.
+        preset: ChampPoolPreset,
+    },
+
+    /// Save a player's champs as a champ pool preset. 
+    SaveChampPool {
+        /// The index of the player whose champs to save.
+        player_index: usize,
+        /// The name of the preset.
+        name: String,
+    },
+
+    /// Delete a saved champ pool preset. 
+    DeleteChampPool {
+        /// The name of the preset.
+        name: String,
+    },
+
+    /// Exclude the skinsets in a blacklist preset (in addition to the ones already excluded). 
+    ApplyBlacklist {
+        /// The blacklist to exclude.
+        preset: BlacklistPreset,
+    },
+
+    /// Save the excluded skinsets as a blacklist preset. 
+    SaveBlacklist {
+        /// The name of the preset.
+        name: String,
+    },
+
+    /// Delete a saved blacklist preset. 
+    DeleteBlacklist {
+        /// The name of the preset.
+        name: String,
+    },
 }
 
 /// The main component that the frontend is rendered as.
 #[derive(Debug)]
 pub struct App {
-    /// The five players (max) in the league comp.
+    /// The players in the league comp -- up to [TEAM_SIZE](crate::teams::TEAM_SIZE), or twice that when planning two
+    /// teams.
     pub players: Vec<PlayerRecord>,
-    /// The list of skins excluded from consideration. This should stay sorted and deduplicated. 
-    pub skinsets_excluded: Vec<SkinsetId>,
+    /// The set of skinsets excluded from consideration. 
+    pub skinsets_excluded: SkinsetMask,
+    /// Whether comps are matched by skinset or by universe. 
+    pub match_mode: MatchMode,
+    /// Whether the wiki or official skinset table is used. 
+    pub skinset_source: SkinsetSource,
+    /// The lanes added to or removed from champs' default lanes when they're added to a player. 
+    pub lane_overrides: LaneOverrides,
+    /// The skinsets pinned as favourites, which comps score higher for sharing. 
+    pub favourite_skinsets: SkinsetMask,
+    /// How much each part of a comp's score counts. 
+    pub score_weights: ScoreWeights,
+    /// The least preferred tier of champ that comps are searched with. 
+    pub min_preference_tier: PreferenceTier,
+    /// The fewest players that have to share a skinset in each comp, or `None` if every player has to. 
+    pub min_themed_players: Option<usize>,
+    /// The game mode comps are found for. 
+    pub game_mode: GameMode,
+    /// Whether the players are planned as one team or split into two. 
+    pub team_setup: TeamSetup,
+    /// A message explaining why the saved state, shared link, or team file couldn't be loaded, if it couldn't be. 
+    pub load_error: Option<String>,
+    /// The entries skipped during the last team file import. 
+    pub import_problems: Vec<ImportProblem>,
+    /// The reader for a team file being imported -- the read is cancelled if this is dropped. 
+    pub file_reader: Option<FileReader>,
+    /// The champ pool and blacklist presets the user has saved. 
+    pub presets: Presets,
+}
+
+impl App {
+    /// Make an app with the default state -- one empty player and the default skinsets excluded. 
+    fn with_defaults() -> Self {
+        // Create the list of players stored in this app.
+        let mut players = Vec::with_capacity(5);
+        // Add the default player.
+        players.push(PlayerRecord::new());
+
+        App {
+            players,
+            skinsets_excluded: SkinsetMask::from_skinsets(SkinsetId::DEFAULT_EXCLUDED_SKINSETS),
+            match_mode: MatchMode::default(),
+            skinset_source: SkinsetSource::default(),
+            lane_overrides: LaneOverrides::default(),
+            favourite_skinsets: SkinsetMask::EMPTY,
+            score_weights: ScoreWeights::default(),
+            min_preference_tier: PreferenceTier::least(),
+            min_themed_players: None,
+            game_mode: GameMode::default(),
+            team_setup: TeamSetup::default(),
+            load_error: None,
+            import_problems: Vec::new(),
+            file_reader: None,
+            presets: Presets::default(),
+        }
+    }
+
+    /// Get the state of this app to save. 
+    fn saved_state(&self) -> SavedState {
+        SavedState {
+            version: SchemaVersion,
+            players: self.players.clone(),
+            skinsets_excluded: self.skinsets_excluded,
+            match_mode: self.match_mode,
+            skinset_source: self.skinset_source,
+            lane_overrides: self.lane_overrides.clone(),
+            favourite_skinsets: self.favourite_skinsets,
+            score_weights: self.score_weights,
+            min_preference_tier: self.min_preference_tier,
+            min_themed_players: self.min_themed_players,
+            game_mode: self.game_mode,
+            team_setup: self.team_setup,
+        }
+    }
+
+    /// Restore the state saved in local storage, falling back to the defaults if it can't be loaded.
+    fn restore_saved(&mut self) {
+        match SavedState::load() {
+            Ok(Some(saved_state)) => self.restore(saved_state),
+            Ok(None) => {}
+            Err(err) => {
+                // Keep a copy of the unreadable state so it isn't lost.
+                log::warn!("Failed to load saved app state: {err}");
+                SavedState::back_up_unreadable();
+                self.load_error = Some(format!(
+                    "Your saved team couldn't be loaded, so the defaults are being used instead ({err})."
+                ));
+            }
+        }
+    }
+
+    /// Replace the state of this app with a saved state. 
+    fn restore(&mut self, saved_state: SavedState) {
+        self.players = saved_state.players;
+        self.skinsets_excluded = saved_state.skinsets_excluded;
+        self.match_mode = saved_state.match_mode;
+        self.skinset_source = saved_state.skinset_source;
+        self.lane_overrides = saved_state.lane_overrides;
+        self.favourite_skinsets = saved_state.favourite_skinsets;
+        self.score_weights = saved_state.score_weights;
+        self.min_preference_tier = saved_state.min_preference_tier;
+        self.min_themed_players = saved_state.min_themed_players;
+        self.game_mode = saved_state.game_mode;
+        self.team_setup = saved_state.team_setup;
+
+        // Keep the player count in the range the UI allows, in case the saved state was edited by hand. 
+        self.players.truncate(self.team_setup.max_players());
+        if self.players.is_empty() {
+            self.players.push(PlayerRecord::new());
+        }
+    }
 }
 
 impl Component for App {
@@ -126,19 +347,36 @@ impl Component for App {
 
     fn create(_: &Context<Self>) -> Self {
         log::info!("App component created");
-        // Create the list of players stored in this app.
-        let mut players = Vec::with_capacity(5);
-        // Add the default player.
-        players.push(PlayerRecord::new());
+        let mut app = App::with_defaults();
+
+        // Load the user's saved presets, keeping a copy of them if they're unreadable so they aren't lost.
+        app.presets = Presets::load().unwrap_or_else(|err| {
+            log::warn!("Failed to load saved presets: {err}");
+            Presets::back_up_unreadable();
+            Presets::default()
+        });
+
+        // Restore the team from a shared link if the page was opened with one, otherwise restore the state saved 
+        // from the last visit, if there is one. 
+        match SavedState::from_location() {
+            Ok(Some(shared_state)) => {
+                // The shared team is saved over the one from the last visit, so keep a copy of that first.
+                SavedState::back_up_before_shared_link();
+                app.restore(shared_state);
+            }
+            Ok(None) => app.restore_saved(),
+            Err(err) => {
+                log::warn!("Failed to open shared link: {err}");
+                app.restore_saved();
+                app.load_error = Some(format!("The shared link couldn't be opened because {err}."));
+            }
+        }
 
         // Return
-        App {
-            players,
-            skinsets_excluded: Vec::from(SkinsetId::DEFAULT_EXCLUDED_SKINSETS),
-        }
+        app
     }
 
-    fn update(&mut self, _: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             // Handle a player updating their name.
             AppMsg::PlayerNameUpdate { index, new_name } => {
@@ -146,12 +384,12 @@ impl Component for App {
                 self.players[index].name = if new_name.is_empty() {
                     None
                 } else {
-                    Some(new_name.into())
+                    Some(new_name)
                 };
             }
 
             AppMsg::AddPlayer => {
-                if self.players.len() <= 5 {
+                if self.players.len() < self.team_setup.max_players() {
                     self.players.push(PlayerRecord::new())
                 }
             }
@@ -163,31 +401,161 @@ impl Component for App {
             }
 
             AppMsg::ExcludeAllSkinsets => {
-                self.skinsets_excluded = SkinsetId::iter_all().collect();
+                self.skinsets_excluded = SkinsetMask::ALL;
             }
 
             AppMsg::IncludeAllSkinsets => {
-                self.skinsets_excluded.clear();
+                self.skinsets_excluded = SkinsetMask::EMPTY;
             }
 
             AppMsg::ToggleSkinset { skinset_id } => {
-                match self.skinsets_excluded.binary_search(&skinset_id) {
-                    // If found, remove (maintaining order).
-                    Ok(index) => { self.skinsets_excluded.remove(index); },
-                    // If not found, insert (maintaining order).
-                    Err(index) => self.skinsets_excluded.insert(index, skinset_id),
+                self.skinsets_excluded.toggle(skinset_id);
+            }
+
+            AppMsg::ToggleFavouriteSkinset { skinset_id } => {
+                self.favourite_skinsets.toggle(skinset_id);
+            }
+
+            AppMsg::AddChampToPlayer { player_index, champ_id } => {
+                let lanes = self.lane_overrides.lanes_for(champ_id);
+                self.players[player_index].upsert_champ(champ_id, lanes);
+            }
+
+            AppMsg::UpsertChampOnPlayer { player_index, champ_id, lanes } => {
+                self.players[player_index].upsert_champ(champ_id, lanes);
+            }
+
+            AppMsg::SetChampTier { player_index, champ_id, tier } => {
+                self.players[player_index].set_tier(champ_id, tier);
+            }
+
+            AppMsg::ReplaceChampOnPlayer { player_index, old_champ_id, new_champ_id } => {
+                let lanes = self.lane_overrides.lanes_for(new_champ_id);
+                self.players[player_index].replace_champ(old_champ_id, new_champ_id, lanes);
+            }
+
+            AppMsg::RemoveChampFromPlayer { player_index, champ_id } => {
+                self.players[player_index].remove_champ(champ_id);
+            }
+
+            AppMsg::SetMatchMode { match_mode } => {
+                self.match_mode = match_mode;
+            }
+
+            AppMsg::SetSkinsetSource { skinset_source } => {
+                self.skinset_source = skinset_source;
+            }
+
+            AppMsg::SetMinPreferenceTier { min_preference_tier } => {
+                self.min_preference_tier = min_preference_tier;
+            }
+
+            AppMsg::SetTeamSetup { team_setup } => {
+                // Only go back to one team once the extra players have been removed.
+                if self.players.len() <= team_setup.max_players() {
+                    self.team_setup = team_setup;
+                }
+            }
+
+            AppMsg::SetGameMode { game_mode } => {
+                self.game_mode = game_mode;
+            }
+
+            AppMsg::SetMinThemedPlayers { min_themed_players } => {
+                self.min_themed_players = min_themed_players;
+            }
+
+            AppMsg::SetScoreWeights { score_weights } => {
+                self.score_weights = score_weights;
+            }
+
+            AppMsg::SetLaneOverride { champ_id, lanes } => {
+                self.lane_overrides.set(champ_id, lanes);
+            }
+
+            AppMsg::RemoveLaneOverride { champ_id } => {
+                self.lane_overrides.remove(champ_id);
+            }
+
+            AppMsg::ResetToDefaults => {
+                // Keep the user's saved presets.
+                let presets = std::mem::take(&mut self.presets);
+                *self = App::with_defaults();
+                self.presets = presets;
+            }
+
+            AppMsg::DismissLoadError => {
+                self.load_error = None;
+            }
+
+            AppMsg::ImportTeamFile { file } => {
+                // Read the file in the background and pass the contents back to this component.
+                let link = ctx.link().clone();
+                self.file_reader = Some(gloo_file::callbacks::read_as_text(&Blob::from(file), move |result| {
+                    link.send_message(AppMsg::TeamFileRead { contents: result.map_err(|err| err.to_string()) });
+                }));
+
+                // Nothing has changed until the file is read.
+                return false;
+            }
+
+            AppMsg::TeamFileRead { contents } => {
+                self.file_reader = None;
+
+                // Import the file, skipping (and listing) any entries that don't match the current data.
+                match contents.and_then(|json| SavedState::from_team_file(&json).map_err(|err| err.to_string())) {
+                    Ok((saved_state, problems)) => {
+                        self.restore(saved_state);
+                        self.load_error = None;
+                        self.import_problems = problems;
+                    }
+
+                    Err(err) => {
+                        log::warn!("Failed to import team file: {err}");
+                        self.load_error = Some(format!("The team file couldn't be imported ({err})."));
+                    }
                 }
             }
 
-            // AppMsg::UpsertChampOnPlayer { player_index, champ_id, lanes } => {
-            //     self.players[player_index].upsert_champ(champ_id, lanes);
-            // }
+            AppMsg::DismissImportProblems => {
+                self.import_problems.clear();
+            }
+
+            AppMsg::ApplyChampPool { player_index, preset } => {
+                self.players[player_index].champs = preset.champs;
+            }
 
-            // AppMsg::RemoveChampFromPlayer { player_index, champ_id } => {
+            AppMsg::SaveChampPool { player_index, name } => {
+                let champs = self.players[player_index].champs.clone();
+                self.presets.save_champ_pool(ChampPoolPreset { name, champs });
+                self.presets.save();
+            }
+
+            AppMsg::DeleteChampPool { name } => {
+                self.presets.delete_champ_pool(&name);
+                self.presets.save();
+            }
 
-            // }
+            AppMsg::ApplyBlacklist { preset } => {
+                self.skinsets_excluded |= preset.skinsets;
+            }
+
+            AppMsg::SaveBlacklist { name } => {
+                self.presets.save_blacklist(BlacklistPreset { name, skinsets: self.skinsets_excluded });
+                self.presets.save();
+            }
+
+            AppMsg::DeleteBlacklist { name } => {
+                self.presets.delete_blacklist(&name);
+                self.presets.save();
+            }
         }
 
+        // Save the updated state so that it survives a reload, and update the share link. 
+        let saved_state = self.saved_state();
+        saved_state.save();
+        saved_state.replace_location();
+
         // Log that we're re-rendering the root application. 
         log::info!("Re-rendering page");
         // Always return true to indicate the need for a re-render.
@@ -198,6 +566,13 @@ impl Component for App {
         // Resolve whether any players can be removed currently.
         let enable_player_removal = self.players.len() > 1;
 
+        // Resolve each player's name, using their number if they don't have one.
+        let player_names: Vec<AttrValue> = self.players
+            .iter()
+            .enumerate()
+            .map(|(id, player)| player.name.clone().unwrap_or_else(|| format!("Player {}", id + 1)).into())
+            .collect();
+
         html! {
             // Add a margin an padding to the bottom to force scroll bar to appear slightly sooner
             <div class={"pb-5 mb-5"}>
@@ -212,7 +587,13 @@ impl Component for App {
                             <Link href="https://leagueoflegends.fandom.com/wiki/List_of_champions_by_draft_position" open_in_new_tab={true} />
                             {"."}
                         </p>
-                        <p> {"Data was last updated from these sources on December 8th, 2023."} </p>
+                        <p> {"Data was last updated from these sources on "} {DATA_SOURCE_DATE_LONG} {"."} </p>
+                        <p>
+                            {"
+                            Your team is saved in this browser, and the link in your address bar always opens the current 
+                            team -- copy it to share your setup. 
+                            "}
+                        </p>
                         <p>
                             {"
                             I will try to keep this generally up to date with league skins and champions, but may not always
@@ -224,63 +605,213 @@ impl Component for App {
                     </div>
                 </div>
 
-                // Skinset list component will automatically reach up into this App through the dom tree and 
-                // get a reference to the "global" skinset exclusion list. 
-                <SkinsetList />
-
-                // {
-                //     self.players.iter()
-                //         .enumerate()
-                //         .map(|(id, player)| {
-                //             html! {
-                //                 <Player
-                //                     {id}
-                //                     name={player.name.clone()}
-                //                     champs={player.champs.clone()}
-
-                //                     on_name_change={
-                //                         ctx.link().callback(move |new_name| {
-                //                             Msg::PlayerNameUpdate { index: id, new_name }
-                //                         })
-                //                     }
-
-                //                     enable_remove={enable_player_removal}
-                //                     on_remove={
-                //                         ctx.link().callback(move |_| {
-                //                             Msg::RemovePlayer { player_index: id }
-                //                         })
-                //                     }
-
-                //                     on_champ_list_update={ ctx.link().callback(move |new_champ_list| Msg::PlayerChampListUpdate {
-                //                         player_id: id,
-                //                         new_champ_list
-                //                     }) }
-                //                 />
-                //             }
-                //         })
-                //         .collect::<Html>()
-                // }
+                // Changes from the last data update, if this build has them. 
+                <WhatsNew />
+
+                // Notice for when the saved state or shared link couldn't be loaded. 
+                if let Some(load_error) = self.load_error.clone() {
+                    <div class="alert alert-warning d-flex align-items-center my-2" role="alert">
+                        <span class="flex-grow-1"> {load_error} </span>
+                        <button type="button" class="btn-close" aria-label="Close" onclick={ ctx.link().callback(|_| AppMsg::DismissLoadError) }></button>
+                    </div>
+                }
+
+                // List of the entries skipped during the last import. 
+                if !self.import_problems.is_empty() {
+                    <div class="alert alert-warning d-flex align-items-start my-2" role="alert">
+                        <div class="flex-grow-1">
+                            {"Some entries in the team file didn't match the current data and were skipped:"}
+                            <ul class="mb-0">
+                                {
+                                    self.import_problems
+                                        .iter()
+                                        .map(|problem| html! { <li> {problem.to_string()} </li> })
+                                        .collect::<Html>()
+                                }
+                            </ul>
+                        </div>
+                        <button type="button" class="btn-close" aria-label="Close" onclick={ ctx.link().callback(|_| AppMsg::DismissImportProblems) }></button>
+                    </div>
+                }
+
+                // Skinset list component will automatically reach up into this App through the dom tree to update 
+                // the "global" skinset exclusion list. 
+                <SkinsetList
+                    skinset_source={self.skinset_source}
+                    skinsets_excluded={self.skinsets_excluded}
+                    favourite_skinsets={self.favourite_skinsets}
+                />
+
+                <SearchOptions
+                    game_mode={self.game_mode}
+                    on_game_mode_change={ ctx.link().callback(|game_mode| AppMsg::SetGameMode { game_mode }) }
+                    match_mode={self.match_mode}
+                    on_match_mode_change={ ctx.link().callback(|match_mode| AppMsg::SetMatchMode { match_mode }) }
+                    skinset_source={self.skinset_source}
+                    on_skinset_source_change={ ctx.link().callback(|skinset_source| AppMsg::SetSkinsetSource { skinset_source }) }
+                    min_preference_tier={self.min_preference_tier}
+                    on_min_preference_tier_change={ ctx.link().callback(|min_preference_tier| AppMsg::SetMinPreferenceTier { min_preference_tier }) }
+                    player_count={self.players.len()}
+                    team_setup={self.team_setup}
+                    on_team_setup_change={ ctx.link().callback(|team_setup| AppMsg::SetTeamSetup { team_setup }) }
+                    min_themed_players={self.min_themed_players}
+                    on_min_themed_players_change={ ctx.link().callback(|min_themed_players| AppMsg::SetMinThemedPlayers { min_themed_players }) }
+                    score_weights={self.score_weights}
+                    on_score_weights_change={ ctx.link().callback(|score_weights| AppMsg::SetScoreWeights { score_weights }) }
+                />
+
+                <PresetManager
+                    {player_names}
+                    presets={self.presets.clone()}
+                    lane_overrides={self.lane_overrides.clone()}
+                    on_apply_champ_pool={ ctx.link().callback(|(player_index, preset)| AppMsg::ApplyChampPool { player_index, preset }) }
+                    on_save_champ_pool={ ctx.link().callback(|(player_index, name)| AppMsg::SaveChampPool { player_index, name }) }
+                    on_delete_champ_pool={ ctx.link().callback(|name| AppMsg::DeleteChampPool { name }) }
+                    on_apply_blacklist={ ctx.link().callback(|preset| AppMsg::ApplyBlacklist { preset }) }
+                    on_save_blacklist={ ctx.link().callback(|name| AppMsg::SaveBlacklist { name }) }
+                    on_delete_blacklist={ ctx.link().callback(|name| AppMsg::DeleteBlacklist { name }) }
+                />
+
+                // Lane overrides only matter in game modes with lanes.
+                if self.game_mode.has_lanes() {
+                    <LaneOverridesEditor
+                        lane_overrides={self.lane_overrides.clone()}
+                        on_set_override={ ctx.link().callback(|(champ_id, lanes)| AppMsg::SetLaneOverride { champ_id, lanes }) }
+                        on_remove_override={ ctx.link().callback(|champ_id| AppMsg::RemoveLaneOverride { champ_id }) }
+                    />
+                }
+
+                {
+                    self.players.iter()
+                        .enumerate()
+                        .map(|(id, player)| {
+                            html! {
+                                <Player
+                                    {id}
+                                    name={player.name.clone().map(AttrValue::from)}
+                                    champs={player.champs.clone()}
+                                    show_lanes={self.game_mode.has_lanes()}
+
+                                    on_name_change={
+                                        ctx.link().callback(move |new_name| {
+                                            AppMsg::PlayerNameUpdate { index: id, new_name }
+                                        })
+                                    }
+
+                                    enable_remove={enable_player_removal}
+                                    on_remove={
+                                        ctx.link().callback(move |_| {
+                                            AppMsg::RemovePlayer { player_index: id }
+                                        })
+                                    }
+
+                                    on_add_champ={
+                                        ctx.link().callback(move |champ_id| {
+                                            AppMsg::AddChampToPlayer { player_index: id, champ_id }
+                                        })
+                                    }
+
+                                    on_upsert_champ={
+                                        ctx.link().callback(move |(champ_id, lanes)| {
+                                            AppMsg::UpsertChampOnPlayer { player_index: id, champ_id, lanes }
+                                        })
+                                    }
+
+                                    on_set_tier={
+                                        ctx.link().callback(move |(champ_id, tier)| {
+                                            AppMsg::SetChampTier { player_index: id, champ_id, tier }
+                                        })
+                                    }
+
+                                    on_replace_champ={
+                                        ctx.link().callback(move |(old_champ_id, new_champ_id)| {
+                                            AppMsg::ReplaceChampOnPlayer { player_index: id, old_champ_id, new_champ_id }
+                                        })
+                                    }
+
+                                    on_remove_champ={
+                                        ctx.link().callback(move |champ_id| {
+                                            AppMsg::RemoveChampFromPlayer { player_index: id, champ_id }
+                                        })
+                                    }
+                                />
+                            }
+                        })
+                        .collect::<Html>()
+                }
 
                 // Block button to add a player.
-                // <div class={"d-grid gap-2 my-2"}>
-                //     <button
-                //         type={"button"}
-                //         class={"btn btn-success"}
-                //         disabled={self.players.len() == 5}
-
-                //         // On-click handler to add a player.
-                //         onclick={
-                //             ctx.link().callback(move |_| {
-                //                 Msg::AddPlayer
-                //             })
-                //         }
-                //     >
-                //         <Icon icon_id={IconId::BootstrapPersonAdd} /> {" Add Player"}
-                //     </button>
-                // </div>
-
-                // // Table component to be rendered here.
-                // <ResultsTable players={self.players.clone()} skinsets_excluded={self.skinsets_excluded.clone()} />
+                <div class={"d-grid gap-2 my-2"}>
+                    <Button
+                        class={"btn btn-success"}
+                        enable={self.players.len() < self.team_setup.max_players()}
+                        on_click={ ctx.link().callback(|_| AppMsg::AddPlayer) }
+                    >
+                        <Icon icon_id={IconId::BootstrapPersonAdd} /> {" Add Player"}
+                    </Button>
+
+                    // Buttons to export the team to a file and import it again.
+                    <TeamFile
+                        saved_state={self.saved_state()}
+                        on_import={ ctx.link().callback(|file| AppMsg::ImportTeamFile { file }) }
+                    />
+
+                    // Button to clear the saved team and options.
+                    <Button
+                        class={"btn btn-outline-danger"}
+                        enable={true}
+                        on_click={ ctx.link().callback(|_| AppMsg::ResetToDefaults) }
+                    >
+                        {"Reset to defaults"}
+                    </Button>
+                </div>
+
+                // Table components to be rendered here -- the matchups when planning two teams, or else one table for
+                // the whole team (or one for each duo in Arena).
+                if let TeamSetup::TwoTeams(team_themes) = self.team_setup {
+                    <MatchupTable
+                        players={self.players.clone()}
+                        skinsets_excluded={self.skinsets_excluded}
+                        match_mode={self.match_mode}
+                        skinset_source={self.skinset_source}
+                        favourite_skinsets={self.favourite_skinsets}
+                        score_weights={self.score_weights}
+                        min_preference_tier={self.min_preference_tier}
+                        game_mode={self.game_mode}
+                        {team_themes}
+                    />
+                } else {
+                    {
+                        self.game_mode
+                            .teams(self.players.len())
+                            .into_iter()
+                            .enumerate()
+                            .map(|(team_index, team)| {
+                                let team_players = &self.players[team];
+
+                                html! {
+                                    <>
+                                        if self.game_mode == GameMode::Arena {
+                                            <h4 class="mx-2 mt-3"> {"Duo "} {team_index + 1} </h4>
+                                        }
+
+                                        <ResultsTable
+                                            players={team_players.to_vec()}
+                                            skinsets_excluded={self.skinsets_excluded}
+                                            match_mode={self.match_mode}
+                                            skinset_source={self.skinset_source}
+                                            favourite_skinsets={self.favourite_skinsets}
+                                            score_weights={self.score_weights}
+                                            min_preference_tier={self.min_preference_tier}
+                                            min_themed_players={self.min_themed_players}
+                                            game_mode={self.game_mode}
+                                        />
+                                    </>
+                                }
+                            })
+                            .collect::<Html>()
+                    }
+                }
             </div>
         }
     }
@@ -0,0 +1,90 @@
+//! Card used to edit the lanes champs are played in when they're added to a player.
+
+use super::button::Button;
+use super::player::{champ_dropdown::ChampDropdown, lanes_select::LaneSelect};
+use crate::constants::{ChampId, Lane};
+use crate::lane_overrides::LaneOverrides;
+use enumflags2::BitFlags;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew_icons::{Icon, IconId};
+
+/// Properties passed to the lane overrides editor.
+#[derive(Properties, PartialEq)]
+pub struct LaneOverridesEditorProps {
+    /// The current lane overrides.
+    pub lane_overrides: LaneOverrides,
+    /// Callback emitted to override the lanes of a champ (adding an override if they don't have one).
+    pub on_set_override: Callback<(ChampId, BitFlags<Lane>)>,
+    /// Callback emitted to remove the override for a champ.
+    pub on_remove_override: Callback<ChampId>,
+}
+
+/// Lane overrides editor card component.
+#[function_component(LaneOverridesEditor)]
+pub fn lane_overrides_editor(props: &LaneOverridesEditorProps) -> Html {
+    // List the champs without an override, to add one for.
+    let champs_without_override: Rc<Vec<ChampId>> = Rc::new(
+        ChampId::iter_all()
+            .filter(|champ_id| props.lane_overrides.iter().all(|(overridden, _)| overridden != *champ_id))
+            .collect(),
+    );
+
+    // Start new overrides at the champ's default lanes.
+    let on_add_override = {
+        let callback = props.on_set_override.clone();
+        Callback::from(move |champ_id: ChampId| callback.emit((champ_id, champ_id.default_lanes())))
+    };
+
+    html! {
+        <div class="card bg-light text-dark my-2">
+            <div class="card-body">
+                <h3 class="card-title"> {"Lane overrides"} </h3>
+                <p class="card-text">
+                    {"
+                    Champs added to a player start with the lanes listed here instead of their lanes from the wiki.
+                    Changing an override doesn't change champs that players already have.
+                    "}
+                </p>
+            </div>
+
+            <ul class="list-group list-group-flush">
+                {
+                    props.lane_overrides
+                        .iter()
+                        .map(|(champ_id, _)| {
+                            let on_set_override = props.on_set_override.clone();
+                            let on_remove_override = props.on_remove_override.clone();
+
+                            html! {
+                                <li class="list-group-item d-flex flex-wrap align-items-center gap-2">
+                                    <span class="fw-bold me-2"> {champ_id.champ_name()} </span>
+                                    <div class="flex-grow-1">
+                                        <LaneSelect
+                                            lanes={props.lane_overrides.lanes_for(champ_id)}
+                                            update_lanes_callback={ Callback::from(move |lanes| on_set_override.emit((champ_id, lanes))) }
+                                        />
+                                    </div>
+                                    <Button
+                                        class={"btn btn-outline-danger btn-sm"}
+                                        enable={true}
+                                        on_click={ Callback::from(move |_| on_remove_override.emit(champ_id)) }
+                                    >
+                                        <Icon icon_id={IconId::BootstrapTrash} /> {" Use wiki lanes"}
+                                    </Button>
+                                </li>
+                            }
+                        })
+                        .collect::<Html>()
+                }
+                <li class="list-group-item">
+                    <ChampDropdown
+                        selected_champ={None}
+                        other_available_champs={champs_without_override}
+                        on_change={on_add_override}
+                    />
+                </li>
+            </ul>
+        </div>
+    }
+}
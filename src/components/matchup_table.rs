@@ -0,0 +1,203 @@
+//! The matchup table component, used to render the two-team matchups planned for the selected champs.
+
+use crate::constants::{SkinsetId, SkinsetMask, SkinsetSource, UniverseId};
+use crate::persist::{PlayerRecord, PreferenceTier};
+use crate::scoring::ScoreWeights;
+use crate::solver::{GameMode, MatchMode};
+use crate::teams::{Matchup, TeamCache, TeamComp, TeamPlanner, TeamThemes};
+use instant::Instant;
+use yew::prelude::*;
+
+/// The most matchups shown.
+const MAX_MATCHUPS: usize = 100;
+
+/// Plan the matchups for the props, reusing the cached comps of teams whose players haven't changed, and logging how
+/// long it took.
+fn plan_matchups(props: &MatchupTableProps, cache: &mut TeamCache) -> Vec<Matchup> {
+    // Track the start instant so we can log planning times.
+    let start = Instant::now();
+
+    let planner = TeamPlanner {
+        players: &props.players,
+        skinsets_excluded: props.skinsets_excluded,
+        match_mode: props.match_mode,
+        skinset_source: props.skinset_source,
+        game_mode: props.game_mode,
+        min_preference_tier: props.min_preference_tier,
+        favourite_skinsets: props.favourite_skinsets,
+        score_weights: props.score_weights,
+        team_themes: props.team_themes,
+    };
+
+    let matchups = planner.plan_cached(MAX_MATCHUPS, cache);
+
+    // Log info on planning speed.
+    log::info!("Planned {} matchups in {:?}", matchups.len(), Instant::now() - start);
+    matchups
+}
+
+/// Properties passed to the table.
+#[derive(PartialEq, Properties)]
+pub struct MatchupTableProps {
+    /// The list of players with their champ selections, to be split into two teams.
+    pub players: Vec<PlayerRecord>,
+    /// The set of skinsets to exclude from results.
+    pub skinsets_excluded: SkinsetMask,
+    /// Whether comps are matched by skinset or by universe.
+    pub match_mode: MatchMode,
+    /// Which skinset table to use.
+    pub skinset_source: SkinsetSource,
+    /// The skinsets pinned as favourites.
+    pub favourite_skinsets: SkinsetMask,
+    /// How much each part of a comp's score counts.
+    pub score_weights: ScoreWeights,
+    /// The least preferred tier of champ to search.
+    pub min_preference_tier: PreferenceTier,
+    /// The game mode comps are found for.
+    pub game_mode: GameMode,
+    /// How the two teams' skinsets have to relate to each other.
+    pub team_themes: TeamThemes,
+}
+
+impl MatchupTableProps {
+    /// Check whether two sets of props plan with the same options, whatever their players.
+    fn same_options(&self, other: &Self) -> bool {
+        let options = |props: &Self| {
+            (
+                props.skinsets_excluded,
+                props.match_mode,
+                props.skinset_source,
+                props.favourite_skinsets,
+                props.score_weights,
+                props.min_preference_tier,
+                props.game_mode,
+                props.team_themes,
+            )
+        };
+
+        options(self) == options(other)
+    }
+}
+
+/// Matchup table component.
+pub struct MatchupTable {
+    /// The best matchups for the current props, best first.
+    matchups: Vec<Matchup>,
+    /// The best comps of each team in the last plan, reused while only the players change.
+    cache: TeamCache,
+}
+
+impl Component for MatchupTable {
+    type Message = ();
+
+    type Properties = MatchupTableProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut cache = TeamCache::default();
+        Self { matchups: plan_matchups(ctx.props(), &mut cache), cache }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        // Any team's comps can change with the options, but only the teams of changed players change with them.
+        if !ctx.props().same_options(old_props) {
+            self.cache = TeamCache::default();
+        }
+
+        // The players or options have changed so plan the matchups again.
+        self.matchups = plan_matchups(ctx.props(), &mut self.cache);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        // Get the component props.
+        let props = ctx.props();
+
+        // Resolve the player names.
+        let player_names: Vec<String> = props
+            .players
+            .iter()
+            .enumerate()
+            .map(|(id, player)| player.name.clone().unwrap_or_else(|| format!("Player {}", id + 1)))
+            .collect();
+
+        // Get the names of the skinsets (or universes) in a set.
+        let theme_names = |themes: SkinsetMask| match props.match_mode {
+            MatchMode::Skinset => themes.iter().map(SkinsetId::skinset_name).collect::<Vec<&str>>().join(", "),
+
+            MatchMode::Universe => themes
+                .universes(props.skinset_source)
+                .into_iter()
+                .map(UniverseId::universe_name)
+                .collect::<Vec<&str>>()
+                .join(", "),
+        };
+
+        // Render one team's comp, with each player's pick and the team's themes.
+        let team_cell = |team: &TeamComp| html! {
+            <td>
+                <ul class="list-unstyled mb-1">
+                    {
+                        team.players
+                            .iter()
+                            .zip(&team.comp)
+                            .map(|(player, (champ_id, lane))| html! {
+                                <li>
+                                    <span class="fw-semibold"> {&player_names[*player]} {": "} </span>
+                                    {champ_id.champ_name()}
+
+                                    // Lanes are only meaningful in game modes that have them.
+                                    if props.game_mode.has_lanes() {
+                                        {" "} {lane}
+                                    }
+                                </li>
+                            })
+                            .collect::<Html>()
+                    }
+                </ul>
+                <span class="small text-muted"> {theme_names(team.themes)} </span>
+            </td>
+        };
+
+        html! {
+            <div class="card m-2">
+                <div class="card-body">
+                    <span>
+                        if self.matchups.is_empty() {
+                            {"No matchups found"}
+                        } else {
+                            {"Showing the best "} {self.matchups.len()} {" matchups"}
+                        }
+                    </span>
+
+                    <table class="table table-stripped">
+                        <tr>
+                            <th> {"Score"} </th>
+                            <th> {"Team 1"} </th>
+                            <th> {"Team 2"} </th>
+                        </tr>
+
+                        {
+                            self.matchups
+                                .iter()
+                                .map(|matchup| html! {
+                                    <tr>
+                                        // Total score, with each team's score in the tooltip.
+                                        <td title={format!(
+                                            "{:.1} + {:.1}",
+                                            matchup.teams[0].score.total, matchup.teams[1].score.total,
+                                        )}>
+                                            {format!("{:.1}", matchup.total)}
+                                        </td>
+
+                                        { team_cell(&matchup.teams[0]) }
+                                        { team_cell(&matchup.teams[1]) }
+                                    </tr>
+                                })
+                                .collect::<Html>()
+                        }
+                    </table>
+                </div>
+            </div>
+        }
+    }
+}
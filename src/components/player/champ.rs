@@ -1,40 +1,50 @@
 //! Champion component used for player champ lists.
 
 use super::lanes_select::LaneSelect;
+use super::tier_select::TierSelect;
 use crate::components::{button::Button, player::champ_dropdown::ChampDropdown};
-use crate::lanes::Lane;
+use crate::constants::{ChampId, Lane};
+use crate::persist::{ChampEntry, PreferenceTier};
 use enumflags2::BitFlags;
 use std::rc::Rc;
 use yew::prelude::*;
 use yew_icons::{Icon, IconId};
 
-/// Champ Selection component including champion drop-down, lane checkboxes and button to remove champion from player.
+/// Champ Selection component including champion drop-down, lane checkboxes, preference tier, and button to remove
+/// champion from player.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ChampSelection;
 
 /// Properties passed to the champ selection component.
 #[derive(Properties, PartialEq)]
 pub struct ChampSelectionProps {
-    /// The selected champion and lanes.
-    pub selected_champ: Option<(AttrValue, BitFlags<Lane>)>,
+    /// The selected champion, lanes, and preference tier.
+    pub selected_champ: Option<ChampEntry>,
     /// The list of other champions not currently selected by this player.
-    pub other_available_champs: Rc<Vec<AttrValue>>,
+    pub other_available_champs: Rc<Vec<ChampId>>,
     /// Callback to handle changes to the champ selected -- passes the previously selected champ for ease of use.
-    pub change_champ_callback: Callback<(Option<AttrValue>, String)>,
+    pub change_champ_callback: Callback<(Option<ChampId>, ChampId)>,
     /// Callback to handle changes to the lanes selected.
-    pub lane_change_callback: Callback<(AttrValue, BitFlags<Lane>)>,
+    pub lane_change_callback: Callback<(ChampId, BitFlags<Lane>)>,
+    /// Callback to handle changes to the preference tier selected.
+    pub tier_change_callback: Callback<(ChampId, PreferenceTier)>,
     /// Callback to remove this champ from the player.
-    pub remove_champ_callback: Callback<AttrValue>,
+    pub remove_champ_callback: Callback<ChampId>,
+    /// Whether to show the lane checkboxes.
+    pub show_lanes: bool,
 }
 
 /// Messages passed to the champ selection component by its callbacks.
 pub enum Msg {
     /// Change the selected champion.
-    ChangeChamp { new_champ: String },
+    ChangeChamp { new_champ: ChampId },
 
     /// Change in the lanes for the selected champion.
     ChangeLanes { new_lanes: BitFlags<Lane> },
 
+    /// Change in the preference tier for the selected champion.
+    ChangeTier { new_tier: PreferenceTier },
+
     /// Remove this champion from the player.
     RemoveChamp,
 }
@@ -52,11 +62,10 @@ impl Component for ChampSelection {
         match msg {
             Msg::ChangeChamp { new_champ } => {
                 // Get the currently selected champ.
-                let currently_selected: Option<AttrValue> = ctx
+                let currently_selected: Option<ChampId> = ctx
                     .props()
                     .selected_champ
-                    .as_ref()
-                    .map(|(champ_name, _)| champ_name.clone());
+                    .map(|(champ_id, _, _)| champ_id);
 
                 // Trigger the callback to the parent.
                 ctx.props()
@@ -65,25 +74,34 @@ impl Component for ChampSelection {
             }
 
             Msg::ChangeLanes { new_lanes } => {
-                // Get the currently selected champ name. Use unwrap here as lane selection should only be available
+                // Get the currently selected champ. Use unwrap here as lane selection should only be available
                 // on selected champs.
-                let current_champ_name: AttrValue =
-                    ctx.props().selected_champ.as_ref().unwrap().0.clone();
+                let current_champ: ChampId = ctx.props().selected_champ.unwrap().0;
 
                 // Trigger the callback to the parent.
                 ctx.props()
                     .lane_change_callback
-                    .emit((current_champ_name, new_lanes));
+                    .emit((current_champ, new_lanes));
+            }
+
+            Msg::ChangeTier { new_tier } => {
+                // Get the currently selected champ. Use unwrap here as tier selection should only be available
+                // on selected champs.
+                let current_champ: ChampId = ctx.props().selected_champ.unwrap().0;
+
+                // Trigger the callback to the parent.
+                ctx.props()
+                    .tier_change_callback
+                    .emit((current_champ, new_tier));
             }
 
             Msg::RemoveChamp => {
-                // Get the currently selected champ name. Use unwrap here as lane selection should only be available
+                // Get the currently selected champ. Use unwrap here as the remove button should only be available
                 // on selected champs.
-                let current_champ_name: AttrValue =
-                    ctx.props().selected_champ.as_ref().unwrap().0.clone();
+                let current_champ: ChampId = ctx.props().selected_champ.unwrap().0;
 
                 // Trigger the callback to the parent.
-                ctx.props().remove_champ_callback.emit(current_champ_name);
+                ctx.props().remove_champ_callback.emit(current_champ);
             }
         }
 
@@ -97,32 +115,39 @@ impl Component for ChampSelection {
         let ChampSelectionProps {
             selected_champ,
             other_available_champs,
+            show_lanes,
             ..
         } = ctx.props();
 
-        // Get the selected champ name.
-        let selected_champ_name = selected_champ
-            .as_ref()
-            .map(|(champ_name, _)| champ_name.clone());
+        // Get the selected champ.
+        let selected_champ_id: Option<ChampId> = selected_champ.map(|(champ_id, _, _)| champ_id);
 
         html! {
             <div class={"card-body"}>
                 <ChampDropdown
-                    selected_champ={selected_champ_name.clone()}
+                    selected_champ={selected_champ_id}
                     other_available_champs={other_available_champs.clone()}
                     on_change={ ctx.link().callback(|new_champ| Msg::ChangeChamp { new_champ }) }
                 />
 
                 if selected_champ.is_some() {
-                    <div class={"my-1 align-items-center"}>
-                        <LaneSelect
-                            lanes={selected_champ.clone().unwrap().1}
-                            update_lanes_callback={ ctx.link().callback(|new_lanes| Msg::ChangeLanes { new_lanes }) }
+                    <div class={"my-1 d-flex flex-wrap align-items-center gap-2"}>
+                        if *show_lanes {
+                            <div>
+                                <LaneSelect
+                                    lanes={selected_champ.unwrap().1}
+                                    update_lanes_callback={ ctx.link().callback(|new_lanes| Msg::ChangeLanes { new_lanes }) }
+                                />
+                            </div>
+                        }
+                        <TierSelect
+                            tier={selected_champ.unwrap().2}
+                            on_change={ ctx.link().callback(|new_tier| Msg::ChangeTier { new_tier }) }
                         />
                     </div>
 
                     <Button class={"btn btn-danger w-100"} enable={true} on_click={ ctx.link().callback(|_| Msg::RemoveChamp) }>
-                        <Icon icon_id={IconId::BootstrapTrash} /> {" Remove "} {selected_champ_name.clone()}
+                        <Icon icon_id={IconId::BootstrapTrash} /> {" Remove "} {selected_champ_id.map(ChampId::champ_name)}
                     </Button>
                 }
             </div>
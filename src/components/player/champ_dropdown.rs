@@ -14,7 +14,7 @@ pub struct ChampDropdownProps {
     /// Shared list of all other available champs to select from
     pub other_available_champs: Rc<Vec<ChampId>>,
     /// Callback emitted when a new champ is selected.
-    pub on_change: Callback<String>,
+    pub on_change: Callback<ChampId>,
 }
 
 /// Dropdown component to choose a champ from.
@@ -38,8 +38,8 @@ impl Component for ChampDropdown {
     // Custom `changed` implementation needed to deal with stupid select/auto-fill issue on firefox.
     fn changed(&mut self, ctx: &Context<Self>, _: &Self::Properties) -> bool {
         if let Some(select) = self.select_ref.cast::<HtmlSelectElement>() {
-            if let Some(selected_champ) = ctx.props().selected_champ.as_ref() {
-                select.set_value(&selected_champ);
+            if let Some(selected_champ) = ctx.props().selected_champ {
+                select.set_value(selected_champ.champ_name());
             } else {
                 select.set_value("Select a champion...");
             }
@@ -54,29 +54,41 @@ impl Component for ChampDropdown {
         // Make a node ID to connect the select to the label.
         let select_id: AttrValue = Uuid::new_v4().to_string().into();
 
+        // Make a list of all the champs and sort it to keep it alphabetical (champ IDs are in alphabetical order).
+        let mut all_listed_champs_alphabetical: Vec<ChampId> = props
+            .other_available_champs
+            .iter()
+            .chain(props.selected_champ.iter())
+            .copied()
+            .collect();
+
+        all_listed_champs_alphabetical.sort();
+
         // Make the event handler callback to be triggered when a champ is selected.
         let onchange = {
             // Clone the callback to pass the selected champ to.
             let callback = props.on_change.clone();
             // Clone the reference to the select node that we're waiting for a change on.
             let select_node_ref = self.select_ref.clone();
+            // Clone the list of champs that the options were rendered from.
+            let listed_champs = all_listed_champs_alphabetical.clone();
+            // Account for the placeholder option at the top of the list if there is one.
+            let placeholder_offset = props.selected_champ.is_none() as i32;
 
             Callback::from(move |_| {
                 if let Some(select) = select_node_ref.cast::<HtmlSelectElement>() {
-                    callback.emit(select.value());
+                    // Resolve the selected option back to a champ ID.
+                    let selected = usize::try_from(select.selected_index() - placeholder_offset)
+                        .ok()
+                        .and_then(|index| listed_champs.get(index));
+
+                    if let Some(champ_id) = selected {
+                        callback.emit(*champ_id);
+                    }
                 }
             })
         };
 
-        // Make a list of all the champs and collect to a vec to keep as alphabetical.
-        let mut all_listed_champs_alphabetical: Vec<&AttrValue> = props
-            .other_available_champs
-            .iter()
-            .chain(props.selected_champ.iter())
-            .collect();
-
-        all_listed_champs_alphabetical.sort();
-
         html! {
             // Form tag in here is necessary to prevent firefox from auto selecting an option
             <div class="form-floating">
@@ -92,10 +104,10 @@ impl Component for ChampDropdown {
                     {
                         all_listed_champs_alphabetical
                             .iter()
-                            .map(|champ_name| {
+                            .map(|champ_id| {
                                 html! {
-                                    <option selected={Some(champ_name) == props.selected_champ.clone().as_ref().as_ref()}>
-                                        {champ_name}
+                                    <option selected={Some(*champ_id) == props.selected_champ}>
+                                        {champ_id.champ_name()}
                                     </option>
                                 }
                             })
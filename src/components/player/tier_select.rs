@@ -0,0 +1,47 @@
+//! Preference tier selector component attached to champ selector.
+
+use crate::persist::PreferenceTier;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+/// Properties passed to the tier selection component.
+#[derive(PartialEq, Properties)]
+pub struct TierSelectProps {
+    /// The selected tier.
+    pub tier: PreferenceTier,
+    /// The callback emitted when a different tier is selected.
+    pub on_change: Callback<PreferenceTier>,
+}
+
+/// Preference tier selection component attached to each champ.
+#[function_component(TierSelect)]
+pub fn tier_select(props: &TierSelectProps) -> Html {
+    let onchange = {
+        // Clone the passed callback (cheap Rc clone).
+        let callback = props.on_change.clone();
+
+        Callback::from(move |event: Event| {
+            let select: HtmlSelectElement = event.target_unchecked_into();
+
+            // The options are listed in the same order as the tiers.
+            let selected = usize::try_from(select.selected_index())
+                .ok()
+                .and_then(|index| PreferenceTier::ALL.get(index));
+
+            if let Some(tier) = selected {
+                callback.emit(*tier);
+            }
+        })
+    };
+
+    html! {
+        <select class="form-select form-select-sm w-auto" aria-label="Preference tier" {onchange} autocomplete="off">
+            {
+                PreferenceTier::ALL
+                    .iter()
+                    .map(|tier| html! { <option selected={*tier == props.tier}> {tier.label()} </option> })
+                    .collect::<Html>()
+            }
+        </select>
+    }
+}
@@ -0,0 +1,207 @@
+//! Card used to apply, save, and delete champ pool and skinset blacklist presets.
+
+use crate::components::button::Button;
+use crate::lane_overrides::LaneOverrides;
+use crate::presets::{BlacklistPreset, ChampPoolPreset, Presets};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+/// Properties passed to the preset manager.
+#[derive(Properties, PartialEq)]
+pub struct PresetManagerProps {
+    /// The display name of each player.
+    pub player_names: Vec<AttrValue>,
+    /// The presets the user has saved.
+    pub presets: Presets,
+    /// The lane overrides used for the champs in built-in champ pools.
+    pub lane_overrides: LaneOverrides,
+    /// Callback emitted to replace a player's (by index) champs with a champ pool.
+    pub on_apply_champ_pool: Callback<(usize, ChampPoolPreset)>,
+    /// Callback emitted to save a player's (by index) champs as a champ pool with a given name.
+    pub on_save_champ_pool: Callback<(usize, String)>,
+    /// Callback emitted to delete the saved champ pool with a given name.
+    pub on_delete_champ_pool: Callback<String>,
+    /// Callback emitted to add a blacklist's skinsets to the excluded skinsets.
+    pub on_apply_blacklist: Callback<BlacklistPreset>,
+    /// Callback emitted to save the excluded skinsets as a blacklist with a given name.
+    pub on_save_blacklist: Callback<String>,
+    /// Callback emitted to delete the saved blacklist with a given name.
+    pub on_delete_blacklist: Callback<String>,
+}
+
+/// Make a callback that stores the index of the selected option of a select element.
+fn select_callback(choice: &UseStateHandle<usize>) -> Callback<Event> {
+    let choice = choice.clone();
+
+    Callback::from(move |event: Event| {
+        let select: HtmlSelectElement = event.target_unchecked_into();
+        choice.set(usize::try_from(select.selected_index()).unwrap_or_default());
+    })
+}
+
+/// Make a callback that stores the value of a text input.
+fn input_callback(value: &UseStateHandle<String>) -> Callback<InputEvent> {
+    let value = value.clone();
+
+    Callback::from(move |event: InputEvent| {
+        let input: HtmlInputElement = event.target_unchecked_into();
+        value.set(input.value());
+    })
+}
+
+/// Preset manager card component.
+#[function_component(PresetManager)]
+pub fn preset_manager(props: &PresetManagerProps) -> Html {
+    // The selected champ pool, player, and blacklist, and the names typed in for new presets.
+    let pool_choice = use_state(|| 0);
+    let player_choice = use_state(|| 0);
+    let new_pool_name = use_state(String::new);
+    let blacklist_choice = use_state(|| 0);
+    let new_blacklist_name = use_state(String::new);
+
+    // List the built-in presets first, then the saved ones, marking which are saved (and so can be deleted).
+    let champ_pools: Vec<(ChampPoolPreset, bool)> = Presets::built_in_champ_pools(&props.lane_overrides)
+        .map(|preset| (preset, false))
+        .chain(props.presets.champ_pools.iter().cloned().map(|preset| (preset, true)))
+        .collect();
+
+    let blacklists: Vec<(BlacklistPreset, bool)> = Presets::built_in_blacklists()
+        .map(|preset| (preset, false))
+        .chain(props.presets.blacklists.iter().cloned().map(|preset| (preset, true)))
+        .collect();
+
+    // Keep the choices in range in case presets or players were removed since they were made.
+    let pool_index = (*pool_choice).min(champ_pools.len() - 1);
+    let player_index = (*player_choice).min(props.player_names.len().saturating_sub(1));
+    let blacklist_index = (*blacklist_choice).min(blacklists.len() - 1);
+
+    let (chosen_pool, chosen_pool_saved) = champ_pools[pool_index].clone();
+    let (chosen_blacklist, chosen_blacklist_saved) = blacklists[blacklist_index].clone();
+
+    // Make the button callbacks.
+    let on_apply_champ_pool = {
+        let callback = props.on_apply_champ_pool.clone();
+        let chosen_pool = chosen_pool.clone();
+        Callback::from(move |_| callback.emit((player_index, chosen_pool.clone())))
+    };
+
+    let on_delete_champ_pool = {
+        let callback = props.on_delete_champ_pool.clone();
+        let name = chosen_pool.name.clone();
+        Callback::from(move |_| callback.emit(name.clone()))
+    };
+
+    let on_save_champ_pool = {
+        let callback = props.on_save_champ_pool.clone();
+        let name = (*new_pool_name).clone();
+        Callback::from(move |_| callback.emit((player_index, name.clone())))
+    };
+
+    let on_apply_blacklist = {
+        let callback = props.on_apply_blacklist.clone();
+        let chosen_blacklist = chosen_blacklist.clone();
+        Callback::from(move |_| callback.emit(chosen_blacklist.clone()))
+    };
+
+    let on_delete_blacklist = {
+        let callback = props.on_delete_blacklist.clone();
+        let name = chosen_blacklist.name.clone();
+        Callback::from(move |_| callback.emit(name.clone()))
+    };
+
+    let on_save_blacklist = {
+        let callback = props.on_save_blacklist.clone();
+        let name = (*new_blacklist_name).clone();
+        Callback::from(move |_| callback.emit(name.clone()))
+    };
+
+    html! {
+        <div class="card bg-light text-dark my-2">
+            <div class="card-body">
+                <h3 class="card-title"> {"Presets"} </h3>
+
+                // Champ pools.
+                <div class="input-group my-2">
+                    <span class="input-group-text"> {"Champ pool"} </span>
+                    <select class="form-select" aria-label="Champ pool preset" onchange={select_callback(&pool_choice)}>
+                        {
+                            champ_pools
+                                .iter()
+                                .enumerate()
+                                .map(|(index, (preset, _))| html! {
+                                    <option selected={index == pool_index}> {preset.name.clone()} </option>
+                                })
+                                .collect::<Html>()
+                        }
+                    </select>
+                    <span class="input-group-text"> {"for"} </span>
+                    <select class="form-select" aria-label="Player" onchange={select_callback(&player_choice)}>
+                        {
+                            props.player_names
+                                .iter()
+                                .enumerate()
+                                .map(|(index, name)| html! {
+                                    <option selected={index == player_index}> {name.clone()} </option>
+                                })
+                                .collect::<Html>()
+                        }
+                    </select>
+                    <Button class={"btn btn-primary"} enable={true} on_click={on_apply_champ_pool}>
+                        {"Replace champs"}
+                    </Button>
+                    <Button class={"btn btn-outline-danger"} enable={chosen_pool_saved} on_click={on_delete_champ_pool}>
+                        {"Delete"}
+                    </Button>
+                </div>
+
+                <div class="input-group my-2">
+                    <input
+                        type="text"
+                        class="form-control"
+                        placeholder="New champ pool name"
+                        value={(*new_pool_name).clone()}
+                        oninput={input_callback(&new_pool_name)}
+                    />
+                    <Button class={"btn btn-secondary"} enable={!new_pool_name.is_empty()} on_click={on_save_champ_pool}>
+                        {"Save "} {props.player_names[player_index].clone()} {"'s champs"}
+                    </Button>
+                </div>
+
+                // Skinset blacklists.
+                <div class="input-group my-2">
+                    <span class="input-group-text"> {"Skinset blacklist"} </span>
+                    <select class="form-select" aria-label="Skinset blacklist preset" onchange={select_callback(&blacklist_choice)}>
+                        {
+                            blacklists
+                                .iter()
+                                .enumerate()
+                                .map(|(index, (preset, _))| html! {
+                                    <option selected={index == blacklist_index}> {preset.name.clone()} </option>
+                                })
+                                .collect::<Html>()
+                        }
+                    </select>
+                    <Button class={"btn btn-primary"} enable={true} on_click={on_apply_blacklist}>
+                        {"Exclude skinsets"}
+                    </Button>
+                    <Button class={"btn btn-outline-danger"} enable={chosen_blacklist_saved} on_click={on_delete_blacklist}>
+                        {"Delete"}
+                    </Button>
+                </div>
+
+                <div class="input-group my-2">
+                    <input
+                        type="text"
+                        class="form-control"
+                        placeholder="New skinset blacklist name"
+                        value={(*new_blacklist_name).clone()}
+                        oninput={input_callback(&new_blacklist_name)}
+                    />
+                    <Button class={"btn btn-secondary"} enable={!new_blacklist_name.is_empty()} on_click={on_save_blacklist}>
+                        {"Save excluded skinsets"}
+                    </Button>
+                </div>
+            </div>
+        </div>
+    }
+}
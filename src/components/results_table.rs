@@ -1,177 +1,556 @@
 //! The results table component, used to render the skinsets resolved for the selected champs.
 
-use super::app::PlayerRecord;
-use crate::{
-    lanes::Lane,
-    skinsets::{Skinsets, GLOBAL_SKINSETS_MAP},
-};
+use super::button::Button;
+use crate::constants::{ChampId, Lane, SkinsetId, SkinsetMask, SkinsetSource, UniverseId};
+use crate::persist::{PlayerRecord, PreferenceTier};
+use crate::scoring::{CompScore, ScoreWeights, Scorer};
+use crate::solver::{self, ChampPool, Comp, CompThemes, GameMode, MatchMode, ThemedGroup, TooManyPlayers};
+use enumflags2::BitFlags;
 use instant::Instant;
-use std::collections::HashSet;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+use std::time::Duration;
+use yew::platform::spawn_local;
+use yew::platform::time::sleep;
 use yew::prelude::*;
+use yew_icons::{Icon, IconId};
 
-/// Get a list of every combination of champs that this set of players could queue.
-/// This list will match the order of the list of players stored in the app.
-///
-/// Requires that the slice has length >= 1 or panics.
-fn resolve_all_champ_combinations(players: &[PlayerRecord]) -> Vec<Vec<(AttrValue, Lane)>> {
-    match players.len() {
-        0 => unreachable!("This function requires at least one champ in the slice to call"),
-
-        // One player -- suggest any of their champs in any available lane.
-        1 => {
-            // Borrow the players champ list.
-            let champs_list = players[0].champs.as_slice();
-            // Create new result vec to populate -- starting capacity is at least the number of
-            // champs for the player.
-            let mut result: Vec<Vec<(AttrValue, Lane)>> = Vec::with_capacity(champs_list.len());
-
-            // Iterate over all the champs for the one player.
-            for (champ, lanes) in champs_list.iter() {
-                // Iterate over the lanes for a champ
-                for lane in lanes.iter() {
-                    result.push(vec![(champ.clone(), lane)]);
-                }
+/// The number of comps shown on each page of the table.
+const PAGE_SIZE: usize = 100;
+
+/// The most comps collected when only some of the players have to share a skinset, since almost every comp can qualify
+/// when few of them have to. The solver finds comps with the largest themed groups first, so these are the best ones.
+const MAX_PARTIAL_COMPS: usize = 10_000;
+
+/// The most comps kept when every player has to share a skinset. Every comp scanned is scored, but only the best
+/// scoring ones are kept to be sorted and paged through, so that large champ pools don't run the browser out of memory.
+const MAX_SCORED_COMPS: usize = 10_000;
+
+/// The most comps scanned when every player has to share a skinset. Large champ pools can have millions of comps, so
+/// the ranking is only of the best of the first ones found rather than of every comp.
+const MAX_SCANNED_COMPS: usize = 100_000;
+
+/// The number of comps found and scored between each time the search hands control back to the browser.
+const SEARCH_CHUNK_SIZE: usize = 1_000;
+
+/// Check whether the table is searching for comps where only some of the players share a skinset.
+fn is_partial(props: &ResultsTableProps) -> bool {
+    props.min_themed_players.is_some_and(|min_themed_players| min_themed_players < props.players.len())
+}
+
+/// Get each player's champ pool to pass to the solver, leaving out champs in tiers that aren't searched and ignoring
+/// lanes in game modes without them.
+fn champ_pools(players: &[PlayerRecord], min_tier: PreferenceTier, game_mode: GameMode) -> Result<Vec<ChampPool>, TooManyPlayers> {
+    let mut champ_pools: Vec<ChampPool> = players
+        .iter()
+        .map(|player| player.champ_pool(min_tier))
+        .collect();
+
+    if !game_mode.has_lanes() {
+        solver::ignore_lanes(&mut champ_pools)?;
+    }
+
+    Ok(champ_pools)
+}
+
+/// A comp found by the solver, with its score.
+pub struct ScoredComp {
+    /// The order the solver found the comp in.
+    index: usize,
+    /// The champ and lane for each player.
+    comp: Comp,
+    /// The groups of players sharing skinsets.
+    themes: CompThemes,
+    /// The comp's score.
+    score: CompScore,
+}
+
+/// Search for comps in the background, sending them to the table a chunk at a time so that the page stays responsive
+/// while large champ pools are searched. The search stops early once `search_id` is no longer the table's current
+/// search.
+fn spawn_search(ctx: &Context<ResultsTable>, current_search: Rc<Cell<usize>>) {
+    let search_id = current_search.get();
+    let link = ctx.link().clone();
+    let props = ctx.props().clone();
+
+    spawn_local(async move {
+        // Track the start instant so we can log resolution times.
+        let start = Instant::now();
+        let owned_pools = match champ_pools(&props.players, props.min_preference_tier, props.game_mode) {
+            Ok(owned_pools) => owned_pools,
+            Err(err) => {
+                log::warn!("Not searching for comps: {err}");
+                link.send_message(Msg::Found { search_id, comps: Vec::new(), done: true });
+                return;
             }
+        };
+        let champ_pools: Vec<&[(ChampId, BitFlags<Lane>)]> = owned_pools.iter().map(Vec::as_slice).collect();
 
-            result
-        }
+        let scorer = Scorer {
+            players: &props.players,
+            favourite_skinsets: props.favourite_skinsets,
+            match_mode: props.match_mode,
+            skinset_source: props.skinset_source,
+            game_mode: props.game_mode,
+            weights: props.score_weights,
+        };
 
-        _ => {
-            // Get a list of all champ combinataions not including the first player.
-            let others: Vec<Vec<(AttrValue, Lane)>> = resolve_all_champ_combinations(&players[1..]);
-            // Borrow the first player's champ list.
-            let champ_list = players[0].champs.as_slice();
-            // Make a list to copy results into.
-            let mut result = Vec::new();
-
-            // Iterate over all the champs a player could play.
-            for (champ, lanes) in champ_list.iter() {
-                // Iterate over all the lanes the champ could play.
-                for lane in lanes.iter() {
-                    // Iterate over all the other champ combos for the rest of the team.
-                    for champ_combo in others.iter() {
-                        // Check if this champ is already in the combo
-                        let contains_champ: bool =
-                            champ_combo.iter().find(|(c, _)| c == champ).is_some();
-                        // Check if this lane is already covered in the combo.
-                        let lane_covered: bool =
-                            champ_combo.iter().find(|(_, l)| *l == lane).is_some();
-
-                        // If neither are true then we can make a new combo using this champ in this lane for this
-                        // player.
-                        if !contains_champ && !lane_covered {
-                            let mut new_combo = champ_combo.clone();
-                            new_combo.insert(0, (champ.clone(), lane));
-                            result.push(new_combo);
-                        }
-                    }
-                }
+        // The solver already finds the best partial matches first, so only the first of them are searched.
+        let max_comps = if is_partial(&props) { MAX_PARTIAL_COMPS } else { MAX_SCANNED_COMPS };
+
+        let themed_comps = match solver::resolve_themed_comps(
+            &champ_pools,
+            props.skinsets_excluded,
+            props.match_mode,
+            props.skinset_source,
+            props.min_themed_players,
+        ) {
+            Ok(themed_comps) => themed_comps,
+            Err(err) => {
+                log::warn!("Not searching for comps: {err}");
+                link.send_message(Msg::Found { search_id, comps: Vec::new(), done: true });
+                return;
             }
+        };
 
-            result
+        let mut scored_comps = themed_comps.take(max_comps).enumerate().map(|(index, (comp, themes))| {
+            let score = scorer.score(&comp, themes.shared());
+            ScoredComp { index, comp, themes, score }
+        });
+
+        let mut total_found = 0;
+
+        while current_search.get() == search_id {
+            let comps: Vec<ScoredComp> = scored_comps.by_ref().take(SEARCH_CHUNK_SIZE).collect();
+            let done = comps.len() < SEARCH_CHUNK_SIZE;
+            total_found += comps.len();
+            link.send_message(Msg::Found { search_id, comps, done });
+
+            if done {
+                // Log info on resolution speed.
+                log::info!("Resolved and scored {total_found} champion combos in {:?}", Instant::now() - start);
+                return;
+            }
+
+            // Hand control back to the browser so that it can show the comps found so far.
+            sleep(Duration::ZERO).await;
         }
-    }
+    });
 }
 
 /// Properties passed to the table.
-#[derive(PartialEq, Properties)]
+#[derive(Clone, PartialEq, Properties)]
 pub struct ResultsTableProps {
     /// The list of players with their champ selections.
     pub players: Vec<PlayerRecord>,
     /// The set of skinsets to exclude from results.
-    pub skinsets_excluded: HashSet<AttrValue>,
+    pub skinsets_excluded: SkinsetMask,
+    /// Whether comps are matched by skinset or by universe.
+    pub match_mode: MatchMode,
+    /// Which skinset table to use.
+    pub skinset_source: SkinsetSource,
+    /// The skinsets pinned as favourites.
+    pub favourite_skinsets: SkinsetMask,
+    /// How much each part of a comp's score counts.
+    pub score_weights: ScoreWeights,
+    /// The least preferred tier of champ to search.
+    pub min_preference_tier: PreferenceTier,
+    /// The fewest players that have to share a skinset, or `None` if every player has to.
+    pub min_themed_players: Option<usize>,
+    /// The game mode comps are found for.
+    pub game_mode: GameMode,
+}
+
+/// The columns the table can be sorted by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortColumn {
+    /// The comp's score.
+    Score,
+    /// A player's champ (by name, then lane).
+    Player(usize),
+    /// The number of skinsets (or universes) shared.
+    Shared,
+    /// The size of the largest group of players sharing a skinset (then the score).
+    Themed,
+}
+
+impl SortColumn {
+    /// Compare two comps by this column, in ascending order (ignoring the order they were found in).
+    fn compare(self, a: &ScoredComp, b: &ScoredComp) -> Ordering {
+        match self {
+            SortColumn::Score => a.score.total.total_cmp(&b.score.total),
+            // Champ IDs are in alphabetical order.
+            SortColumn::Player(index) => {
+                let pick_key = |comp: &ScoredComp| comp.comp.get(index).map(|(champ_id, lane)| (*champ_id, BitFlags::from(*lane).bits()));
+                pick_key(a).cmp(&pick_key(b))
+            }
+            SortColumn::Shared => a.score.shared_skinsets.cmp(&b.score.shared_skinsets),
+            SortColumn::Themed => a
+                .themes
+                .largest_group()
+                .cmp(&b.themes.largest_group())
+                .then_with(|| a.score.total.total_cmp(&b.score.total)),
+        }
+    }
+
+    /// Whether this column is sorted in descending order when it's first picked.
+    fn descending_by_default(self) -> bool {
+        !matches!(self, SortColumn::Player(_))
+    }
+}
+
+/// Messages passed to the results table.
+pub enum Msg {
+    /// Go to the previous page of results.
+    PreviousPage,
+
+    /// Go to the next page of results.
+    NextPage,
+
+    /// Sort by a column, or reverse the sort if it's already sorted by that column.
+    SortBy(SortColumn),
+
+    /// The search found another chunk of comps.
+    Found {
+        /// The search that found the comps, which is ignored if it's been replaced by a newer one.
+        search_id: usize,
+        /// The comps found, in the order the solver found them.
+        comps: Vec<ScoredComp>,
+        /// Whether the search has finished.
+        done: bool,
+    },
 }
 
 /// Results table component.
-pub struct ResultsTable;
+pub struct ResultsTable {
+    /// The index of the page of results being shown.
+    page: usize,
+    /// The comps kept for the current props so far, in sorted order.
+    comps: Vec<ScoredComp>,
+    /// The number of comps found for the current props so far, including any that weren't kept.
+    total_found: usize,
+    /// Whether the search for the current props is still running.
+    searching: bool,
+    /// The ID of the current search, shared with the search so that it stops once it's replaced.
+    current_search: Rc<Cell<usize>>,
+    /// The column the comps are sorted by.
+    sort_column: SortColumn,
+    /// Whether the comps are sorted in descending order.
+    sort_descending: bool,
+}
+
+impl ResultsTable {
+    /// Get the number of pages needed to show every comp (at least one, even if empty).
+    fn page_count(&self) -> usize {
+        self.comps.len().div_ceil(PAGE_SIZE).max(1)
+    }
+
+    /// Sort the comps by the current sort column. Ties stay in the order the solver found them.
+    fn sort_comps(&mut self) {
+        let (column, descending) = (self.sort_column, self.sort_descending);
+
+        self.comps.sort_unstable_by(|a, b| {
+            let ordering = column.compare(a, b);
+            let ordering = if descending { ordering.reverse() } else { ordering };
+            ordering.then_with(|| a.index.cmp(&b.index))
+        });
+    }
+
+    /// Start searching for the comps for the current props, replacing any search that's still running.
+    fn start_search(&mut self, ctx: &Context<Self>) {
+        self.current_search.set(self.current_search.get() + 1);
+        self.comps.clear();
+        self.total_found = 0;
+        self.searching = true;
+
+        spawn_search(ctx, Rc::clone(&self.current_search));
+    }
+
+    /// Add a chunk of comps found by the search, keeping only the best scoring comps when every player has to share a
+    /// skinset.
+    fn add_comps(&mut self, ctx: &Context<Self>, comps: Vec<ScoredComp>) {
+        self.total_found += comps.len();
+        self.comps.extend(comps);
+
+        if !is_partial(ctx.props()) && self.comps.len() > MAX_SCORED_COMPS {
+            self.comps.select_nth_unstable_by(MAX_SCORED_COMPS, |a, b| {
+                b.score.total.total_cmp(&a.score.total).then_with(|| a.index.cmp(&b.index))
+            });
+            self.comps.truncate(MAX_SCORED_COMPS);
+        }
+
+        self.sort_comps();
+    }
+
+    /// Make a header cell that sorts the table by a column when clicked, with an arrow if it's sorted by it.
+    fn sort_header(&self, ctx: &Context<Self>, column: SortColumn, label: String) -> Html {
+        let arrow = (self.sort_column == column).then(|| {
+            let icon_id = if self.sort_descending { IconId::BootstrapArrowDownShort } else { IconId::BootstrapArrowUpShort };
+            html! { <Icon {icon_id} width={"1em"} height={"1em"} /> }
+        });
+
+        html! {
+            <th role="button" class="text-nowrap" onclick={ ctx.link().callback(move |_| Msg::SortBy(column)) }>
+                {label} {arrow}
+            </th>
+        }
+    }
+}
 
 impl Component for ResultsTable {
-    type Message = ();
+    type Message = Msg;
 
     type Properties = ResultsTableProps;
 
-    fn create(_: &Context<Self>) -> Self {
-        Self
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut results_table = Self {
+            page: 0,
+            comps: Vec::new(),
+            total_found: 0,
+            searching: true,
+            current_search: Rc::new(Cell::new(0)),
+            // Partial matches are ranked by their largest themed group first.
+            sort_column: if is_partial(ctx.props()) { SortColumn::Themed } else { SortColumn::Score },
+            sort_descending: true,
+        };
+
+        results_table.start_search(ctx);
+        results_table
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        // The results have changed so find them again and go back to the first page.
+        self.page = 0;
+        self.start_search(ctx);
+
+        // Players may have been removed, so fall back to sorting by score if the sorted player is gone. The same goes
+        // for the themed column when every player has to share a skinset again.
+        let partial = is_partial(ctx.props());
+        let column_gone = match self.sort_column {
+            SortColumn::Player(index) => index >= ctx.props().players.len(),
+            SortColumn::Themed => !partial,
+            SortColumn::Score | SortColumn::Shared => false,
+        };
+
+        if column_gone {
+            self.sort_column = SortColumn::Score;
+            self.sort_descending = true;
+        } else if partial && !is_partial(old_props) {
+            // Rank partial matches by their largest themed group as soon as they're switched on.
+            self.sort_column = SortColumn::Themed;
+            self.sort_descending = true;
+        }
+
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::PreviousPage => self.page = self.page.saturating_sub(1),
+            Msg::NextPage => self.page = (self.page + 1).min(self.page_count() - 1),
+
+            Msg::SortBy(column) => {
+                if self.sort_column == column {
+                    self.sort_descending = !self.sort_descending;
+                } else {
+                    self.sort_column = column;
+                    self.sort_descending = column.descending_by_default();
+                }
+
+                self.page = 0;
+                self.sort_comps();
+            }
+
+            Msg::Found { search_id, comps, done } => {
+                // Ignore comps from a search that's been replaced.
+                if search_id != self.current_search.get() {
+                    return false;
+                }
+
+                self.add_comps(ctx, comps);
+                self.searching = !done;
+            }
+        }
+
+        true
+    }
+
+    fn destroy(&mut self, _: &Context<Self>) {
+        // Stop the search, since there's nothing to show its comps.
+        self.current_search.set(self.current_search.get() + 1);
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         // Get the component props.
         let props = ctx.props();
 
-        // Track the start instant so we can log resolution/render times.
-        let start = Instant::now();
-        // Get an iterator over the champ-combinations that could be played.
-        let all_comps = resolve_all_champ_combinations(&props.players);
-        // Log info on resolution speed.
-        log::info!(
-            "Resolved all champion combos in {:?}",
-            Instant::now() - start
-        );
-        // Get an iterator over the champ combinations that filters out any with no-overlapping, non-excluded skinsets.
-        let displayed_comps = all_comps
-            .into_iter()
-            // Add the set of overlapping non-excluded skinsets.
-            .map(|champ_combo: Vec<(AttrValue, Lane)>| {
-                // Get the list of overlapping skinsets.
-                let overlapping_skinsets: HashSet<AttrValue> = GLOBAL_SKINSETS_MAP
-                    .with(|s: &Skinsets| s.get_overlapping_skinsets(&champ_combo));
-
-                // Remove any excluded/unwanted skinsets.
-                let final_skinsets: Vec<AttrValue> = overlapping_skinsets
-                    .difference(&props.skinsets_excluded)
-                    .cloned()
-                    .collect();
-
-                (champ_combo, final_skinsets)
-            })
-            // Filter out champ combos with no skinsets
-            .filter(|(_, skinsets)| !skinsets.is_empty());
+        // Get the range of comps shown on this page.
+        let first_shown = self.page * PAGE_SIZE;
+        let last_shown = (first_shown + PAGE_SIZE).min(self.comps.len());
+        let displayed_comps = &self.comps[first_shown.min(last_shown)..last_shown];
+
+        // Resolve the player names.
+        let player_names: Vec<String> = props
+            .players
+            .iter()
+            .enumerate()
+            .map(|(id, player)| player.name.clone().unwrap_or_else(|| format!("Player {}", id + 1)))
+            .collect();
+
+        let partial = is_partial(props);
+
+        // Get the names of the skinsets (or universes) in a set.
+        let shared_names = |shared: SkinsetMask| match props.match_mode {
+            MatchMode::Skinset => shared.iter().map(SkinsetId::skinset_name).collect::<Vec<&str>>().join(", "),
+
+            MatchMode::Universe => shared
+                .universes(props.skinset_source)
+                .into_iter()
+                .map(UniverseId::universe_name)
+                .collect::<Vec<&str>>()
+                .join(", "),
+        };
 
         html! {
             <div class="card m-2">
                 <div class="card-body">
+                    // Pagination controls.
+                    <span class="d-inline-flex w-100 align-items-center">
+                        <span class="flex-grow-1">
+                            if self.comps.is_empty() {
+                                if self.searching { {"Searching\u{2026}"} } else { {"No comps found"} }
+                            } else {
+                                {"Showing "} {first_shown + 1} {"\u{2013}"} {last_shown} {" of "} {self.comps.len()} {" comps"}
+                            }
+
+                            if self.searching {
+                                if !self.comps.is_empty() {
+                                    {" (still searching, "} {self.total_found} {"+ found so far)"}
+                                }
+                            } else if partial && self.comps.len() == MAX_PARTIAL_COMPS {
+                                {" (the first found, with the most players in theme)"}
+                            } else if self.total_found == MAX_SCANNED_COMPS {
+                                {" (the best scoring of the first "} {self.total_found} {" found)"}
+                            } else if self.total_found > self.comps.len() {
+                                {" (the best scoring of "} {self.total_found} {" found)"}
+                            }
+                        </span>
+
+                        <Button class={"btn btn-secondary mx-1"} enable={self.page > 0} on_click={ ctx.link().callback(|_| Msg::PreviousPage) }>
+                            {"Previous"}
+                        </Button>
+
+                        <Button class={"btn btn-secondary mx-1"} enable={self.page + 1 < self.page_count()} on_click={ ctx.link().callback(|_| Msg::NextPage) }>
+                            {"Next"}
+                        </Button>
+                    </span>
+
                     <table class="table table-stripped">
                         <tr>
+                            {self.sort_header(ctx, SortColumn::Score, "Score".to_owned())}
+
+                            if partial {
+                                {self.sort_header(ctx, SortColumn::Themed, "In Theme".to_owned())}
+                            }
+
                             {
-                                props.players
+                                player_names
                                     .iter()
                                     .enumerate()
-                                    .map(|(id, player)| {
-                                        // Resolve the player name
-                                        let player_name = player
-                                            .clone()
-                                            .name
-                                            .unwrap_or_else(|| format!("Player {}", id + 1).into());
-
-                                        html! {
-                                            <th> {player_name} </th>
-                                        }
-                                    })
+                                    .map(|(id, player_name)| self.sort_header(ctx, SortColumn::Player(id), player_name.clone()))
                                     .collect::<Html>()
                             }
 
                             // Header for skinsets collumn
-                            <th> {"Overlapping Skinsets"} </th>
+                            {
+                                self.sort_header(ctx, SortColumn::Shared, match props.match_mode {
+                                    MatchMode::Skinset => "Overlapping Skinsets",
+                                    MatchMode::Universe => "Overlapping Universes",
+                                }.to_owned())
+                            }
                         </tr>
 
                         // Table data
                         {
                             displayed_comps
-                                .map(|(champ_combo, skinsets): (Vec<(AttrValue, Lane)>, Vec<AttrValue>)| html! {
+                                .iter()
+                                .map(|ScoredComp { comp, themes, score, .. }| html! {
                                     <tr>
+                                        // Score, with the unweighted parts in the tooltip.
+                                        <td title={format!(
+                                            "{} shared, {:.2} champ preference, {} in default lanes, {} favourites",
+                                            score.shared_skinsets, score.champ_preference, score.lane_fit, score.favourite_skinsets,
+                                        )}>
+                                            {format!("{:.1}", score.total)}
+                                        </td>
+
+                                        if partial {
+                                            <td> {themes.largest_group()} {" of "} {comp.len()} </td>
+                                        }
+
                                         // Champs and lanes
                                         {
-                                            champ_combo
-                                                .into_iter()
-                                                .map(|(champ, lane): (AttrValue, Lane)| html! {
-                                                    <td> {champ} {" "} {lane} </td>
+                                            comp
+                                                .iter()
+                                                .enumerate()
+                                                .map(|(player, &(champ, lane)): (usize, &(ChampId, Lane))| {
+                                                    // Get the skinsets shared by the groups this player is in.
+                                                    let player_shared = themes
+                                                        .groups
+                                                        .iter()
+                                                        .filter(|group| group.players.contains(&player))
+                                                        .fold(SkinsetMask::EMPTY, |union, group| union | group.shared);
+
+                                                    html! {
+                                                        <td>
+                                                            {champ.champ_name()}
+
+                                                            // Lanes are only meaningful in game modes that have them.
+                                                            if props.game_mode.has_lanes() {
+                                                                {" "} {lane}
+                                                            }
+
+                                                            if !themes.is_in_theme(player) {
+                                                                {" "} <span class="badge text-bg-secondary"> {"Off-theme"} </span>
+                                                            }
+
+                                                            // The skins this champ would use for each shared skinset.
+                                                            <ul class="list-unstyled small text-muted mb-0">
+                                                                {
+                                                                    player_shared
+                                                                        .iter()
+                                                                        .flat_map(|skinset_id| champ.skins_in(skinset_id, props.skinset_source))
+                                                                        .map(|skin_id| html! { <li> {skin_id.skin_name()} </li> })
+                                                                        .collect::<Html>()
+                                                                }
+                                                            </ul>
+                                                        </td>
+                                                    }
                                                 })
                                                 .collect::<Html>()
                                         }
 
                                         <td>
-                                            {
-                                                skinsets.join(", ")
+                                            if partial {
+                                                // List which players are in theme for which skinsets.
+                                                <ul class="list-unstyled mb-0">
+                                                    {
+                                                        themes
+                                                            .groups
+                                                            .iter()
+                                                            .map(|ThemedGroup { players, shared }| {
+                                                                let names: Vec<&str> = players.iter().map(|player| player_names[*player].as_str()).collect();
+
+                                                                html! {
+                                                                    <li> <span class="fw-semibold"> {names.join(", ")} {": "} </span> {shared_names(*shared)} </li>
+                                                                }
+                                                            })
+                                                            .collect::<Html>()
+                                                    }
+                                                </ul>
+                                            } else {
+                                                {shared_names(themes.shared())}
                                             }
                                         </td>
                                     </tr>
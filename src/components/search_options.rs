@@ -0,0 +1,302 @@
+//! Card with the options that change how comps are searched for.
+
+use crate::{components::button::Button, constants::SkinsetSource, solver::{GameMode, MatchMode}};
+use crate::persist::PreferenceTier;
+use crate::scoring::{ScoreWeights, MAX_WEIGHT};
+use crate::teams::{TeamSetup, TeamThemes, TEAM_SIZE};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+/// Properties passed to the search options card.
+#[derive(Properties, PartialEq)]
+pub struct SearchOptionsProps {
+    /// The currently selected game mode.
+    pub game_mode: GameMode,
+    /// Callback emitted when a different game mode is selected.
+    pub on_game_mode_change: Callback<GameMode>,
+    /// The currently selected match mode.
+    pub match_mode: MatchMode,
+    /// Callback emitted when a different match mode is selected.
+    pub on_match_mode_change: Callback<MatchMode>,
+    /// The currently selected skinset table.
+    pub skinset_source: SkinsetSource,
+    /// Callback emitted when a different skinset table is selected.
+    pub on_skinset_source_change: Callback<SkinsetSource>,
+    /// The least preferred tier of champ currently searched.
+    pub min_preference_tier: PreferenceTier,
+    /// Callback emitted when a different minimum tier is selected.
+    pub on_min_preference_tier_change: Callback<PreferenceTier>,
+    /// The number of players.
+    pub player_count: usize,
+    /// The currently selected team setup.
+    pub team_setup: TeamSetup,
+    /// Callback emitted when a different team setup is selected.
+    pub on_team_setup_change: Callback<TeamSetup>,
+    /// The fewest players that currently have to share a skinset, or `None` if every player has to.
+    pub min_themed_players: Option<usize>,
+    /// Callback emitted when a different minimum number of themed players is selected.
+    pub on_min_themed_players_change: Callback<Option<usize>>,
+    /// The current weights of each part of a comp's score.
+    pub score_weights: ScoreWeights,
+    /// Callback emitted when a score weight is changed.
+    pub on_score_weights_change: Callback<ScoreWeights>,
+}
+
+/// Search options card component.
+#[function_component(SearchOptions)]
+pub fn search_options(props: &SearchOptionsProps) -> Html {
+    // Make a toggle button for each of the game modes.
+    let game_mode_buttons = [
+        (GameMode::SummonersRift, "Summoner's Rift"),
+        (GameMode::Aram, "ARAM"),
+        (GameMode::Arena, "Arena"),
+    ]
+    .into_iter()
+    .map(|(game_mode, label)| {
+        // Highlight the selected mode.
+        let class = if props.game_mode == game_mode { "btn btn-primary" } else { "btn btn-outline-primary" };
+        // Clone the callback (cheap Rc clone).
+        let callback = props.on_game_mode_change.clone();
+
+        html! {
+            <Button {class} enable={true} on_click={ Callback::from(move |_| callback.emit(game_mode)) }>
+                {label}
+            </Button>
+        }
+    })
+    .collect::<Html>();
+
+    // Make a toggle button for each of the team setups.
+    let team_setup_buttons = [
+        (TeamSetup::OneTeam, "One team"),
+        (TeamSetup::TwoTeams(TeamThemes::Any), "Two teams"),
+        (TeamSetup::TwoTeams(TeamThemes::Different), "Two teams, different themes"),
+        (TeamSetup::TwoTeams(TeamThemes::Rival), "Two teams, rival themes"),
+    ]
+    .into_iter()
+    .map(|(team_setup, label)| {
+        // Highlight the selected setup.
+        let class = if props.team_setup == team_setup { "btn btn-primary" } else { "btn btn-outline-primary" };
+        // Only allow setups with room for every player.
+        let enable = props.player_count <= team_setup.max_players();
+        // Clone the callback (cheap Rc clone).
+        let callback = props.on_team_setup_change.clone();
+
+        html! {
+            <Button {class} {enable} on_click={ Callback::from(move |_| callback.emit(team_setup)) }>
+                {label}
+            </Button>
+        }
+    })
+    .collect::<Html>();
+
+    // Make a toggle button for each of the match modes.
+    let match_mode_buttons = [
+        (MatchMode::Skinset, "Match by skinset"),
+        (MatchMode::Universe, "Match by universe"),
+    ]
+    .into_iter()
+    .map(|(match_mode, label)| {
+        // Highlight the selected mode.
+        let class = if props.match_mode == match_mode { "btn btn-primary" } else { "btn btn-outline-primary" };
+        // Clone the callback (cheap Rc clone).
+        let callback = props.on_match_mode_change.clone();
+
+        html! {
+            <Button {class} enable={true} on_click={ Callback::from(move |_| callback.emit(match_mode)) }>
+                {label}
+            </Button>
+        }
+    })
+    .collect::<Html>();
+
+    // Make a toggle button for each of the skinset tables.
+    let skinset_source_buttons = [
+        (SkinsetSource::Wiki, "Wiki/community sets"),
+        (SkinsetSource::Official, "Official Riot sets"),
+    ]
+    .into_iter()
+    .map(|(skinset_source, label)| {
+        // Highlight the selected table.
+        let class = if props.skinset_source == skinset_source { "btn btn-primary" } else { "btn btn-outline-primary" };
+        // Clone the callback (cheap Rc clone).
+        let callback = props.on_skinset_source_change.clone();
+
+        html! {
+            <Button {class} enable={true} on_click={ Callback::from(move |_| callback.emit(skinset_source)) }>
+                {label}
+            </Button>
+        }
+    })
+    .collect::<Html>();
+
+    // Make a toggle button for each of the tiers that can be the least preferred tier searched.
+    let min_tier_buttons = [
+        (PreferenceTier::Willing, "Any pick"),
+        (PreferenceTier::Comfortable, "Main or comfortable picks"),
+        (PreferenceTier::Main, "Mains only"),
+    ]
+    .into_iter()
+    .map(|(min_tier, label)| {
+        // Highlight the selected tier.
+        let class = if props.min_preference_tier == min_tier { "btn btn-primary" } else { "btn btn-outline-primary" };
+        // Clone the callback (cheap Rc clone).
+        let callback = props.on_min_preference_tier_change.clone();
+
+        html! {
+            <Button {class} enable={true} on_click={ Callback::from(move |_| callback.emit(min_tier)) }>
+                {label}
+            </Button>
+        }
+    })
+    .collect::<Html>();
+
+    // Make a toggle button for everyone sharing a skinset, and for each smaller group that could share one instead.
+    let themed_player_buttons = std::iter::once((None, "Everyone in theme".to_owned()))
+        .chain((2..props.player_count).rev().map(|min_themed_players| {
+            (Some(min_themed_players), format!("At least {min_themed_players} in theme"))
+        }))
+        .map(|(min_themed_players, label)| {
+            // Highlight the selected minimum, counting any minimum that covers the whole team as everyone.
+            let selected = match (props.min_themed_players, min_themed_players) {
+                (Some(current), Some(option)) => current == option,
+                (current, None) => current.is_none_or(|current| current >= props.player_count),
+                (None, Some(_)) => false,
+            };
+
+            let class = if selected { "btn btn-primary" } else { "btn btn-outline-primary" };
+            // Clone the callback (cheap Rc clone).
+            let callback = props.on_min_themed_players_change.clone();
+
+            html! {
+                <Button {class} enable={true} on_click={ Callback::from(move |_| callback.emit(min_themed_players)) }>
+                    {label}
+                </Button>
+            }
+        })
+        .collect::<Html>();
+
+    // Make a slider for each of the score weights.
+    let score_weight_sliders = [
+        ("Shared skinsets", (|weights| &mut weights.shared_skinsets) as fn(&mut ScoreWeights) -> &mut u8),
+        ("Champ preference", |weights| &mut weights.champ_preference),
+        ("Default lanes", |weights| &mut weights.lane_fit),
+        ("Favourite skinsets", |weights| &mut weights.favourite_skinsets),
+    ]
+    .into_iter()
+    .map(|(label, weight)| {
+        let score_weights = props.score_weights;
+        let value = *weight(&mut score_weights.clone());
+        // Clone the callback (cheap Rc clone).
+        let callback = props.on_score_weights_change.clone();
+
+        let oninput = Callback::from(move |event: InputEvent| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+
+            if let Ok(value) = input.value().parse() {
+                let mut score_weights = score_weights;
+                *weight(&mut score_weights) = value;
+                callback.emit(score_weights);
+            }
+        });
+
+        html! {
+            <div class="col">
+                <label class="form-label mb-0"> {label} {": "} {value} </label>
+                <input type="range" class="form-range" min="0" max={MAX_WEIGHT.to_string()} value={value.to_string()} {oninput} />
+            </div>
+        }
+    })
+    .collect::<Html>();
+
+    html! {
+        <div class="card bg-light text-dark my-2">
+            <div class="card-body">
+                <h3 class="card-title"> {"Search Options"} </h3>
+
+                <div class="btn-group" role="group" aria-label="Game mode">
+                    {game_mode_buttons}
+                </div>
+
+                <p class="form-text">
+                    {"
+                    ARAM and Arena have no lanes, so comps only need different champions that share a skinset. In 
+                    Arena, players are paired up in order into duos, and each duo's comps are found separately.
+                    "}
+                </p>
+
+                <div class="btn-group" role="group" aria-label="Team setup">
+                    {team_setup_buttons}
+                </div>
+
+                <p class="form-text">
+                    {format!("
+                    For custom games, add up to {} players and they'll be split into two teams that each share a 
+                    skinset. Rival themes pit sets like Star Guardian against Dark Star. Every player on each team 
+                    has to share it (the themed players option only applies to one team), and in Arena the two teams 
+                    aren't split into duos. Remove players down to {} to go back to one team.
+                    ", 2 * TEAM_SIZE, TEAM_SIZE)}
+                </p>
+
+                <div class="btn-group" role="group" aria-label="Match mode">
+                    {match_mode_buttons}
+                </div>
+
+                <p class="form-text">
+                    {"
+                    Matching by universe also counts comps as themed when everyone has a skin in a related skinset, 
+                    like Crime City and Debonair.
+                    "}
+                </p>
+
+                <div class="btn-group" role="group" aria-label="Skinset table">
+                    {skinset_source_buttons}
+                </div>
+
+                <p class="form-text">
+                    {"
+                    The wiki/community sets are maintained by the League of Legends wiki and group some skins 
+                    differently from the sets Riot officially lists.
+                    "}
+                </p>
+
+                <div class="btn-group" role="group" aria-label="Champ preference tiers">
+                    {min_tier_buttons}
+                </div>
+
+                <p class="form-text">
+                    {"
+                    Only search comps where everyone is on a champ in one of these preference tiers. Each champ's tier 
+                    can be set next to their lanes.
+                    "}
+                </p>
+
+                // Partial matches only apply to one team.
+                if props.team_setup == TeamSetup::OneTeam {
+                    <div class="btn-group" role="group" aria-label="Themed players">
+                        {themed_player_buttons}
+                    </div>
+
+                    <p class="form-text">
+                        {"
+                        When the whole team rarely shares a skinset, also find comps where only some of the players do. 
+                        The rest are shown as off-theme.
+                        "}
+                    </p>
+                }
+
+                <h5> {"Score weights"} </h5>
+                <div class="row row-cols-2 row-cols-md-4">
+                    {score_weight_sliders}
+                </div>
+
+                <p class="form-text mb-0">
+                    {"
+                    Comps are ranked by their score. Mains count as more preferred than comfortable picks, and 
+                    skinsets can be pinned as favourites with the star next to them in the skinset list.
+                    "}
+                </p>
+            </div>
+        </div>
+    }
+}
@@ -1,6 +1,6 @@
 //! Component/card listing all the skinsets to be played.
 
-use crate::{components::button::Button, constants::SkinsetId};
+use crate::{components::button::Button, constants::{SkinsetId, SkinsetMask, SkinsetSource}};
 use uuid::Uuid;
 use yew::{prelude::*, html::Scope};
 use yew_icons::{Icon, IconId};
@@ -28,6 +28,17 @@ impl SkinsetList {
     // }
 }
 
+/// Properties passed to the SkinsetList component.
+#[derive(Properties, PartialEq)]
+pub struct SkinsetListProps {
+    /// Which skinset table's skinsets to list.
+    pub skinset_source: SkinsetSource,
+    /// The set of excluded skinsets -- passed down so that this re-renders when other components change it.
+    pub skinsets_excluded: SkinsetMask,
+    /// The set of skinsets pinned as favourites.
+    pub favourite_skinsets: SkinsetMask,
+}
+
 /// Messages passed to the SkinsetList component.
 pub enum Msg {
     /// Toggle whether the body is visible or hidden.
@@ -41,12 +52,15 @@ pub enum Msg {
 
     /// Toggle the exclusion of a specific skinset.
     ToggleSkinset { skinset_id: SkinsetId },
+
+    /// Toggle whether a specific skinset is pinned as a favourite.
+    ToggleFavourite { skinset_id: SkinsetId },
 }
 
 impl Component for SkinsetList {
     type Message = Msg;
 
-    type Properties = ();
+    type Properties = SkinsetListProps;
 
     fn create(_: &Context<Self>) -> Self {
         SkinsetList { collapsed: false }
@@ -80,6 +94,13 @@ impl Component for SkinsetList {
                 // Do not re-render this component separately.
                 false
             }
+
+            Msg::ToggleFavourite { skinset_id } => {
+                // Bubble message to parent.
+                SkinsetList::get_parent_app_scope(ctx).send_message(AppMsg::ToggleFavouriteSkinset { skinset_id });
+                // Do not re-render this component separately.
+                false
+            }
         }
     }
 
@@ -122,28 +143,25 @@ impl Component for SkinsetList {
                 if !self.collapsed {
                     <div class="card-body row row-cols-6" id={collapse_id.clone()}>
                         {{
-                            // Get the parent app scope. 
-                            let parent_app_scope = SkinsetList::get_parent_app_scope(ctx);
-                            // Get the parent app itself (as a ref). 
-                            let parent_app = parent_app_scope
-                                .get_component()
-                                .expect("got parent App Component");
-
-                            // Get a reference to the list of excluded skinsets from the parent component. 
-                            let excluded_skinsets: &[SkinsetId] = parent_app.skinsets_excluded.as_slice();
+                            // Get a copy of the set of excluded skinsets. 
+                            let excluded_skinsets: SkinsetMask = ctx.props().skinsets_excluded;
 
-                            // Get an iterator over all skinset ids in alphabetical order by name. 
-                            let all_skinsets = SkinsetId::iter_all();
+                            // Get an iterator over the skinset ids in the selected table in alphabetical order by name. 
+                            let all_skinsets = ctx.props().skinset_source.skinset_mask().iter();
 
                             // Iterate and generate html checkboxes and labels
                             all_skinsets
                                 .map(|skinset_id: SkinsetId| {
                                     // Determine wether this skinset is checked.
-                                    let checked = !excluded_skinsets.contains(&skinset_id);
+                                    let checked = !excluded_skinsets.contains(skinset_id);
                                     // Make an ID for the checkbox.
                                     let checkbox_id: AttrValue = Uuid::new_v4().to_string().into();
                                     // Make the on-change callback.
                                     let onchange = ctx.link().callback(move |_| Msg::ToggleSkinset { skinset_id });
+                                    // Show a filled star for favourites, which can be clicked to unpin them.
+                                    let favourite = ctx.props().favourite_skinsets.contains(skinset_id);
+                                    let star_icon = if favourite { IconId::BootstrapStarFill } else { IconId::BootstrapStar };
+                                    let on_star_click = ctx.link().callback(move |_| Msg::ToggleFavourite { skinset_id });
 
                                     // Make a transformed skinset name to handle long skinset names.
                                     let transformed_skinset_name = if skinset_id.skinset_name().len() > 22 {
@@ -164,6 +182,14 @@ impl Component for SkinsetList {
                                             <label class="form-check-label" for={checkbox_id}>
                                                 {transformed_skinset_name}
                                             </label>
+                                            <button
+                                                type="button"
+                                                class="btn btn-link btn-sm p-0 ms-1 align-baseline text-warning"
+                                                title={if favourite { "Unpin favourite" } else { "Pin as favourite" }}
+                                                onclick={on_star_click}
+                                            >
+                                                <Icon icon_id={star_icon} width={"1em"} height={"1em"} />
+                                            </button>
                                         </div>
                                     }
                                 })
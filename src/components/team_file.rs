@@ -0,0 +1,86 @@
+//! Buttons to export the team to a JSON file and import it again.
+
+use crate::persist::SavedState;
+use web_sys::{Element, File, HtmlInputElement};
+use yew::prelude::*;
+
+/// Properties passed to the team file buttons.
+#[derive(Properties, PartialEq)]
+pub struct TeamFileProps {
+    /// The state to export.
+    pub saved_state: SavedState,
+    /// Callback emitted with the file chosen to import.
+    pub on_import: Callback<File>,
+}
+
+/// Team file export/import buttons component.
+#[function_component(TeamFile)]
+pub fn team_file(props: &TeamFileProps) -> Html {
+    // Use the file input's node ref to get the chosen file whenever it changes.
+    let input_node_ref = use_node_ref();
+
+    // Make a callback to handle the chosen file.
+    let on_file_change = {
+        // Clone the input node ref.
+        let input_node_ref = input_node_ref.clone();
+        // Clone the callback prop to invoke.
+        let pass_to = props.on_import.clone();
+
+        Callback::from(move |_| {
+            if let Some(input) = input_node_ref.cast::<HtmlInputElement>() {
+                if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                    pass_to.emit(file);
+                }
+
+                // Clear the input so that choosing the same file again still triggers a change.
+                input.set_value("");
+            }
+        })
+    };
+
+    // Use the export link's node ref to point it at the exported file when it's clicked.
+    let export_node_ref = use_node_ref();
+
+    // Make a callback to export the state. The file is only built when it's downloaded, rather than on every render.
+    let on_export_click = {
+        // Clone the export link's node ref.
+        let export_node_ref = export_node_ref.clone();
+        // Clone the state to export.
+        let saved_state = props.saved_state.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            if let Some(link) = export_node_ref.cast::<Element>() {
+                // The link is followed after this handler runs, so it downloads the file set here.
+                if let Err(err) = link.set_attribute("href", &saved_state.to_team_file_url()) {
+                    log::warn!("Failed to export the team file: {err:?}");
+                }
+            }
+        })
+    };
+
+    html! {
+        <>
+            <a
+                ref={export_node_ref}
+                class={"btn btn-outline-secondary"}
+                href={"#"}
+                download={"team.json"}
+                onclick={on_export_click}
+            >
+                {"Export team"}
+            </a>
+
+            // The file input is hidden behind a label styled as a button.
+            <label class={"btn btn-outline-secondary"}>
+                {"Import team"}
+                <input
+                    ref={input_node_ref}
+                    type={"file"}
+                    accept={".json,application/json"}
+                    hidden={true}
+                    onchange={on_file_change}
+                />
+            </label>
+        </>
+    }
+}
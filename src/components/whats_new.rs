@@ -0,0 +1,51 @@
+//! Card listing what changed in the last data update.
+
+use crate::constants::DATA_CHANGES_JSON;
+use crate::data_diff::DataDiff;
+use yew::prelude::*;
+
+/// "What's new" card component. Renders nothing if the build has no changes embedded.
+#[function_component(WhatsNew)]
+pub fn whats_new() -> Html {
+    // The changes only depend on the build, so only parse them once.
+    let data_diff = use_memo((), |_| {
+        DATA_CHANGES_JSON.and_then(|json| {
+            serde_json::from_str::<DataDiff>(json)
+                .map_err(|err| log::warn!("Failed to parse the embedded data changes: {err}"))
+                .ok()
+        })
+    });
+
+    let Some(data_diff) = data_diff.as_ref() else {
+        return Html::default();
+    };
+
+    let sections = data_diff.sections();
+
+    html! {
+        <div class="card bg-light text-dark my-2">
+            <div class="card-body">
+                <details>
+                    <summary class="h3"> {"What's new"} </summary>
+                    <p class="card-text text-muted"> {"Changes to the data from "} {data_diff.date_range()} {"."} </p>
+                    if sections.is_empty() {
+                        <p class="card-text"> {"No champs or skinsets changed in the last update."} </p>
+                    }
+                    {
+                        sections
+                            .into_iter()
+                            .map(|section| html! {
+                                <>
+                                    <h5> {section.title} </h5>
+                                    <ul>
+                                        { section.items.into_iter().map(|item| html! { <li> {item} </li> }).collect::<Html>() }
+                                    </ul>
+                                </>
+                            })
+                            .collect::<Html>()
+                    }
+                </details>
+            </div>
+        </div>
+    }
+}
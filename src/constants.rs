@@ -2,16 +2,53 @@
 //! tables.
 
 use std::iter::FusedIterator;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
 use enumflags2::BitFlags;
-use serde::{Serialize, Deserialize};
-use super::generated::{LANE_DATA, ALL_SKINSET_NAMES, CHAMPS_TO_SKINSETS};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use super::generated::{
+    LANE_DATA, ALL_SKINSET_NAMES, SKINSET_MASK_WORDS, SKIN_DATA, ALL_UNIVERSE_NAMES, SkinsetData, WIKI_SKINSETS, 
+    OFFICIAL_SKINSETS, CHAMP_NAME_LOOKUP
+};
+use crate::champ_names::normalize_champ_name;
 
 /// Re-export the lane enum.
 pub use crate::generated::Lane;
 
+/// Re-export the date the data is from, and what changed in the last update.
+pub use crate::generated::{DATA_CHANGES_JSON, DATA_SOURCE_DATE, DATA_SOURCE_DATE_LONG};
+
+/// Which skinset table to use skinset data from. 
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SkinsetSource {
+    /// The community-maintained skinsets from the wiki table. 
+    #[default]
+    Wiki,
+
+    /// The skinsets as Riot officially groups them. 
+    Official,
+}
+
+impl SkinsetSource {
+    /// Get the generated dataset for this source. 
+    #[inline]
+    const fn data(self) -> &'static SkinsetData {
+        match self {
+            SkinsetSource::Wiki => &WIKI_SKINSETS,
+            SkinsetSource::Official => &OFFICIAL_SKINSETS,
+        }
+    }
+
+    /// Get the set of skinsets that are in this source's table. 
+    #[inline]
+    pub const fn skinset_mask(self) -> SkinsetMask {
+        SkinsetMask(self.data().skinsets)
+    }
+}
+
 /// The ID used to refer to a league of legends champ.
-/// Under the hood this is just an index into the [LANE_DATA] constant. 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+/// Under the hood this is just an index into the [LANE_DATA] constant. This index changes whenever a champ is added, so 
+/// champs are serialized by name instead. 
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ChampId(usize);
 
 impl ChampId {
@@ -23,6 +60,46 @@ impl ChampId {
         (0..LANE_DATA.len()).map(ChampId)
     }
 
+    /// Look up a champion by name or alias, ignoring case, spacing, and punctuation (e.g. "kogmaw", "Kog'Maw", and 
+    /// "KOG MAW" all find Kog'Maw). 
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized_name = normalize_champ_name(name);
+        // The lookup table is sorted by normalized name. 
+        CHAMP_NAME_LOOKUP
+            .binary_search_by_key(&normalized_name.as_str(), |(lookup_name, _)| lookup_name)
+            .ok()
+            .map(|index| ChampId(CHAMP_NAME_LOOKUP[index].1))
+    }
+
+    /// Compile-time function to get the ID of a champ by their exact name. 
+    pub const fn id_of_champ(champ_name: &'static str) -> Self {
+        let mut index: usize = 0;
+
+        while index < LANE_DATA.len() {
+            // Work-around for string equality not being available in const contexts.
+            if const_str::equal!(LANE_DATA[index].0, champ_name) {
+                return ChampId(index);
+            }
+            index += 1;
+        }
+
+        // Panic here if we don't find it -- we cannot use nice format args to make a nice panic message so just do 
+        // this.
+        panic!("BAD CHAMP NAME PASSED TO CONST FN");
+    }
+
+    /// Get the champ at an index into [LANE_DATA], if it's in range. Indices change whenever the data is updated, so 
+    /// prefer names for anything that's kept around. 
+    pub fn from_index(index: usize) -> Option<Self> {
+        (index < LANE_DATA.len()).then_some(ChampId(index))
+    }
+
+    /// Get the index of this champ into [LANE_DATA]. 
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.0
+    }
+
     /// Get the name of the champion that this ID refers to. 
     #[inline]
     pub const fn champ_name(self) -> &'static str {
@@ -35,16 +112,49 @@ impl ChampId {
         LANE_DATA[self.0].1
     }
 
-    /// Get an iterator over all the skinsets available for the champ referred to by this ID.
-    pub fn skinsets(self) -> impl Iterator<Item = SkinsetId> + ExactSizeIterator + DoubleEndedIterator + FusedIterator {
-        CHAMPS_TO_SKINSETS[self.0].iter().map(|index: &usize| SkinsetId(*index))
+    /// Get the set of skinsets available for the champ referred to by this ID in a given skinset source.
+    #[inline]
+    pub const fn skinset_mask(self, source: SkinsetSource) -> SkinsetMask {
+        SkinsetMask(source.data().champs_to_skinsets[self.0])
+    }
+
+    /// Get an iterator over all the skinsets available for the champ referred to by this ID in a given skinset source.
+    pub fn skinsets(self, source: SkinsetSource) -> impl Iterator<Item = SkinsetId> + ExactSizeIterator + DoubleEndedIterator + FusedIterator {
+        self.skinset_mask(source).iter()
+    }
+
+    /// Get an iterator over the skins the champ referred to by this ID has in a given skinset in a given skinset 
+    /// source. 
+    pub fn skins_in(self, skinset_id: SkinsetId, source: SkinsetSource) -> impl DoubleEndedIterator<Item = SkinId> + FusedIterator {
+        // The skin table is sorted by champ then skinset, so the skins we want are in one contiguous range. 
+        let start = SKIN_DATA.partition_point(|(champ, skinset, _, _)| (*champ, *skinset) < (self.0, skinset_id.0));
+        let end = SKIN_DATA.partition_point(|(champ, skinset, _, _)| (*champ, *skinset) <= (self.0, skinset_id.0));
+        // Filter out the skins that aren't listed in this source's table. 
+        let skin_flag = source.data().skin_flag;
+        (start..end).filter(move |index| SKIN_DATA[*index].3 & skin_flag != 0).map(SkinId)
     }
 }
 
 
+/// Serialize champs by their canonical name.
+impl Serialize for ChampId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.champ_name())
+    }
+}
+
+/// Deserialize champs by name or alias, failing on names that aren't a known champ.
+impl<'de> Deserialize<'de> for ChampId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        ChampId::from_name(&name).ok_or_else(|| D::Error::custom(format!("unknown champion {name:?}")))
+    }
+}
+
 /// The ID used to refer to a league of legends skinset. 
-/// Under the hood this is just an index into the [ALL_SKINSET_NAMES] constant. 
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+/// Under the hood this is just an index into the [ALL_SKINSET_NAMES] constant. Like [ChampId], skinsets are 
+/// serialized by name. 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SkinsetId(usize);
 
 impl SkinsetId {
@@ -61,7 +171,7 @@ impl SkinsetId {
     pub const DEFAULT_EXCLUDED_SKINSETS: &'static [SkinsetId] = &[Self::LEGACY, Self::NA];
 
     /// Compile-time function to get the index of a skinset by name. 
-    const fn id_of_skinset(skinset_name: &'static str) -> Self {
+    pub const fn id_of_skinset(skinset_name: &'static str) -> Self {
         let mut index: usize = 0;
 
         while index < ALL_SKINSET_NAMES.len() {
@@ -82,9 +192,418 @@ impl SkinsetId {
         (0..ALL_SKINSET_NAMES.len()).map(SkinsetId)
     }
 
+    /// Look up a skinset by its exact name. 
+    pub fn from_name(name: &str) -> Option<Self> {
+        // The skinset names are sorted.
+        ALL_SKINSET_NAMES.binary_search(&name).ok().map(SkinsetId)
+    }
+
     /// Get the name of the skinset this ID refers to. 
     #[inline]
     pub const fn skinset_name(self) -> &'static str {
         ALL_SKINSET_NAMES[self.0]
     }
+
+    /// Get the universe that the skinset this ID refers to is part of in a given skinset source. 
+    #[inline]
+    pub const fn universe(self, source: SkinsetSource) -> UniverseId {
+        UniverseId(source.data().skinset_universes[self.0])
+    }
+}
+
+/// The ID used to refer to a skin universe -- a group of related skinsets (e.g. "Crime City Nightmare" contains both 
+/// "Crime City" and "Debonair"). 
+/// Under the hood this is just an index into the [ALL_UNIVERSE_NAMES] constant. 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct UniverseId(usize);
+
+impl UniverseId {
+    /// Get an iterator over all the valid [UniverseId]s. 
+    pub fn iter_all() -> impl DoubleEndedIterator<Item = Self> + FusedIterator + ExactSizeIterator {
+        (0..ALL_UNIVERSE_NAMES.len()).map(UniverseId)
+    }
+
+    /// Get the name of the universe this ID refers to. 
+    #[inline]
+    pub const fn universe_name(self) -> &'static str {
+        ALL_UNIVERSE_NAMES[self.0]
+    }
+
+    /// Get the set of skinsets that are part of this universe in a given skinset source. 
+    pub fn skinset_mask(self, source: SkinsetSource) -> SkinsetMask {
+        SkinsetId::iter_all().filter(|skinset_id| skinset_id.universe(source) == self).collect()
+    }
+}
+
+/// The ID used to refer to an individual champion skin that is part of a skinset. 
+/// Under the hood this is just an index into the [SKIN_DATA] constant. 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct SkinId(usize);
+
+impl SkinId {
+    /// Get an iterator over all the valid [SkinId]s. 
+    pub fn iter_all() -> impl DoubleEndedIterator<Item = Self> + FusedIterator + ExactSizeIterator {
+        (0..SKIN_DATA.len()).map(SkinId)
+    }
+
+    /// Get the full name of the skin this ID refers to (e.g. "Arcana Lux"). 
+    #[inline]
+    pub const fn skin_name(self) -> &'static str {
+        SKIN_DATA[self.0].2
+    }
+
+    /// Get the champ this skin is for. 
+    #[inline]
+    pub const fn champ(self) -> ChampId {
+        ChampId(SKIN_DATA[self.0].0)
+    }
+
+    /// Get the skinset this skin is part of. 
+    #[inline]
+    pub const fn skinset(self) -> SkinsetId {
+        SkinsetId(SKIN_DATA[self.0].1)
+    }
+}
+
+/// Serialize skinsets by name.
+impl Serialize for SkinsetId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.skinset_name())
+    }
+}
+
+/// Deserialize skinsets by name, failing on names that aren't a known skinset.
+impl<'de> Deserialize<'de> for SkinsetId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        SkinsetId::from_name(&name).ok_or_else(|| D::Error::custom(format!("unknown skinset {name:?}")))
+    }
+}
+
+/// A fixed-width set of [SkinsetId]s, stored as a bitset with one bit per skinset. This is serialized as a list of
+/// skinset names rather than the raw bitset. 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SkinsetMask([u64; SKINSET_MASK_WORDS]);
+
+impl SkinsetMask {
+    /// The mask containing no skinsets. 
+    pub const EMPTY: Self = SkinsetMask([0; SKINSET_MASK_WORDS]);
+
+    /// The mask containing every skinset. 
+    pub const ALL: Self = {
+        let mut words = [u64::MAX; SKINSET_MASK_WORDS];
+        // Clear the unused bits past the last skinset in the last word. 
+        let used_bits = ALL_SKINSET_NAMES.len() % 64;
+        if used_bits != 0 {
+            words[SKINSET_MASK_WORDS - 1] = (1 << used_bits) - 1;
+        }
+        SkinsetMask(words)
+    };
+
+    /// Compile-time function to make a mask from a list of skinsets. 
+    pub const fn from_skinsets(skinsets: &[SkinsetId]) -> Self {
+        let mut mask = Self::EMPTY;
+        let mut index: usize = 0;
+
+        while index < skinsets.len() {
+            mask.0[skinsets[index].0 / 64] |= 1 << (skinsets[index].0 % 64);
+            index += 1;
+        }
+
+        mask
+    }
+
+    /// Check whether a skinset is in this mask. 
+    #[inline]
+    pub const fn contains(self, skinset_id: SkinsetId) -> bool {
+        self.0[skinset_id.0 / 64] & (1 << (skinset_id.0 % 64)) != 0
+    }
+
+    /// Add a skinset to this mask. 
+    #[inline]
+    pub fn insert(&mut self, skinset_id: SkinsetId) {
+        self.0[skinset_id.0 / 64] |= 1 << (skinset_id.0 % 64);
+    }
+
+    /// Remove a skinset from this mask. 
+    #[inline]
+    pub fn remove(&mut self, skinset_id: SkinsetId) {
+        self.0[skinset_id.0 / 64] &= !(1 << (skinset_id.0 % 64));
+    }
+
+    /// Add a skinset to this mask if it's not in it, otherwise remove it. 
+    #[inline]
+    pub fn toggle(&mut self, skinset_id: SkinsetId) {
+        self.0[skinset_id.0 / 64] ^= 1 << (skinset_id.0 % 64);
+    }
+
+    /// Check if this mask has no skinsets in it. 
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.0.iter().all(|word| *word == 0)
+    }
+
+    /// Count the number of skinsets in this mask. 
+    #[inline]
+    pub fn count(self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Get an iterator over the skinsets in this mask in [SkinsetId] (alphabetical) order. 
+    pub fn iter(self) -> SkinsetMaskIter {
+        SkinsetMaskIter { words: self.0 }
+    }
+
+    /// Get the universes of the skinsets in this mask in a given skinset source, in [UniverseId] order without 
+    /// duplicates. 
+    pub fn universes(self, source: SkinsetSource) -> Vec<UniverseId> {
+        let mut universes: Vec<UniverseId> = self.iter().map(|skinset_id| skinset_id.universe(source)).collect();
+        universes.sort();
+        universes.dedup();
+        universes
+    }
+
+    /// Get the set of every skinset that is in the same universe as a skinset in this mask in a given skinset source. 
+    pub fn expand_to_universes(self, source: SkinsetSource) -> Self {
+        self.universes(source)
+            .into_iter()
+            .fold(Self::EMPTY, |mask, universe_id| mask | universe_id.skinset_mask(source))
+    }
+}
+
+impl Serialize for SkinsetMask {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for SkinsetMask {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<SkinsetId>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
+impl Default for SkinsetMask {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl BitAnd for SkinsetMask {
+    type Output = Self;
+
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        self &= rhs;
+        self
+    }
+}
+
+impl BitAndAssign for SkinsetMask {
+    fn bitand_assign(&mut self, rhs: Self) {
+        for (word, rhs_word) in self.0.iter_mut().zip(rhs.0) {
+            *word &= rhs_word;
+        }
+    }
+}
+
+impl BitOr for SkinsetMask {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl BitOrAssign for SkinsetMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        for (word, rhs_word) in self.0.iter_mut().zip(rhs.0) {
+            *word |= rhs_word;
+        }
+    }
+}
+
+impl Not for SkinsetMask {
+    type Output = Self;
+
+    /// Get the complement of this mask (every valid skinset not in this mask).
+    fn not(mut self) -> Self::Output {
+        for (word, all_word) in self.0.iter_mut().zip(Self::ALL.0) {
+            *word = !*word & all_word;
+        }
+        self
+    }
+}
+
+impl FromIterator<SkinsetId> for SkinsetMask {
+    fn from_iter<T: IntoIterator<Item = SkinsetId>>(iter: T) -> Self {
+        let mut mask = Self::EMPTY;
+        iter.into_iter().for_each(|skinset_id| mask.insert(skinset_id));
+        mask
+    }
+}
+
+impl IntoIterator for SkinsetMask {
+    type Item = SkinsetId;
+
+    type IntoIter = SkinsetMaskIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the [SkinsetId]s in a [SkinsetMask]. 
+#[derive(Clone, Debug)]
+pub struct SkinsetMaskIter {
+    /// The bits remaining to be iterated over. 
+    words: [u64; SKINSET_MASK_WORDS],
+}
+
+impl Iterator for SkinsetMaskIter {
+    type Item = SkinsetId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Find the first word with any bits remaining. 
+        let (index, word) = self.words.iter_mut().enumerate().find(|(_, word)| **word != 0)?;
+        // Get the lowest set bit and clear it. 
+        let bit = word.trailing_zeros() as usize;
+        *word &= *word - 1;
+        Some(SkinsetId(index * 64 + bit))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = SkinsetMask(self.words).count();
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for SkinsetMaskIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // Find the last word with any bits remaining. 
+        let (index, word) = self.words.iter_mut().enumerate().rfind(|(_, word)| **word != 0)?;
+        // Get the highest set bit and clear it. 
+        let bit = 63 - word.leading_zeros() as usize;
+        *word &= !(1 << bit);
+        Some(SkinsetId(index * 64 + bit))
+    }
+}
+
+impl ExactSizeIterator for SkinsetMaskIter {}
+
+impl FusedIterator for SkinsetMaskIter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Skinsets at the edges of the words in a mask, up to the last skinset.
+    fn edge_skinsets() -> Vec<SkinsetId> {
+        let last = ALL_SKINSET_NAMES.len() - 1;
+        [0, 1, 63, 64, 127, 128, last].into_iter().filter(|index| *index <= last).map(SkinsetId).collect()
+    }
+
+    #[test]
+    fn inserts_and_removes_skinsets_in_every_word() {
+        let mut mask = SkinsetMask::EMPTY;
+
+        for (count, skinset_id) in edge_skinsets().into_iter().enumerate() {
+            assert!(!mask.contains(skinset_id));
+            mask.insert(skinset_id);
+            assert!(mask.contains(skinset_id));
+            assert_eq!(mask.count(), count + 1);
+        }
+
+        for skinset_id in edge_skinsets() {
+            mask.toggle(skinset_id);
+            assert!(!mask.contains(skinset_id));
+            mask.toggle(skinset_id);
+            mask.remove(skinset_id);
+            assert!(!mask.contains(skinset_id));
+        }
+
+        assert!(mask.is_empty());
+        assert_eq!(mask, SkinsetMask::EMPTY);
+    }
+
+    #[test]
+    fn iterates_skinsets_in_order_from_either_end() {
+        let skinsets = edge_skinsets();
+        let mask: SkinsetMask = skinsets.iter().rev().copied().collect();
+
+        assert_eq!(mask, SkinsetMask::from_skinsets(&skinsets));
+        assert_eq!(mask.iter().len(), skinsets.len());
+        assert_eq!(mask.iter().collect::<Vec<_>>(), skinsets);
+        assert_eq!(mask.iter().rev().collect::<Vec<_>>(), skinsets.iter().rev().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn combines_masks_within_the_valid_skinsets() {
+        let mask: SkinsetMask = edge_skinsets().into_iter().collect();
+        let other: SkinsetMask = edge_skinsets().into_iter().step_by(2).chain([SkinsetId(2)]).collect();
+
+        assert_eq!(SkinsetMask::ALL.count(), ALL_SKINSET_NAMES.len());
+        assert_eq!(SkinsetMask::ALL.iter().collect::<Vec<_>>(), SkinsetId::iter_all().collect::<Vec<_>>());
+        assert_eq!(!SkinsetMask::EMPTY, SkinsetMask::ALL);
+        assert_eq!(!SkinsetMask::ALL, SkinsetMask::EMPTY);
+        assert_eq!(mask | !mask, SkinsetMask::ALL);
+        assert!((mask & !mask).is_empty());
+
+        let shared: Vec<SkinsetId> = edge_skinsets().into_iter().step_by(2).collect();
+        assert_eq!((mask & other).iter().collect::<Vec<_>>(), shared);
+        assert_eq!((mask | other).count(), mask.count() + 1);
+    }
+
+    #[test]
+    fn expands_skinsets_to_their_universes() {
+        for source in [SkinsetSource::Wiki, SkinsetSource::Official] {
+            let mask: SkinsetMask = source.skinset_mask().iter().step_by(10).collect();
+            let expanded = mask.expand_to_universes(source);
+
+            // Every skinset is still there, along with the rest of its universe.
+            assert_eq!(expanded & mask, mask);
+            assert_eq!(expanded.universes(source), mask.universes(source));
+            assert!(expanded.iter().all(|skinset_id| mask.universes(source).contains(&skinset_id.universe(source))));
+
+            let universes = mask.universes(source);
+            assert!(universes.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn finds_champs_by_any_spelling_of_their_name() {
+        let kog_maw = ChampId::from_name("Kog'Maw").expect("champ exists");
+        assert_eq!(kog_maw.champ_name(), "Kog'Maw");
+
+        for name in ["KogMaw", "kog maw", "KOG'MAW"] {
+            assert_eq!(ChampId::from_name(name), Some(kog_maw));
+        }
+
+        // Aliases find the champ they're for.
+        let wukong = ChampId::from_name("Wukong").expect("champ exists");
+        assert_eq!(ChampId::from_name("MonkeyKing"), Some(wukong));
+        assert_eq!(ChampId::from_name("Nunu"), ChampId::from_name("Nunu & Willump"));
+
+        assert_eq!(ChampId::from_name("Not A Champ"), None);
+        assert_eq!(ChampId::from_name(""), None);
+    }
+
+    #[test]
+    fn finds_every_champ_by_their_own_name() {
+        for champ_id in ChampId::iter_all() {
+            assert_eq!(ChampId::from_name(champ_id.champ_name()), Some(champ_id));
+        }
+    }
+
+    #[test]
+    fn serializes_masks_as_skinset_names() {
+        let mask: SkinsetMask = edge_skinsets().into_iter().collect();
+
+        let json = serde_json::to_value(mask).expect("mask serializes");
+        let names: Vec<&str> = edge_skinsets().into_iter().map(SkinsetId::skinset_name).collect();
+        assert_eq!(json, serde_json::json!(names));
+
+        assert_eq!(serde_json::from_value::<SkinsetMask>(json).expect("mask deserializes"), mask);
+        assert!(serde_json::from_value::<SkinsetMask>(serde_json::json!(["Not A Skinset"])).is_err());
+    }
 }
@@ -0,0 +1,466 @@
+//! Changes between two snapshots of the data tables in `assets/`, shared between the native data tools (which find and
+//! save them) and the web app (which shows the changes in the data it was built with).
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::champ_names::champ_key;
+use crate::constants::SkinsetSource;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::data_metadata::{DataMetadata, METADATA_PATH};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::data_tables::{parse_lanes_file, parse_skinsets_file, SkinsetTable};
+#[cfg(not(target_arch = "wasm32"))]
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::BTreeMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+
+/// The path of the changes file the data tools save, relative to the crate root. The build script embeds it if it
+/// exists.
+pub const DATA_CHANGES_PATH: &str = "assets/data-changes.json";
+
+/// The changes from one snapshot of the data tables to another.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataDiff {
+    /// The source date of the old snapshot, as `YYYY-MM-DD`, if it has metadata.
+    pub old_date: Option<String>,
+    /// The source date of the new snapshot, as `YYYY-MM-DD`, if it has metadata.
+    pub new_date: Option<String>,
+    /// Champs in the new lane table that aren't in the old one.
+    pub new_champs: Vec<ChampLanes>,
+    /// Champs in both lane tables whose lanes changed.
+    pub lane_changes: Vec<LaneChange>,
+    /// The changes to each skinset table.
+    pub skinset_changes: Vec<SkinsetChanges>,
+}
+
+/// A champ and the lanes they're played in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChampLanes {
+    /// The name of the champ.
+    pub champ: String,
+    /// The names of the lanes the champ is played in.
+    pub lanes: Vec<String>,
+}
+
+/// A champ whose lanes changed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaneChange {
+    /// The name of the champ.
+    pub champ: String,
+    /// The names of the lanes the champ was played in.
+    pub old_lanes: Vec<String>,
+    /// The names of the lanes the champ is played in now.
+    pub new_lanes: Vec<String>,
+}
+
+/// The changes to one skinset table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkinsetChanges {
+    /// The skinset table that changed.
+    pub source: SkinsetSource,
+    /// Skinsets in the new table that aren't in the old one, with all of their champs.
+    pub new_skinsets: Vec<SkinsetChamps>,
+    /// The names of the skinsets in the old table that aren't in the new one.
+    pub removed_skinsets: Vec<String>,
+    /// Skinsets in both tables, with the champs that have a skin in them now but didn't before.
+    pub champs_added: Vec<SkinsetChamps>,
+}
+
+/// A skinset and some of the champs with a skin in it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkinsetChamps {
+    /// The name of the skinset.
+    pub skinset: String,
+    /// The names of the champs.
+    pub champs: Vec<String>,
+}
+
+/// A titled list of changes, for display.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffSection {
+    /// The title of the section (e.g. "New champions").
+    pub title: String,
+    /// One line per change.
+    pub items: Vec<String>,
+}
+
+impl SkinsetChanges {
+    /// Check if the skinset table didn't change.
+    pub fn is_empty(&self) -> bool {
+        self.new_skinsets.is_empty() && self.removed_skinsets.is_empty() && self.champs_added.is_empty()
+    }
+}
+
+impl DataDiff {
+    /// Check if nothing changed between the snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.new_champs.is_empty() && self.lane_changes.is_empty() && self.skinset_changes.iter().all(SkinsetChanges::is_empty)
+    }
+
+    /// Get a short description of the snapshots compared (e.g. "2023-12-08 to 2024-01-10").
+    pub fn date_range(&self) -> String {
+        let old_date = self.old_date.as_deref().unwrap_or("unknown date");
+        let new_date = self.new_date.as_deref().unwrap_or("unknown date");
+        format!("{old_date} to {new_date}")
+    }
+
+    /// List the changes in sections, leaving out sections without any changes.
+    pub fn sections(&self) -> Vec<DiffSection> {
+        let mut sections = vec![
+            DiffSection {
+                title: "New champions".to_owned(),
+                items: self
+                    .new_champs
+                    .iter()
+                    .map(|new_champ| format!("{} ({})", new_champ.champ, new_champ.lanes.join(", ")))
+                    .collect(),
+            },
+            DiffSection {
+                title: "Lane changes".to_owned(),
+                items: self
+                    .lane_changes
+                    .iter()
+                    .map(|change| format!("{}: {} → {}", change.champ, change.old_lanes.join(", "), change.new_lanes.join(", ")))
+                    .collect(),
+            },
+        ];
+
+        for changes in &self.skinset_changes {
+            let source = match changes.source {
+                SkinsetSource::Wiki => "wiki",
+                SkinsetSource::Official => "official",
+            };
+
+            sections.push(DiffSection {
+                title: format!("New {source} skinsets"),
+                items: changes
+                    .new_skinsets
+                    .iter()
+                    .map(|skinset| format!("{}: {}", skinset.skinset, skinset.champs.join(", ")))
+                    .collect(),
+            });
+
+            sections.push(DiffSection {
+                title: format!("Removed {source} skinsets"),
+                items: changes.removed_skinsets.clone(),
+            });
+
+            sections.push(DiffSection {
+                title: format!("Champions added to {source} skinsets"),
+                items: changes
+                    .champs_added
+                    .iter()
+                    .map(|skinset| format!("{}: {}", skinset.skinset, skinset.champs.join(", ")))
+                    .collect(),
+            });
+        }
+
+        sections.retain(|section| !section.items.is_empty());
+        sections
+    }
+
+    /// Format the changes as a Markdown report.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Data changes ({})\n", self.date_range());
+
+        if self.is_empty() {
+            markdown.push_str("\nNo changes.\n");
+        }
+
+        for section in self.sections() {
+            markdown.push_str(&format!("\n## {}\n\n", section.title));
+
+            for item in &section.items {
+                markdown.push_str(&format!("- {item}\n"));
+            }
+        }
+
+        markdown
+    }
+}
+
+/// The data tables in one snapshot directory.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct Snapshot {
+    /// The source date from the metadata, if the snapshot has any.
+    pub source_date: Option<String>,
+    /// Each champ and their lanes, keyed by [champ_key].
+    pub champ_lanes: BTreeMap<String, (String, Vec<&'static str>)>,
+    /// The skinset table of each source.
+    pub skinset_tables: [(SkinsetSource, SkinsetTable); 2],
+}
+
+/// Read a file in a snapshot directory.
+#[cfg(not(target_arch = "wasm32"))]
+fn read_file(dir: &Path, file_name: &str) -> anyhow::Result<String> {
+    let path = dir.join(file_name);
+    std::fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+/// Get the path of a crate-relative file in a snapshot directory.
+#[cfg(not(target_arch = "wasm32"))]
+fn snapshot_path(dir: &Path, crate_path: &str) -> PathBuf {
+    dir.join(Path::new(crate_path).file_name().expect("asset path has a file name"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Snapshot {
+    /// Make a snapshot from parsed data tables.
+    pub fn new(
+        source_date: Option<String>,
+        champ_lanes: Vec<(String, Vec<&'static str>)>,
+        wiki_table: SkinsetTable,
+        official_table: SkinsetTable,
+    ) -> Self {
+        Snapshot {
+            source_date,
+            champ_lanes: champ_lanes.into_iter().map(|(champ, lanes)| (champ_key(&champ), (champ, lanes))).collect(),
+            skinset_tables: [(SkinsetSource::Wiki, wiki_table), (SkinsetSource::Official, official_table)],
+        }
+    }
+
+    /// Read and parse the data tables in a directory. Snapshots from before the metadata file was added are read
+    /// without a source date.
+    pub fn read(dir: &Path) -> anyhow::Result<Self> {
+        let source_date = match std::fs::read_to_string(snapshot_path(dir, METADATA_PATH)) {
+            Ok(json) => Some(serde_json::from_str::<DataMetadata>(&json).context("invalid data metadata")?.source_date),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err).context("failed to read the data metadata"),
+        };
+
+        Ok(Snapshot::new(
+            source_date,
+            parse_lanes_file(&read_file(dir, "champ-lanes-table.html")?),
+            parse_skinsets_file(&read_file(dir, "wiki-sets-table.html")?),
+            parse_skinsets_file(&read_file(dir, "official-sets-table.html")?),
+        ))
+    }
+}
+
+/// Map each skinset in a table to its champs, keyed by [champ_key].
+#[cfg(not(target_arch = "wasm32"))]
+fn skinset_champs(table: &SkinsetTable) -> BTreeMap<&str, BTreeMap<String, &str>> {
+    let mut skinset_champs: BTreeMap<&str, BTreeMap<String, &str>> =
+        table.all_skinsets.iter().map(|skinset| (skinset.as_str(), BTreeMap::new())).collect();
+
+    for (champ, skinsets) in &table.champ_to_skinsets {
+        for skinset in skinsets {
+            skinset_champs.entry(skinset).or_default().insert(champ_key(champ), champ);
+        }
+    }
+
+    skinset_champs
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SkinsetChanges {
+    /// Compare the skinset tables of one source.
+    fn compare(source: SkinsetSource, old: &SkinsetTable, new: &SkinsetTable) -> Self {
+        let old_skinsets = skinset_champs(old);
+        let new_skinsets = skinset_champs(new);
+
+        // Champs are listed by name, in the order their keys sort in.
+        let champ_names = |champs: &BTreeMap<String, &str>| champs.values().map(|champ| champ.to_string()).collect();
+
+        SkinsetChanges {
+            source,
+            new_skinsets: new_skinsets
+                .iter()
+                .filter(|(skinset, _)| !old_skinsets.contains_key(*skinset))
+                .map(|(skinset, champs)| SkinsetChamps { skinset: skinset.to_string(), champs: champ_names(champs) })
+                .collect(),
+            removed_skinsets: old_skinsets
+                .keys()
+                .filter(|skinset| !new_skinsets.contains_key(*skinset))
+                .map(|skinset| skinset.to_string())
+                .collect(),
+            champs_added: new_skinsets
+                .iter()
+                .filter_map(|(skinset, champs)| {
+                    let old_champs = old_skinsets.get(skinset)?;
+                    let added: BTreeMap<String, &str> = champs
+                        .iter()
+                        .filter(|(key, _)| !old_champs.contains_key(*key))
+                        .map(|(key, champ)| (key.clone(), *champ))
+                        .collect();
+
+                    (!added.is_empty())
+                        .then(|| SkinsetChamps { skinset: skinset.to_string(), champs: champ_names(&added) })
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DataDiff {
+    /// Compare two snapshots.
+    pub fn compare(old: &Snapshot, new: &Snapshot) -> Self {
+        let mut diff = DataDiff {
+            old_date: old.source_date.clone(),
+            new_date: new.source_date.clone(),
+            ..DataDiff::default()
+        };
+
+        let lane_names = |lanes: &[&str]| lanes.iter().map(|lane| lane.to_string()).collect();
+
+        for (key, (champ, lanes)) in &new.champ_lanes {
+            match old.champ_lanes.get(key) {
+                None => diff.new_champs.push(ChampLanes { champ: champ.clone(), lanes: lane_names(lanes) }),
+
+                // Lanes are always listed in the same order, so they can be compared directly.
+                Some((_, old_lanes)) if old_lanes != lanes => diff.lane_changes.push(LaneChange {
+                    champ: champ.clone(),
+                    old_lanes: lane_names(old_lanes),
+                    new_lanes: lane_names(lanes),
+                }),
+
+                Some(_) => {}
+            }
+        }
+
+        diff.skinset_changes = old
+            .skinset_tables
+            .iter()
+            .zip(&new.skinset_tables)
+            .map(|((source, old_table), (_, new_table))| SkinsetChanges::compare(*source, old_table, new_table))
+            .collect();
+
+        diff
+    }
+
+    /// Save the changes in a snapshot directory, where the build script picks them up. Returns the path written.
+    pub fn save(&self, dir: &Path) -> anyhow::Result<PathBuf> {
+        let path = snapshot_path(dir, DATA_CHANGES_PATH);
+        std::fs::write(&path, format!("{}\n", serde_json::to_string_pretty(self)?))
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Remove the changes saved in a snapshot directory, if there are any. Returns whether there were.
+    pub fn remove_saved(dir: &Path) -> anyhow::Result<bool> {
+        let path = snapshot_path(dir, DATA_CHANGES_PATH);
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err).with_context(|| format!("failed to remove {}", path.display())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+
+    /// Make a skinset table from (champ, skinsets) pairs.
+    fn skinset_table(champ_skinsets: &[(&str, &[&str])]) -> SkinsetTable {
+        let champ_to_skinsets: HashMap<String, HashSet<String>> = champ_skinsets
+            .iter()
+            .map(|(champ, skinsets)| (champ.to_string(), skinsets.iter().map(|skinset| skinset.to_string()).collect()))
+            .collect();
+
+        SkinsetTable {
+            all_skinsets: champ_to_skinsets.values().flatten().cloned().collect(),
+            champ_to_skinsets,
+            skins: Vec::new(),
+            skinset_universes: HashMap::new(),
+        }
+    }
+
+    /// Make a snapshot with the same skinsets in both tables.
+    fn snapshot(date: &str, champ_lanes: &[(&str, &[&'static str])], champ_skinsets: &[(&str, &[&str])]) -> Snapshot {
+        Snapshot::new(
+            Some(date.to_owned()),
+            champ_lanes.iter().map(|(champ, lanes)| (champ.to_string(), lanes.to_vec())).collect(),
+            skinset_table(champ_skinsets),
+            skinset_table(champ_skinsets),
+        )
+    }
+
+    #[test]
+    fn finds_new_champs_and_lane_changes() {
+        let old = snapshot("2024-01-01", &[("Ahri", &["Middle"]), ("Jinx", &["Bottom"])], &[]);
+        let new = snapshot(
+            "2024-02-01",
+            &[("Ahri", &["Middle"]), ("Jinx", &["Middle", "Bottom"]), ("Hwei", &["Middle", "Support"])],
+            &[],
+        );
+
+        let diff = DataDiff::compare(&old, &new);
+
+        assert_eq!(diff.date_range(), "2024-01-01 to 2024-02-01");
+        assert_eq!(
+            diff.new_champs,
+            vec![ChampLanes { champ: "Hwei".into(), lanes: vec!["Middle".into(), "Support".into()] }]
+        );
+        assert_eq!(
+            diff.lane_changes,
+            vec![LaneChange {
+                champ: "Jinx".into(),
+                old_lanes: vec!["Bottom".into()],
+                new_lanes: vec!["Middle".into(), "Bottom".into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn finds_skinset_changes() {
+        let old = snapshot("2024-01-01", &[], &[("Ahri", &["Arcade", "Coven"]), ("Jinx", &["Odyssey"])]);
+        let new = snapshot(
+            "2024-02-01",
+            &[],
+            &[
+                ("Ahri", &["Arcade", "Star Guardian"]),
+                ("Jinx", &["Arcade", "Star Guardian"]),
+                ("Lux", &["Star Guardian"]),
+            ],
+        );
+
+        let diff = DataDiff::compare(&old, &new);
+
+        for (changes, source) in diff.skinset_changes.iter().zip([SkinsetSource::Wiki, SkinsetSource::Official]) {
+            assert_eq!(changes.source, source);
+            assert_eq!(
+                changes.new_skinsets,
+                vec![SkinsetChamps {
+                    skinset: "Star Guardian".into(),
+                    champs: vec!["Ahri".into(), "Jinx".into(), "Lux".into()],
+                }]
+            );
+            assert_eq!(changes.removed_skinsets, vec!["Coven".to_owned(), "Odyssey".to_owned()]);
+            assert_eq!(
+                changes.champs_added,
+                vec![SkinsetChamps { skinset: "Arcade".into(), champs: vec!["Jinx".into()] }]
+            );
+        }
+    }
+
+    #[test]
+    fn matches_champs_by_normalized_name_and_alias() {
+        let old = snapshot(
+            "2024-01-01",
+            &[("Kog'Maw", &["Bottom"]), ("Nunu and Willump", &["Jungle"])],
+            &[("KogMaw", &["Arcade"]), ("MonkeyKing", &["Arcade"])],
+        );
+        let new = snapshot(
+            "2024-02-01",
+            &[("Kog'Maw", &["Bottom"]), ("Nunu & Willump", &["Jungle"])],
+            &[("Kog'Maw", &["Arcade"]), ("Wukong", &["Arcade"])],
+        );
+
+        assert!(DataDiff::compare(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn finds_no_changes_in_the_same_tables() {
+        let assets = Snapshot::read(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/assets"))).expect("assets read");
+
+        let diff = DataDiff::compare(&assets, &assets);
+
+        assert!(diff.is_empty());
+        assert!(diff.to_markdown().ends_with("\nNo changes.\n"));
+    }
+}
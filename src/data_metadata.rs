@@ -0,0 +1,113 @@
+//! Metadata about the scraped data tables in `assets/`, shared between the `update-data` tool (which writes it) and the
+//! build script (which embeds it so the web app can show when its data is from).
+
+use serde::{Deserialize, Serialize};
+
+/// The path of the metadata file, relative to the crate root.
+pub const METADATA_PATH: &str = "assets/data-metadata.json";
+
+/// The names of the months, for formatting dates.
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Metadata about the data tables.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataMetadata {
+    /// The date the source pages were saved, as `YYYY-MM-DD`.
+    pub source_date: String,
+}
+
+/// Parse a `YYYY-MM-DD` date into its year, month, and day, if it's a real date.
+pub fn parse_date(date: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = date.splitn(3, '-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+
+    let (year, month, day): (u32, u32, u32) = (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+
+    let leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+
+    (1..=days_in_month).contains(&day).then_some((year, month, day))
+}
+
+/// Format a `YYYY-MM-DD` date the way the web app shows it (e.g. "December 8th, 2023"), if it's a real date.
+pub fn format_date_long(date: &str) -> Option<String> {
+    let (year, month, day) = parse_date(date)?;
+
+    // 11th through 13th are exceptions to the usual suffixes.
+    let suffix = match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    Some(format!("{} {day}{suffix}, {year}", MONTH_NAMES[month as usize - 1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_real_dates() {
+        assert_eq!(parse_date("2023-12-08"), Some((2023, 12, 8)));
+        assert_eq!(parse_date("2024-02-29"), Some((2024, 2, 29)));
+        assert_eq!(parse_date("2000-02-29"), Some((2000, 2, 29)));
+    }
+
+    #[test]
+    fn rejects_dates_that_dont_exist_or_arent_padded() {
+        for date in ["2023-02-29", "1900-02-29", "2023-04-31", "2023-13-01", "2023-00-10", "2023-01-00"] {
+            assert_eq!(parse_date(date), None, "{date}");
+        }
+
+        for date in ["2023-1-08", "23-12-08", "2023-12-8", "2023/12/08", "2023-12", "", "2023-12-08-01"] {
+            assert_eq!(parse_date(date), None, "{date}");
+        }
+    }
+
+    #[test]
+    fn formats_dates_with_ordinal_suffixes() {
+        let formatted = |date| format_date_long(date).expect("date is real");
+
+        assert_eq!(formatted("2023-12-08"), "December 8th, 2023");
+        assert_eq!(formatted("2024-01-01"), "January 1st, 2024");
+        assert_eq!(formatted("2024-03-02"), "March 2nd, 2024");
+        assert_eq!(formatted("2024-05-03"), "May 3rd, 2024");
+        assert_eq!(formatted("2024-06-21"), "June 21st, 2024");
+        assert_eq!(formatted("2024-07-22"), "July 22nd, 2024");
+        assert_eq!(formatted("2024-08-23"), "August 23rd, 2024");
+        assert_eq!(formatted("2024-10-31"), "October 31st, 2024");
+
+        // The teens all take "th".
+        assert_eq!(formatted("2024-11-11"), "November 11th, 2024");
+        assert_eq!(formatted("2024-11-12"), "November 12th, 2024");
+        assert_eq!(formatted("2024-11-13"), "November 13th, 2024");
+
+        assert_eq!(format_date_long("2024-02-30"), None);
+    }
+}
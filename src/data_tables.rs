@@ -0,0 +1,391 @@
+//! Parsing of the scraped data tables in `assets/` (and the Riot JSON data read with the `riot-data` feature), shared
+//! between the build script (which compiles them into the app) and the native data tools.
+
+use anyhow::Context;
+use scraper::{Html, Selector};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+/// Everything parsed out of a skinset table.
+pub struct SkinsetTable {
+    /// Map from champ name to the names of the skinsets they have skins in.
+    pub champ_to_skinsets: HashMap<String, HashSet<String>>,
+    /// The names of all the skinsets in the table.
+    pub all_skinsets: HashSet<String>,
+    /// Every skin as (champ name, skinset name, skin name).
+    pub skins: Vec<(String, String, String)>,
+    /// Map from skinset name to the name of the universe it is listed under. A few skinsets are listed under more than
+    /// one universe -- these are mapped to the first one they appear under so that every skinset has one universe.
+    pub skinset_universes: HashMap<String, String>,
+}
+
+/// The parts of Data Dragon's `championFull.json` that are used.
+#[derive(Deserialize)]
+struct DataDragonChampions {
+    /// Every champ, keyed by their internal name.
+    data: HashMap<String, DataDragonChampion>,
+}
+
+/// A champ in Data Dragon.
+#[derive(Deserialize)]
+struct DataDragonChampion {
+    /// The champ's display name.
+    name: String,
+    /// The champ's skins, including their default skin.
+    skins: Vec<DataDragonSkin>,
+}
+
+/// A skin in Data Dragon.
+#[derive(Deserialize)]
+struct DataDragonSkin {
+    /// The skin's ID, shared with CommunityDragon.
+    id: String,
+    /// The skin's number for the champ -- 0 is the default skin.
+    num: u32,
+    /// The skin's name.
+    name: String,
+}
+
+/// A skin in CommunityDragon's `skins.json`.
+#[derive(Deserialize)]
+struct CommunityDragonSkin {
+    /// The skin lines the skin is in, if any.
+    #[serde(rename = "skinLines", default)]
+    skin_lines: Option<Vec<CommunityDragonRef>>,
+}
+
+/// A reference to another CommunityDragon entry by ID.
+#[derive(Deserialize)]
+struct CommunityDragonRef {
+    /// The ID of the entry.
+    id: u32,
+}
+
+/// A skin line in CommunityDragon's `skinlines.json`.
+#[derive(Deserialize)]
+struct CommunityDragonSkinline {
+    /// The skin line's ID.
+    id: u32,
+    /// The skin line's name.
+    name: String,
+}
+
+/// A universe in CommunityDragon's `universes.json`.
+#[derive(Deserialize)]
+struct CommunityDragonUniverse {
+    /// The universe's name.
+    name: String,
+    /// The IDs of the skin lines in the universe.
+    #[serde(rename = "skinSets")]
+    skin_sets: Vec<u32>,
+}
+
+/// Parse the skinsets file from html and return a map from champ name -> skinsets, a set of all the skinset names,
+/// a list of every skin, and the universe of every skinset.
+///
+/// Adapted from original runtime version.
+pub fn parse_skinsets_file(skinsets_html: &str) -> SkinsetTable {
+    // Parse the fragment we're useing.
+    let fragment = Html::parse_fragment(skinsets_html);
+    // Make a selector to get rows out of the table.
+    let rows_selector: Selector = Selector::parse("tr").expect("rows selector good");
+    // Make a selector to find champs from a row element.
+    let champs_selector: Selector = Selector::parse("li > span").expect("champ selector good");
+    // Make a selector to find the set name from a row ref.
+    let set_name_selector: Selector =
+        Selector::parse("th:last-of-type").expect("set name selector good");
+    // Make a selector to find the header cells of a row (the universe name is in an extra one spanning multiple rows).
+    let header_selector: Selector = Selector::parse("th").expect("header selector good");
+    // Make an iterator to go over all the rows of the skinset table, skipping the header row.
+    let row_iter = fragment.select(&rows_selector).skip(1);
+    // Make the champ-skinset map to populate
+    let mut champ_to_skinset_map: HashMap<String, HashSet<String>> = HashMap::new();
+    // Make set of all skinsets to store and pass out too.
+    let mut set_of_all_skinsets: HashSet<String> = HashSet::new();
+    // Make the list of individual skins.
+    let mut skins: Vec<(String, String, String)> = Vec::new();
+    // Make the map of skinsets to their universes.
+    let mut skinset_universes: HashMap<String, String> = HashMap::new();
+    // Track the universe of the rows being read -- universe cells span all the rows of the sets in them.
+    let mut current_universe: Option<String> = None;
+
+    // Iterate over all the rows of the table.
+    for row_ref in row_iter {
+        // If this row starts a new universe, the universe name is in the first of two header cells.
+        let mut headers = row_ref.select(&header_selector);
+        if let (Some(universe_ref), Some(_)) = (headers.next(), headers.next()) {
+            current_universe = Some(universe_ref.text().collect::<String>().trim().to_owned());
+        }
+
+        // Get the set name.
+        let set_name: String = row_ref
+            .select(&set_name_selector)
+            .next()
+            .expect("finds set name")
+            .text()
+            .collect::<String>();
+
+        // Insert/upsert into the set of all skinsets.
+        set_of_all_skinsets.insert(set_name.clone());
+        // Record this skinset's universe if it's the first time we've seen it.
+        skinset_universes
+            .entry(set_name.clone())
+            .or_insert_with(|| current_universe.clone().expect("first row has a universe"));
+
+        // Get an iterator over all the champ names in this set with the name of their skin.
+        let champs_iter = row_ref.select(&champs_selector).map(|champ_el_ref| {
+            let champ = champ_el_ref
+                // Get the referenced element
+                .value()
+                // Read the 'data-champion' attribute
+                .attr("data-champion")
+                // Crash if it's not there.
+                .expect("champion name available")
+                // Convert to owned string
+                .to_owned();
+
+            // The text of the element is the full skin name (the 'data-skin' attribute leaves out the champ name).
+            let skin_name = champ_el_ref.text().collect::<String>().trim().to_owned();
+
+            (champ, skin_name)
+        });
+
+        // Add all of the champ-skinset mappings into the map.
+        for (champ, skin_name) in champs_iter {
+            champ_to_skinset_map
+                // Get the map entry for this champ.
+                .entry(champ.clone())
+                // Make a new empty one if not recognized.
+                .or_default()
+                // Add the set name to this champ's list.
+                .insert(set_name.clone());
+
+            // Record the skin.
+            skins.push((champ, set_name.clone(), skin_name));
+        }
+    }
+
+    SkinsetTable {
+        champ_to_skinsets: champ_to_skinset_map,
+        all_skinsets: set_of_all_skinsets,
+        skins,
+        skinset_universes,
+    }
+}
+
+/// Parse the lanes table file from html and return a map from champ name -> lanes.
+///
+/// Adapted from original runtime version.
+pub fn parse_lanes_file(lanes_html: &str) -> Vec<(String, Vec<&'static str>)> {
+    // Make map to populate.
+    let mut champ_to_lanes_map = Vec::new();
+    // Load the table fragment into a scrapable document.
+    let fragment: Html = Html::parse_fragment(lanes_html);
+    // Make a selector to get rows out of the table.
+    let rows_selector: Selector = Selector::parse("tbody > tr").expect("rows selector good");
+    // Make a selector to get columns out of a row element.
+    let cols_selector: Selector = Selector::parse("td").expect("cols selector good");
+
+    // Iterate over every row in the table.
+    for row_ref in fragment.select(&rows_selector) {
+        // Assume the children of the row are (in order):
+        // Champ name, top, jungle, mid, bot lane, support, unused.
+
+        // Make an iterator over column elements that takes the exact number we want.
+        let mut cols_iterator = row_ref
+            .select(&cols_selector)
+            // Ignore the unused column.
+            .take(6);
+
+        // Take the champ name from the iterator.
+        let champ_name: String = cols_iterator
+            .next()
+            .expect("Finds champ name element")
+            .value()
+            .attr("data-sort-value")
+            .expect("Finds champ name attribute")
+            .to_owned();
+
+        // Make a list of lanes to populate by iterating over the rest of the columns.
+        let mut lanes: Vec<&'static str> = Vec::with_capacity(5);
+        // Iterate over the remaining columns to populate bitflags.
+        for (index, col) in cols_iterator.enumerate() {
+            if col.value().attr("data-sort-value").is_some() {
+                match index {
+                    0 => lanes.push("Top"),
+                    1 => lanes.push("Jungle"),
+                    2 => lanes.push("Mid"),
+                    3 => lanes.push("Bot"),
+                    4 => lanes.push("Support"),
+                    // Unreachable because we limit the number of <td> tags in the iterator using take()
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        // Add the champ and their lanes to the map. We use insert rather than upsert here because we assume there
+        // are no duplicates in the table.
+        champ_to_lanes_map.push((champ_name, lanes));
+    }
+
+    champ_to_lanes_map
+}
+
+/// Parse the official skin lines from the JSON contents of Data Dragon's `championFull.json` and CommunityDragon's
+/// `skins.json`, `skinlines.json`, and (optionally) `universes.json`, in the same shape as a parsed skinset table.
+/// Skin lines without a universe are put in a universe of their own name.
+pub fn parse_riot_data(
+    champions_json: &str,
+    skins_json: &str,
+    skinlines_json: &str,
+    universes_json: Option<&str>,
+) -> anyhow::Result<SkinsetTable> {
+    let champions: DataDragonChampions =
+        serde_json::from_str(champions_json).context("failed to parse championFull.json")?;
+    let skins: HashMap<String, CommunityDragonSkin> =
+        serde_json::from_str(skins_json).context("failed to parse skins.json")?;
+    let skinlines: Vec<CommunityDragonSkinline> =
+        serde_json::from_str(skinlines_json).context("failed to parse skinlines.json")?;
+
+    let universes: Vec<CommunityDragonUniverse> = match universes_json {
+        Some(universes_json) => serde_json::from_str(universes_json).context("failed to parse universes.json")?,
+        None => Vec::new(),
+    };
+
+    // Map skin line IDs to their names, skipping the unnamed placeholder entry.
+    let skinline_names: HashMap<u32, &String> = skinlines
+        .iter()
+        .filter(|skinline| !skinline.name.is_empty())
+        .map(|skinline| (skinline.id, &skinline.name))
+        .collect();
+
+    let mut table = SkinsetTable {
+        champ_to_skinsets: HashMap::new(),
+        all_skinsets: HashSet::new(),
+        skins: Vec::new(),
+        skinset_universes: HashMap::new(),
+    };
+
+    for champion in champions.data.values() {
+        // Skip each champ's default skin.
+        for skin in champion.skins.iter().filter(|skin| skin.num != 0) {
+            let skin_lines = skins
+                .get(&skin.id)
+                .and_then(|cdragon_skin| cdragon_skin.skin_lines.as_ref())
+                .into_iter()
+                .flatten();
+
+            for skin_line in skin_lines {
+                let Some(skinline_name) = skinline_names.get(&skin_line.id) else {
+                    anyhow::bail!(
+                        "skin {:?} is in skin line {}, which isn't in skinlines.json",
+                        skin.name,
+                        skin_line.id
+                    );
+                };
+
+                table.champ_to_skinsets.entry(champion.name.clone()).or_default().insert((*skinline_name).clone());
+                table.all_skinsets.insert((*skinline_name).clone());
+                table.skins.push((champion.name.clone(), (*skinline_name).clone(), skin.name.clone()));
+            }
+        }
+    }
+
+    // Put each skin line in its universe, or a universe of its own if it isn't in one. Skin lines listed under more
+    // than one universe use the first, like in the wiki tables.
+    for universe in &universes {
+        for skinline_name in universe.skin_sets.iter().filter_map(|id| skinline_names.get(id)) {
+            if table.all_skinsets.contains(*skinline_name) {
+                table.skinset_universes.entry((*skinline_name).clone()).or_insert_with(|| universe.name.clone());
+            }
+        }
+    }
+
+    for skinline_name in &table.all_skinsets {
+        table.skinset_universes.entry(skinline_name.clone()).or_insert_with(|| skinline_name.clone());
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Read a file from the Riot JSON fixture.
+    macro_rules! riot_fixture {
+        ($file_name:literal) => {
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/riot/", $file_name))
+        };
+    }
+
+    /// Parse the Riot JSON fixture, with or without its universes.
+    fn parse_fixture(with_universes: bool) -> SkinsetTable {
+        parse_riot_data(
+            riot_fixture!("championFull.json"),
+            riot_fixture!("skins.json"),
+            riot_fixture!("skinlines.json"),
+            with_universes.then_some(riot_fixture!("universes.json")),
+        )
+        .expect("fixture parses")
+    }
+
+    /// Make a set of names.
+    fn names(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_riot_skin_lines() {
+        let table = parse_fixture(true);
+
+        assert_eq!(table.champ_to_skinsets["Ahri"], names(&["Arcade", "Star Guardian"]));
+        assert_eq!(table.champ_to_skinsets["Lux"], names(&["Elementalist", "Star Guardian"]));
+        assert_eq!(table.champ_to_skinsets["Jinx"], names(&["Odyssey", "Star Guardian"]));
+        assert_eq!(table.all_skinsets, names(&["Arcade", "Elementalist", "Odyssey", "Star Guardian"]));
+
+        // Default skins and skins without a skin line aren't listed.
+        let mut skins: Vec<(&str, &str, &str)> = table
+            .skins
+            .iter()
+            .map(|(champ, skinset, skin)| (champ.as_str(), skinset.as_str(), skin.as_str()))
+            .collect();
+        skins.sort();
+
+        assert_eq!(
+            skins,
+            vec![
+                ("Ahri", "Arcade", "Arcade Ahri"),
+                ("Ahri", "Star Guardian", "Star Guardian Ahri"),
+                ("Jinx", "Odyssey", "Odyssey Jinx"),
+                ("Jinx", "Star Guardian", "Star Guardian Jinx"),
+                ("Lux", "Elementalist", "Elementalist Lux"),
+                ("Lux", "Star Guardian", "Star Guardian Lux"),
+            ]
+        );
+    }
+
+    #[test]
+    fn puts_riot_skin_lines_in_their_universes() {
+        let table = parse_fixture(true);
+
+        assert_eq!(table.skinset_universes["Arcade"], "Gameverse");
+        assert_eq!(table.skinset_universes["Star Guardian"], "Star Guardian");
+        assert_eq!(table.skinset_universes["Odyssey"], "Odyssey");
+
+        // Without the universes file, every skin line is a universe of its own.
+        let table = parse_fixture(false);
+        assert!(table.skinset_universes.iter().all(|(skinset, universe)| skinset == universe));
+        assert_eq!(table.skinset_universes.len(), 4);
+    }
+
+    #[test]
+    fn rejects_skins_in_unknown_skin_lines() {
+        let skinlines_json = r#"[{ "id": 15, "name": "Arcade" }]"#;
+
+        let result =
+            parse_riot_data(riot_fixture!("championFull.json"), riot_fixture!("skins.json"), skinlines_json, None);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! User-editable lane overrides, for champs whose lanes in the wiki data don't match how people actually play them.
+//!
+//! Each override adds and removes lanes from a champ's [ChampId::default_lanes], and is applied whenever the champ is
+//! added to a player.
+
+use crate::constants::{ChampId, Lane};
+use enumflags2::{make_bitflags, BitFlags};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Lane overrides that new users start with.
+const DEFAULT_OVERRIDES: &[(ChampId, LaneOverride)] = &[
+    (
+        ChampId::id_of_champ("Caitlyn"),
+        LaneOverride { added: make_bitflags!(Lane::{Top}), removed: BitFlags::EMPTY },
+    ),
+    (
+        ChampId::id_of_champ("Cho'Gath"),
+        LaneOverride { added: make_bitflags!(Lane::{Support}), removed: BitFlags::EMPTY },
+    ),
+];
+
+/// The lanes added to and removed from a champ's default lanes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaneOverride {
+    /// Lanes the champ is played in despite not being in their default lanes.
+    pub added: BitFlags<Lane>,
+    /// Default lanes the champ isn't played in.
+    pub removed: BitFlags<Lane>,
+}
+
+/// The table of lane overrides, keyed (and ordered) by champ. This serializes as a map from champ name to override.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LaneOverrides(BTreeMap<ChampId, LaneOverride>);
+
+impl Default for LaneOverrides {
+    fn default() -> Self {
+        Self(DEFAULT_OVERRIDES.iter().copied().collect())
+    }
+}
+
+impl LaneOverrides {
+    /// Get the lanes a champ is played in, after applying any override to their default lanes.
+    pub fn lanes_for(&self, champ_id: ChampId) -> BitFlags<Lane> {
+        let lane_override = self.0.get(&champ_id).copied().unwrap_or_default();
+        (champ_id.default_lanes() | lane_override.added) & !lane_override.removed
+    }
+
+    /// Override the lanes a champ is played in. This is stored as the difference from their default lanes, and
+    /// setting a champ back to their default lanes removes their override.
+    pub fn set(&mut self, champ_id: ChampId, lanes: BitFlags<Lane>) {
+        let default_lanes = champ_id.default_lanes();
+
+        if lanes == default_lanes {
+            self.0.remove(&champ_id);
+            return;
+        }
+
+        self.0.insert(
+            champ_id,
+            LaneOverride { added: lanes & !default_lanes, removed: default_lanes & !lanes },
+        );
+    }
+
+    /// Insert an override directly.
+    pub fn insert(&mut self, champ_id: ChampId, lane_override: LaneOverride) {
+        self.0.insert(champ_id, lane_override);
+    }
+
+    /// Remove the override for a champ, so that they use their default lanes again.
+    pub fn remove(&mut self, champ_id: ChampId) {
+        self.0.remove(&champ_id);
+    }
+
+    /// Make an empty override table.
+    pub fn empty() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Get an iterator over the overridden champs and their overrides, in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (ChampId, LaneOverride)> + '_ {
+        self.0.iter().map(|(champ_id, lane_override)| (*champ_id, *lane_override))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Get a champ's ID by their exact name.
+    fn champ(name: &str) -> ChampId {
+        ChampId::from_name(name).expect("champ exists")
+    }
+
+    #[test]
+    fn starts_with_the_default_overrides() {
+        let lane_overrides = LaneOverrides::default();
+        let caitlyn = champ("Caitlyn");
+
+        assert!(!caitlyn.default_lanes().contains(Lane::Top));
+        assert_eq!(lane_overrides.lanes_for(caitlyn), caitlyn.default_lanes() | Lane::Top);
+        assert_eq!(lane_overrides.iter().count(), DEFAULT_OVERRIDES.len());
+
+        // Champs without an override keep their default lanes.
+        assert_eq!(lane_overrides.lanes_for(champ("Ahri")), champ("Ahri").default_lanes());
+        assert_eq!(LaneOverrides::empty().lanes_for(caitlyn), caitlyn.default_lanes());
+    }
+
+    #[test]
+    fn stores_overrides_as_the_difference_from_the_default_lanes() {
+        let ahri = champ("Ahri");
+        let default_lanes = ahri.default_lanes();
+        let removed = default_lanes.iter().next().expect("champ has a default lane");
+        let added = (!default_lanes).iter().next().expect("champ isn't in every lane");
+
+        let mut lane_overrides = LaneOverrides::empty();
+        lane_overrides.set(ahri, default_lanes & !removed | added);
+
+        assert_eq!(lane_overrides.lanes_for(ahri), default_lanes & !removed | added);
+        assert!(!ahri.default_lanes().contains(added));
+        assert_eq!(
+            lane_overrides.iter().collect::<Vec<_>>(),
+            vec![(ahri, LaneOverride { added: added.into(), removed: removed.into() })]
+        );
+
+        // Setting the default lanes again drops the override, as does removing it.
+        lane_overrides.set(ahri, default_lanes);
+        assert_eq!(lane_overrides, LaneOverrides::empty());
+
+        let mut lane_overrides = LaneOverrides::default();
+        lane_overrides.remove(champ("Caitlyn"));
+        assert_eq!(lane_overrides.lanes_for(champ("Caitlyn")), champ("Caitlyn").default_lanes());
+    }
+
+    #[test]
+    fn serializes_as_a_map_of_champ_names() {
+        let lane_overrides = LaneOverrides::default();
+        let json = serde_json::to_value(&lane_overrides).expect("overrides serialize");
+
+        assert!(json.get("Caitlyn").is_some());
+        assert_eq!(serde_json::from_value::<LaneOverrides>(json).expect("overrides deserialize"), lane_overrides);
+
+        // Removing every override round-trips as no overrides, rather than falling back to the defaults.
+        let json = serde_json::to_value(LaneOverrides::empty()).expect("overrides serialize");
+
+        assert_eq!(json, serde_json::json!({}));
+        let deserialized: LaneOverrides = serde_json::from_value(json).expect("overrides deserialize");
+        assert_eq!(deserialized, LaneOverrides::empty());
+    }
+}
@@ -0,0 +1,22 @@
+//! Champ, lane, and skinset data for League of Legends, and the solver used to find team comps that share skinsets.
+//!
+//! This is shared by the web frontend and the native command line tools.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod api;
+pub mod champ_names;
+pub mod constants;
+pub mod data_diff;
+pub mod data_metadata;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod data_tables;
+pub mod lane_overrides;
+pub mod persist;
+pub mod scoring;
+pub mod solver;
+pub mod teams;
+
+/// Content generated by the build script.
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+}
@@ -1,13 +1,11 @@
 use crate::components::App;
 use log::{info, Level};
 
-mod components;
-mod constants;
+// Bring the shared library's modules in at the root so they're used the same way as the frontend's own modules.
+use league_skinset_finder::{constants, data_diff, lane_overrides, persist, scoring, solver, teams};
 
-/// Content generated by the build script.
-mod generated {
-    include!(concat!(env!("OUT_DIR"), "/generated.rs"));
-}
+mod components;
+mod presets;
 
 fn main() {
     // Initialize the logger.
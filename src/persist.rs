@@ -0,0 +1,436 @@
+//! The versioned format that the app state is saved in, and saving it to the browser's local storage.
+//!
+//! Champs and skinsets are saved by name (see the [Serialize] impls in [crate::constants]) so that saved teams keep
+//! working when new champs or skinsets shift the generated indices.
+//!
+//! Within a schema version, changes to the saved shape have to be additive, so that state saved by an older build of
+//! the app still loads: new fields get a `#[serde(default)]`, and values that gain parts still accept their old form
+//! (like champ entries from before preference tiers, see [deserialize_champ_entries]). Any other change (removing,
+//! renaming, or retyping a field) has to bump [SCHEMA_VERSION] and migrate the older versions.
+
+use crate::constants::{ChampId, Lane, SkinsetMask, SkinsetSource};
+use crate::lane_overrides::LaneOverrides;
+use crate::scoring::ScoreWeights;
+use crate::solver::{GameMode, MatchMode};
+use crate::teams::TeamSetup;
+use enumflags2::BitFlags;
+use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+mod link;
+mod team_file;
+
+pub use team_file::ImportProblem;
+
+/// The current version of the saved state schema.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The local storage key the app state is saved under.
+const STORAGE_KEY: &str = "league-skinset-finder.state";
+
+/// Marker for the schema version in saved state. This always serializes as [SCHEMA_VERSION], and fails to deserialize
+/// from any other version so that state saved in an old or newer schema is reported rather than misread.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SchemaVersion;
+
+impl Serialize for SchemaVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(SCHEMA_VERSION)
+    }
+}
+
+impl<'de> Deserialize<'de> for SchemaVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let version = u32::deserialize(deserializer)?;
+
+        if version == SCHEMA_VERSION {
+            Ok(SchemaVersion)
+        } else {
+            Err(D::Error::custom(format!("unsupported schema version {version} (expected {SCHEMA_VERSION})")))
+        }
+    }
+}
+
+/// How much a player wants to play one of their champs. Tiers are ordered from most to least preferred.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PreferenceTier {
+    /// One of the player's mains.
+    Main,
+
+    /// A champ the player is comfortable on.
+    #[default]
+    Comfortable,
+
+    /// A champ the player will play if they have to.
+    Willing,
+}
+
+impl PreferenceTier {
+    /// Every tier, from most to least preferred.
+    pub const ALL: [Self; 3] = [PreferenceTier::Main, PreferenceTier::Comfortable, PreferenceTier::Willing];
+
+    /// Get the name of this tier to show in the UI.
+    pub const fn label(self) -> &'static str {
+        match self {
+            PreferenceTier::Main => "Main",
+            PreferenceTier::Comfortable => "Comfortable",
+            PreferenceTier::Willing => "Willing",
+        }
+    }
+
+    /// Get the least preferred tier. Searching with this as the minimum tier searches every champ.
+    pub const fn least() -> Self {
+        PreferenceTier::Willing
+    }
+
+    /// Check if this tier is at least as preferred as another.
+    pub fn is_at_least(self, other: Self) -> bool {
+        self <= other
+    }
+
+    /// Get how much a champ in this tier counts towards a comp's champ preference score, from 0 to 1.
+    pub const fn preference(self) -> f64 {
+        match self {
+            PreferenceTier::Main => 1.0,
+            PreferenceTier::Comfortable => 0.5,
+            PreferenceTier::Willing => 0.0,
+        }
+    }
+}
+
+/// A champ in a player's pool, with the lanes they play them in and how much they want to play them.
+pub type ChampEntry = (ChampId, BitFlags<Lane>, PreferenceTier);
+
+/// Deserialize a list of champ entries, also accepting entries saved before preference tiers were added (as a champ
+/// and lanes), which get the default tier.
+pub fn deserialize_champ_entries<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<ChampEntry>, D::Error> {
+    /// A champ entry with or without a tier.
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SavedChampEntry {
+        /// An entry with a tier.
+        WithTier(ChampId, BitFlags<Lane>, PreferenceTier),
+        /// An entry saved before tiers were added.
+        WithoutTier(ChampId, BitFlags<Lane>),
+    }
+
+    let entries = Vec::<SavedChampEntry>::deserialize(deserializer)?;
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| match entry {
+            SavedChampEntry::WithTier(champ_id, lanes, tier) => (champ_id, lanes, tier),
+            SavedChampEntry::WithoutTier(champ_id, lanes) => (champ_id, lanes, PreferenceTier::default()),
+        })
+        .collect())
+}
+
+/// State persisted for each player.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PlayerRecord {
+    /// Player name (optional -- resolve with player number otherwise).
+    pub name: Option<String>,
+
+    /// List of champs, what lanes for them, and how much the player wants to play them. This is in the order that
+    /// they're in in the UI.
+    #[serde(deserialize_with = "deserialize_champ_entries")]
+    pub champs: Vec<ChampEntry>,
+}
+
+impl PlayerRecord {
+    /// Create a new player with a given number and otherwise empty fields.
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            champs: Vec::with_capacity(170),
+        }
+    }
+
+    /// Get the champs (and their lanes) in this player's pool that are at least as preferred as a given tier, to pass
+    /// to the solver.
+    pub fn champ_pool(&self, min_tier: PreferenceTier) -> Vec<(ChampId, BitFlags<Lane>)> {
+        self.champs
+            .iter()
+            .filter(|(_, _, tier)| tier.is_at_least(min_tier))
+            .map(|(champ_id, lanes, _)| (*champ_id, *lanes))
+            .collect()
+    }
+
+    /// Get the tier of one of this player's champs, if they have the champ.
+    pub fn tier_of(&self, champ_id: ChampId) -> Option<PreferenceTier> {
+        self.champs
+            .iter()
+            .find(|(iter_champ_id, _, _)| *iter_champ_id == champ_id)
+            .map(|(_, _, tier)| *tier)
+    }
+
+    /// Update the lanes for a champ for this player, or add the champ (in the default tier) to the end of the list if
+    /// they aren't in it already.
+    pub fn upsert_champ(&mut self, champ_id: ChampId, lanes: BitFlags<Lane>) {
+        // Find the champ id to update if they're in this list already.
+        let existing = self.champs
+            .iter_mut()
+            .find(|(iter_champ_id, _, _)| *iter_champ_id == champ_id);
+
+        match existing {
+            // Once the entry is found, update the lanes for the champ.
+            Some(entry) => entry.1 = lanes,
+            // Otherwise add the champ on to the end of the list.
+            None => self.champs.push((champ_id, lanes, PreferenceTier::default())),
+        }
+    }
+
+    /// Update the tier of one of this player's champs.
+    pub fn set_tier(&mut self, champ_id: ChampId, tier: PreferenceTier) {
+        let existing = self.champs
+            .iter_mut()
+            .find(|(iter_champ_id, _, _)| *iter_champ_id == champ_id);
+
+        if let Some(entry) = existing {
+            entry.2 = tier;
+        }
+    }
+
+    /// Replace a champ on this player with another champ (using the given lanes for the new champ), keeping their
+    /// position in the list and their tier.
+    pub fn replace_champ(&mut self, old_champ_id: ChampId, new_champ_id: ChampId, lanes: BitFlags<Lane>) {
+        // Find the entry for the old champ.
+        let existing = self.champs
+            .iter_mut()
+            .find(|(iter_champ_id, _, _)| *iter_champ_id == old_champ_id);
+
+        if let Some(entry) = existing {
+            *entry = (new_champ_id, lanes, entry.2);
+        }
+    }
+
+    /// Remove a champ on this player.
+    pub fn remove_champ(&mut self, champ_id: ChampId) {
+        // Remove the champ if they already were in the list.
+        let remove_index = self.champs
+            .iter()
+            .enumerate()
+            .find(|(_, (iter_champ_id, _, _))| *iter_champ_id == champ_id)
+            .map(|(index, _)| index);
+
+        if let Some(index) = remove_index {
+            // Just use remove here to maintain order.
+            self.champs.remove(index);
+        }
+    }
+}
+
+impl Default for PlayerRecord {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The saved state of the app.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedState {
+    /// The schema version -- this is first so that it's checked before anything else is read.
+    pub version: SchemaVersion,
+    /// The players and their champ pools.
+    pub players: Vec<PlayerRecord>,
+    /// The set of skinsets excluded from consideration.
+    pub skinsets_excluded: SkinsetMask,
+    /// Whether comps are matched by skinset or by universe.
+    #[serde(default)]
+    pub match_mode: MatchMode,
+    /// Whether the wiki or official skinset table is used.
+    #[serde(default)]
+    pub skinset_source: SkinsetSource,
+    /// The lanes added to or removed from champs' default lanes.
+    #[serde(default)]
+    pub lane_overrides: LaneOverrides,
+    /// The skinsets pinned as favourites, which comps score higher for sharing.
+    #[serde(default)]
+    pub favourite_skinsets: SkinsetMask,
+    /// How much each part of a comp's score counts.
+    #[serde(default)]
+    pub score_weights: ScoreWeights,
+    /// The least preferred tier of champ that comps are searched with.
+    #[serde(default = "PreferenceTier::least")]
+    pub min_preference_tier: PreferenceTier,
+    /// The fewest players that have to share a skinset in each comp, or `None` if every player has to.
+    #[serde(default)]
+    pub min_themed_players: Option<usize>,
+    /// The game mode comps are found for.
+    #[serde(default)]
+    pub game_mode: GameMode,
+    /// Whether the players are planned as one team or split into two.
+    #[serde(default)]
+    pub team_setup: TeamSetup,
+}
+
+impl SavedState {
+    /// Load the saved state from local storage. This is `Ok(None)` if nothing has been saved yet, and an error if the
+    /// saved state is corrupt or from a different schema version.
+    pub fn load() -> Result<Option<Self>, StorageError> {
+        match LocalStorage::get(STORAGE_KEY) {
+            Ok(saved_state) => Ok(Some(saved_state)),
+            Err(StorageError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Save this state to local storage, logging (rather than failing on) any error, since the app works fine
+    /// without it.
+    pub fn save(&self) {
+        if let Err(err) = LocalStorage::set(STORAGE_KEY, self) {
+            log::warn!("Failed to save app state: {err}");
+        }
+    }
+
+    /// Copy the saved state in local storage to a backup key, so that state that failed to load isn't lost when the
+    /// app next saves over it.
+    pub fn back_up_unreadable() {
+        Self::back_up("unreadable");
+    }
+
+    /// Copy the saved state in local storage to a backup key, so that the team saved from the last visit isn't lost
+    /// when the app saves a team opened from a shared link over it.
+    pub fn back_up_before_shared_link() {
+        Self::back_up("before-shared-link");
+    }
+
+    /// Copy the saved state in local storage (if there is any) to the backup key with the given suffix.
+    fn back_up(suffix: &str) {
+        let storage = LocalStorage::raw();
+
+        if let Ok(Some(raw_state)) = storage.get_item(STORAGE_KEY) {
+            if storage.set_item(&format!("{STORAGE_KEY}.{suffix}"), &raw_state).is_err() {
+                log::warn!("Failed to back up app state to {STORAGE_KEY}.{suffix}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SkinsetId;
+
+    #[test]
+    fn loads_state_saved_before_any_fields_were_added() {
+        // The shape of the first version 1 state, with champ entries from before preference tiers.
+        let json = r#"{
+            "version": 1,
+            "players": [{ "name": "Alice", "champs": [["Ahri", 4], ["Jinx", 24]] }, { "name": null, "champs": [] }],
+            "skinsets_excluded": ["Dark Star"]
+        }"#;
+
+        let state: SavedState = serde_json::from_str(json).expect("state loads");
+
+        let champ = |name| ChampId::from_name(name).expect("champ exists");
+        assert_eq!(state.players.len(), 2);
+        assert_eq!(state.players[0].name.as_deref(), Some("Alice"));
+        assert_eq!(
+            state.players[0].champs,
+            vec![
+                (champ("Ahri"), Lane::Mid.into(), PreferenceTier::default()),
+                (champ("Jinx"), Lane::Bot | Lane::Support, PreferenceTier::default()),
+            ]
+        );
+        assert!(state.players[1].champs.is_empty());
+        assert_eq!(state.skinsets_excluded, SkinsetId::from_name("Dark Star").into_iter().collect());
+
+        // Every field added since gets its default.
+        assert_eq!(state.match_mode, MatchMode::default());
+        assert_eq!(state.skinset_source, SkinsetSource::default());
+        assert_eq!(state.lane_overrides, LaneOverrides::default());
+        assert_eq!(state.favourite_skinsets, SkinsetMask::EMPTY);
+        assert_eq!(state.score_weights, ScoreWeights::default());
+        assert_eq!(state.min_preference_tier, PreferenceTier::least());
+        assert_eq!(state.min_themed_players, None);
+        assert_eq!(state.game_mode, GameMode::default());
+        assert_eq!(state.team_setup, TeamSetup::default());
+    }
+
+    #[test]
+    fn round_trips_saved_state() {
+        let mut player = PlayerRecord::new();
+        player.name = Some("Alice".to_owned());
+        player.upsert_champ(ChampId::from_name("Ahri").expect("champ exists"), Lane::Mid.into());
+
+        let state = SavedState {
+            version: SchemaVersion,
+            players: vec![player],
+            skinsets_excluded: SkinsetMask::EMPTY,
+            match_mode: MatchMode::Universe,
+            skinset_source: SkinsetSource::Official,
+            lane_overrides: LaneOverrides::default(),
+            favourite_skinsets: SkinsetMask::EMPTY,
+            score_weights: ScoreWeights::default(),
+            min_preference_tier: PreferenceTier::Main,
+            min_themed_players: Some(3),
+            game_mode: GameMode::default(),
+            team_setup: TeamSetup::default(),
+        };
+
+        let json = serde_json::to_string(&state).expect("state saves");
+        assert_eq!(serde_json::from_str::<SavedState>(&json).expect("state loads"), state);
+    }
+
+    #[test]
+    fn rejects_other_schema_versions() {
+        let json = format!(r#"{{ "version": {}, "players": [], "skinsets_excluded": [] }}"#, SCHEMA_VERSION + 1);
+
+        assert!(serde_json::from_str::<SavedState>(&json).is_err());
+    }
+
+    #[test]
+    fn filters_champ_pools_by_tier() {
+        let champ = |name| ChampId::from_name(name).expect("champ exists");
+        let mut player = PlayerRecord::new();
+        player.champs = vec![
+            (champ("Ahri"), Lane::Mid.into(), PreferenceTier::Main),
+            (champ("Lux"), Lane::Support.into(), PreferenceTier::Willing),
+            (champ("Jinx"), Lane::Bot.into(), PreferenceTier::Comfortable),
+        ];
+
+        let names = |min_tier| -> Vec<&str> {
+            player.champ_pool(min_tier).into_iter().map(|(champ_id, _)| champ_id.champ_name()).collect()
+        };
+
+        // Pools keep the order of the player's list.
+        assert_eq!(names(PreferenceTier::Main), vec!["Ahri"]);
+        assert_eq!(names(PreferenceTier::Comfortable), vec!["Ahri", "Jinx"]);
+        assert_eq!(names(PreferenceTier::least()), vec!["Ahri", "Lux", "Jinx"]);
+        assert_eq!(player.champ_pool(PreferenceTier::Main), vec![(champ("Ahri"), Lane::Mid.into())]);
+    }
+
+    #[test]
+    fn keeps_tiers_when_champs_are_edited() {
+        let champ = |name| ChampId::from_name(name).expect("champ exists");
+        let mut player = PlayerRecord::new();
+
+        // New champs start in the default tier.
+        player.upsert_champ(champ("Ahri"), Lane::Mid.into());
+        assert_eq!(player.tier_of(champ("Ahri")), Some(PreferenceTier::default()));
+
+        player.set_tier(champ("Ahri"), PreferenceTier::Main);
+        player.upsert_champ(champ("Ahri"), Lane::Mid | Lane::Top);
+        assert_eq!(player.tier_of(champ("Ahri")), Some(PreferenceTier::Main));
+
+        player.replace_champ(champ("Ahri"), champ("Lux"), Lane::Support.into());
+        assert_eq!(player.tier_of(champ("Ahri")), None);
+        assert_eq!(player.champs, vec![(champ("Lux"), Lane::Support.into(), PreferenceTier::Main)]);
+
+        // Setting the tier of a champ the player doesn't have does nothing.
+        player.set_tier(champ("Jinx"), PreferenceTier::Willing);
+        assert_eq!(player.tier_of(champ("Jinx")), None);
+    }
+
+    #[test]
+    fn orders_tiers_from_most_to_least_preferred() {
+        assert!(PreferenceTier::Main.is_at_least(PreferenceTier::Comfortable));
+        assert!(PreferenceTier::Comfortable.is_at_least(PreferenceTier::Comfortable));
+        assert!(!PreferenceTier::Willing.is_at_least(PreferenceTier::Comfortable));
+        assert!(PreferenceTier::ALL.iter().all(|tier| tier.is_at_least(PreferenceTier::least())));
+
+        let preferences: Vec<f64> = PreferenceTier::ALL.iter().map(|tier| tier.preference()).collect();
+        assert!(preferences.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+}
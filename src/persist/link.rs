@@ -0,0 +1,778 @@
+//! Compact encoding of the saved state into a URL fragment, so that a whole team setup can be shared as one link.
+//!
+//! The state is packed into bytes and then base64url encoded. Links start with the link version, then the number of
+//! champs and skinsets in the data the link was made with (as varints). The rest of the link is a list of sections,
+//! each written as a tag byte, the length of its contents (as a varint), then its contents:
+//!
+//! - Search options: a byte for the [MatchMode] (0 for skinsets, 1 for universes) and a byte for the [SkinsetSource]
+//!   (0 for the wiki, 1 for official).
+//! - Players: the number of players, then for each player their name (as a varint length and UTF-8 bytes), the number
+//!   of champs in their pool, and a varint for each champ with the champ's index in the top bits and their lanes in
+//!   the low 5 bits.
+//! - Excluded skinsets: a bitset of the excluded skinsets, one bit per skinset.
+//! - Lane overrides: the number of lane overrides, then a varint for each override with the champ's index in the top
+//!   bits, then the lanes added, then the lanes removed (5 bits each).
+//! - Scoring: a bitset of the favourite skinsets, then a byte for each score weight (shared skinsets, champ preference,
+//!   lane fit, then favourite skinsets).
+//! - Preference tiers: the minimum preference tier searched, then the preference tier of every player's champs in
+//!   order, packed 2 bits each (4 to a byte, from the low bits up). Tiers are numbered from 0 for
+//!   [PreferenceTier::Main].
+//! - Themed players: the fewest players that have to share a skinset in each comp, or 0 if every player has to.
+//! - Game mode: 0 for Summoner's Rift, 1 for ARAM, or 2 for Arena.
+//! - Team setup: 0 for one team, then 1, 2, or 3 for two teams with any, different, or rival themes.
+//!
+//! A missing section uses the defaults, and sections with unknown tags are skipped, so sections can be added without
+//! breaking older links. Changing the layout of an existing section needs a new tag (or a new link version).
+//!
+//! Champs and skinsets are stored as indices to keep links short. These shift when the data is updated, so a link made
+//! with a different number of champs or skinsets is rejected rather than misread.
+
+use super::{PlayerRecord, PreferenceTier, SavedState, SchemaVersion};
+use crate::constants::{ChampId, Lane, SkinsetId, SkinsetMask, SkinsetSource};
+use crate::lane_overrides::{LaneOverride, LaneOverrides};
+use crate::scoring::ScoreWeights;
+use crate::solver::{GameMode, MatchMode};
+use crate::teams::{TeamSetup, TeamThemes, TEAM_SIZE};
+use derive_more::Display;
+use enumflags2::BitFlags;
+use std::collections::BTreeMap;
+
+/// The version of the link encoding.
+const LINK_VERSION: u8 = 1;
+
+/// The prefix of URL fragments that contain a shared team.
+const FRAGMENT_PREFIX: &str = "team=";
+
+/// The base64url alphabet.
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The number of bits used for each champ's lanes.
+const LANE_BITS: u32 = 5;
+
+/// The tags of the sections in a link.
+mod tag {
+    /// The match mode and skinset source.
+    pub const SEARCH_OPTIONS: u8 = 0;
+    /// The players and their champ pools.
+    pub const PLAYERS: u8 = 1;
+    /// The excluded skinsets.
+    pub const SKINSETS_EXCLUDED: u8 = 2;
+    /// The lane overrides.
+    pub const LANE_OVERRIDES: u8 = 3;
+    /// The favourite skinsets and score weights.
+    pub const SCORING: u8 = 4;
+    /// The minimum preference tier and each champ's tier.
+    pub const PREFERENCE_TIERS: u8 = 5;
+    /// The minimum number of themed players.
+    pub const MIN_THEMED_PLAYERS: u8 = 6;
+    /// The game mode.
+    pub const GAME_MODE: u8 = 7;
+    /// The team setup.
+    pub const TEAM_SETUP: u8 = 8;
+}
+
+/// Reasons a shared link can fail to decode.
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq)]
+pub enum LinkError {
+    /// The fragment isn't a valid encoding of a team.
+    #[display(fmt = "the link is incomplete or malformed")]
+    Malformed,
+
+    /// The link was made with a different version of the encoding.
+    #[display(fmt = "the link was made with an unsupported version of this site")]
+    UnsupportedVersion,
+
+    /// The link was made with different champ or skinset data, so its indices can't be trusted.
+    #[display(fmt = "the link was made with a different list of champions or skinsets")]
+    DataMismatch,
+}
+
+impl SavedState {
+    /// Encode this state into a URL fragment (without the leading `#`).
+    pub fn to_link_fragment(&self) -> String {
+        let mut bytes: Vec<u8> = vec![LINK_VERSION];
+        write_varint(&mut bytes, ChampId::iter_all().len() as u64);
+        write_varint(&mut bytes, SkinsetId::iter_all().len() as u64);
+
+        write_section(&mut bytes, tag::SEARCH_OPTIONS, |section| {
+            section.push((self.match_mode == MatchMode::Universe) as u8);
+            section.push((self.skinset_source == SkinsetSource::Official) as u8);
+        });
+
+        write_section(&mut bytes, tag::PLAYERS, |section| write_players(section, &self.players));
+        write_section(&mut bytes, tag::SKINSETS_EXCLUDED, |section| {
+            write_skinset_bits(section, self.skinsets_excluded);
+        });
+
+        write_section(&mut bytes, tag::LANE_OVERRIDES, |section| write_lane_overrides(section, &self.lane_overrides));
+
+        write_section(&mut bytes, tag::SCORING, |section| {
+            write_scoring(section, self.favourite_skinsets, self.score_weights);
+        });
+
+        write_section(&mut bytes, tag::PREFERENCE_TIERS, |section| {
+            write_tiers(section, self.min_preference_tier, &self.players);
+        });
+
+        write_section(&mut bytes, tag::MIN_THEMED_PLAYERS, |section| {
+            let min_themed_players = self.min_themed_players.unwrap_or(0).min(TEAM_SIZE);
+            section.push(min_themed_players as u8);
+        });
+
+        write_section(&mut bytes, tag::GAME_MODE, |section| section.push(game_mode_index(self.game_mode)));
+        write_section(&mut bytes, tag::TEAM_SETUP, |section| section.push(team_setup_index(self.team_setup)));
+
+        format!("{FRAGMENT_PREFIX}{}", encode_base64(&bytes))
+    }
+
+    /// Decode a state from a URL fragment (with or without the leading `#`). This is `Ok(None)` if the fragment
+    /// doesn't contain a shared team.
+    pub fn from_link_fragment(fragment: &str) -> Result<Option<Self>, LinkError> {
+        let Some(encoded) = fragment.trim_start_matches('#').strip_prefix(FRAGMENT_PREFIX) else {
+            return Ok(None);
+        };
+
+        let bytes = decode_base64(encoded).ok_or(LinkError::Malformed)?;
+        let mut reader = Reader { bytes: &bytes };
+
+        // Check the version and data the link was made with before reading anything else.
+        if reader.byte()? != LINK_VERSION {
+            return Err(LinkError::UnsupportedVersion);
+        }
+
+        let champ_count = reader.varint()?;
+        let skinset_count = reader.varint()?;
+
+        if champ_count != ChampId::iter_all().len() as u64 || skinset_count != SkinsetId::iter_all().len() as u64 {
+            return Err(LinkError::DataMismatch);
+        }
+
+        read_sections(reader).map(Some)
+    }
+
+    /// Load the state shared in the current page's URL fragment. This is `Ok(None)` if the URL doesn't contain a
+    /// shared team.
+    pub fn from_location() -> Result<Option<Self>, LinkError> {
+        let fragment = web_sys::window()
+            .and_then(|window| window.location().hash().ok())
+            .unwrap_or_default();
+
+        Self::from_link_fragment(&fragment)
+    }
+
+    /// Replace the current page's URL fragment with this state, so that the address bar is always a link to the
+    /// current team. This replaces the current history entry rather than adding one for every change.
+    pub fn replace_location(&self) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+
+        if window.location().replace(&format!("#{}", self.to_link_fragment())).is_err() {
+            log::warn!("Failed to update the share link");
+        }
+    }
+}
+
+/// Read the sections of a link, after its header.
+fn read_sections(mut reader: Reader) -> Result<SavedState, LinkError> {
+    // Split the link into its sections first, so that they can be written in any order.
+    let mut sections: BTreeMap<u8, Reader> = BTreeMap::new();
+
+    while !reader.bytes.is_empty() {
+        let tag = reader.byte()?;
+        let length = usize::try_from(reader.varint()?).map_err(|_| LinkError::Malformed)?;
+        let contents = Reader { bytes: reader.take(length)? };
+
+        if sections.insert(tag, contents).is_some() {
+            return Err(LinkError::Malformed);
+        }
+    }
+
+    // Sections with unknown tags are from newer links, and are skipped.
+    let mut section = |tag: u8| sections.remove(&tag);
+
+    let (match_mode, skinset_source) = read_section(section(tag::SEARCH_OPTIONS), Default::default(), |reader| {
+        let match_mode = match reader.byte()? {
+            0 => MatchMode::Skinset,
+            1 => MatchMode::Universe,
+            _ => return Err(LinkError::Malformed),
+        };
+
+        let skinset_source = match reader.byte()? {
+            0 => SkinsetSource::Wiki,
+            1 => SkinsetSource::Official,
+            _ => return Err(LinkError::Malformed),
+        };
+
+        Ok((match_mode, skinset_source))
+    })?;
+
+    let mut players = read_section(section(tag::PLAYERS), Vec::new(), read_players)?;
+    let skinsets_excluded =
+        read_section(section(tag::SKINSETS_EXCLUDED), SkinsetMask::EMPTY, |reader| reader.skinset_bits())?;
+    let lane_overrides = read_section(section(tag::LANE_OVERRIDES), LaneOverrides::default(), read_lane_overrides)?;
+
+    let (favourite_skinsets, score_weights) =
+        read_section(section(tag::SCORING), (SkinsetMask::EMPTY, ScoreWeights::default()), read_scoring)?;
+
+    // The tiers are read after the players, since they're stored for each of the players' champs.
+    let min_preference_tier = read_section(section(tag::PREFERENCE_TIERS), PreferenceTier::least(), |reader| {
+        read_tiers(reader, &mut players)
+    })?;
+
+    let min_themed_players = read_section(section(tag::MIN_THEMED_PLAYERS), None, read_min_themed_players)?;
+    let game_mode = read_section(section(tag::GAME_MODE), GameMode::default(), |reader| {
+        game_mode_from_index(reader.byte()?)
+    })?;
+    let team_setup = read_section(section(tag::TEAM_SETUP), TeamSetup::default(), |reader| {
+        team_setup_from_index(reader.byte()?)
+    })?;
+
+    Ok(SavedState {
+        version: SchemaVersion,
+        players,
+        skinsets_excluded,
+        match_mode,
+        skinset_source,
+        lane_overrides,
+        favourite_skinsets,
+        score_weights,
+        min_preference_tier,
+        min_themed_players,
+        game_mode,
+        team_setup,
+    })
+}
+
+/// Read a section of a link with a function that has to use up all of its contents, or get a default if the link
+/// doesn't have the section.
+fn read_section<'a, T>(
+    section: Option<Reader<'a>>,
+    default: T,
+    read: impl FnOnce(&mut Reader<'a>) -> Result<T, LinkError>,
+) -> Result<T, LinkError> {
+    let Some(mut reader) = section else {
+        return Ok(default);
+    };
+
+    let value = read(&mut reader)?;
+
+    if reader.bytes.is_empty() {
+        Ok(value)
+    } else {
+        Err(LinkError::Malformed)
+    }
+}
+
+/// Write a section of a link.
+fn write_section(bytes: &mut Vec<u8>, tag: u8, write: impl FnOnce(&mut Vec<u8>)) {
+    let mut section = Vec::new();
+    write(&mut section);
+
+    bytes.push(tag);
+    write_varint(bytes, section.len() as u64);
+    bytes.extend(section);
+}
+
+/// Write the players and their champ pools (without their preference tiers).
+fn write_players(bytes: &mut Vec<u8>, players: &[PlayerRecord]) {
+    bytes.push(players.len() as u8);
+
+    for player in players {
+        let name = player.name.as_deref().unwrap_or_default();
+        write_varint(bytes, name.len() as u64);
+        bytes.extend_from_slice(name.as_bytes());
+
+        write_varint(bytes, player.champs.len() as u64);
+
+        for (champ_id, lanes, _) in &player.champs {
+            write_varint(bytes, (champ_id.index() as u64) << LANE_BITS | lanes.bits() as u64);
+        }
+    }
+}
+
+/// Read the players and their champ pools, with every champ in the default tier.
+fn read_players(reader: &mut Reader) -> Result<Vec<PlayerRecord>, LinkError> {
+    let player_count = reader.byte()?;
+    let mut players = Vec::with_capacity(player_count as usize);
+
+    for _ in 0..player_count {
+        let name_length = reader.varint()? as usize;
+        let name = std::str::from_utf8(reader.take(name_length)?).map_err(|_| LinkError::Malformed)?;
+
+        let mut player = PlayerRecord::new();
+        player.name = (!name.is_empty()).then(|| name.to_owned());
+
+        for _ in 0..reader.varint()? {
+            let packed = reader.varint()?;
+            let champ_id = ChampId::from_index((packed >> LANE_BITS) as usize).ok_or(LinkError::Malformed)?;
+            let lanes = unpack_lanes(packed)?;
+
+            player.champs.push((champ_id, lanes, PreferenceTier::default()));
+        }
+
+        players.push(player);
+    }
+
+    Ok(players)
+}
+
+/// Write the lane overrides.
+fn write_lane_overrides(bytes: &mut Vec<u8>, lane_overrides: &LaneOverrides) {
+    write_varint(bytes, lane_overrides.iter().count() as u64);
+
+    for (champ_id, lane_override) in lane_overrides.iter() {
+        let packed_lanes = (lane_override.added.bits() as u64) << LANE_BITS | lane_override.removed.bits() as u64;
+        write_varint(bytes, (champ_id.index() as u64) << (2 * LANE_BITS) | packed_lanes);
+    }
+}
+
+/// Read the lane overrides.
+fn read_lane_overrides(reader: &mut Reader) -> Result<LaneOverrides, LinkError> {
+    let mut lane_overrides = LaneOverrides::empty();
+
+    for _ in 0..reader.varint()? {
+        let packed = reader.varint()?;
+        let champ_id = ChampId::from_index((packed >> (2 * LANE_BITS)) as usize).ok_or(LinkError::Malformed)?;
+        let added = unpack_lanes(packed >> LANE_BITS)?;
+        let removed = unpack_lanes(packed)?;
+
+        lane_overrides.insert(champ_id, LaneOverride { added, removed });
+    }
+
+    Ok(lane_overrides)
+}
+
+/// Write the favourite skinsets and the score weights.
+fn write_scoring(bytes: &mut Vec<u8>, favourite_skinsets: SkinsetMask, weights: ScoreWeights) {
+    write_skinset_bits(bytes, favourite_skinsets);
+    bytes.extend([weights.shared_skinsets, weights.champ_preference, weights.lane_fit, weights.favourite_skinsets]);
+}
+
+/// Read the favourite skinsets and the score weights.
+fn read_scoring(reader: &mut Reader) -> Result<(SkinsetMask, ScoreWeights), LinkError> {
+    let favourite_skinsets = reader.skinset_bits()?;
+    let score_weights = ScoreWeights {
+        shared_skinsets: reader.byte()?,
+        champ_preference: reader.byte()?,
+        lane_fit: reader.byte()?,
+        favourite_skinsets: reader.byte()?,
+    };
+
+    Ok((favourite_skinsets, score_weights))
+}
+
+/// Write the minimum preference tier, then the tier of every player's champs packed 2 bits each.
+fn write_tiers(bytes: &mut Vec<u8>, min_preference_tier: PreferenceTier, players: &[PlayerRecord]) {
+    bytes.push(tier_index(min_preference_tier));
+
+    let tiers: Vec<u8> =
+        players.iter().flat_map(|player| &player.champs).map(|(_, _, tier)| tier_index(*tier)).collect();
+
+    for chunk in tiers.chunks(4) {
+        bytes.push(chunk.iter().enumerate().fold(0, |packed, (index, tier)| packed | tier << (2 * index)));
+    }
+}
+
+/// Read the minimum preference tier, and set the tier of every player's champs.
+fn read_tiers(reader: &mut Reader, players: &mut [PlayerRecord]) -> Result<PreferenceTier, LinkError> {
+    let min_preference_tier = tier_from_index(reader.byte()?)?;
+    let champ_count: usize = players.iter().map(|player| player.champs.len()).sum();
+    let packed_tiers = reader.take(champ_count.div_ceil(4))?;
+
+    let entries = players.iter_mut().flat_map(|player| &mut player.champs);
+
+    for (index, (_, _, tier)) in entries.enumerate() {
+        *tier = tier_from_index(packed_tiers[index / 4] >> (2 * (index % 4)) & 0b11)?;
+    }
+
+    Ok(min_preference_tier)
+}
+
+/// Read the minimum number of themed players, keeping it in the range team files allow.
+fn read_min_themed_players(reader: &mut Reader) -> Result<Option<usize>, LinkError> {
+    Ok(match reader.byte()? {
+        0 => None,
+        min_themed_players => Some((min_themed_players as usize).clamp(1, TEAM_SIZE)),
+    })
+}
+
+/// Get the number a game mode is stored as.
+fn game_mode_index(game_mode: GameMode) -> u8 {
+    match game_mode {
+        GameMode::SummonersRift => 0,
+        GameMode::Aram => 1,
+        GameMode::Arena => 2,
+    }
+}
+
+/// Get the game mode stored as a number.
+fn game_mode_from_index(index: u8) -> Result<GameMode, LinkError> {
+    match index {
+        0 => Ok(GameMode::SummonersRift),
+        1 => Ok(GameMode::Aram),
+        2 => Ok(GameMode::Arena),
+        _ => Err(LinkError::Malformed),
+    }
+}
+
+/// Get the number a team setup is stored as.
+fn team_setup_index(team_setup: TeamSetup) -> u8 {
+    match team_setup {
+        TeamSetup::OneTeam => 0,
+        TeamSetup::TwoTeams(TeamThemes::Any) => 1,
+        TeamSetup::TwoTeams(TeamThemes::Different) => 2,
+        TeamSetup::TwoTeams(TeamThemes::Rival) => 3,
+    }
+}
+
+/// Get the team setup stored as a number.
+fn team_setup_from_index(index: u8) -> Result<TeamSetup, LinkError> {
+    match index {
+        0 => Ok(TeamSetup::OneTeam),
+        1 => Ok(TeamSetup::TwoTeams(TeamThemes::Any)),
+        2 => Ok(TeamSetup::TwoTeams(TeamThemes::Different)),
+        3 => Ok(TeamSetup::TwoTeams(TeamThemes::Rival)),
+        _ => Err(LinkError::Malformed),
+    }
+}
+
+/// Cursor over the bytes of a decoded link.
+struct Reader<'a> {
+    /// The bytes that haven't been read yet.
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Read the next `count` bytes.
+    fn take(&mut self, count: usize) -> Result<&'a [u8], LinkError> {
+        if count > self.bytes.len() {
+            return Err(LinkError::Malformed);
+        }
+
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    /// Read one byte.
+    fn byte(&mut self) -> Result<u8, LinkError> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Read a LEB128 varint.
+    fn varint(&mut self) -> Result<u64, LinkError> {
+        let mut value: u64 = 0;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        // Too many continuation bytes to fit in a u64.
+        Err(LinkError::Malformed)
+    }
+
+    /// Read a set of skinsets stored as a bitset, one bit per skinset.
+    fn skinset_bits(&mut self) -> Result<SkinsetMask, LinkError> {
+        let skinset_bits = self.take(SkinsetId::iter_all().len().div_ceil(8))?;
+
+        Ok(SkinsetId::iter_all()
+            .enumerate()
+            .filter(|(index, _)| skinset_bits[index / 8] & (1 << (index % 8)) != 0)
+            .map(|(_, skinset_id)| skinset_id)
+            .collect())
+    }
+}
+
+/// Unpack the lanes stored in the low bits of a varint.
+fn unpack_lanes(packed: u64) -> Result<BitFlags<Lane>, LinkError> {
+    BitFlags::<Lane>::from_bits((packed & ((1 << LANE_BITS) - 1)) as u8).map_err(|_| LinkError::Malformed)
+}
+
+/// Get the number a preference tier is stored as.
+fn tier_index(tier: PreferenceTier) -> u8 {
+    PreferenceTier::ALL.iter().position(|other| *other == tier).expect("every tier is listed") as u8
+}
+
+/// Get the preference tier stored as a number.
+fn tier_from_index(index: u8) -> Result<PreferenceTier, LinkError> {
+    PreferenceTier::ALL.get(index as usize).copied().ok_or(LinkError::Malformed)
+}
+
+/// Write a set of skinsets as a bitset, one bit per skinset.
+fn write_skinset_bits(bytes: &mut Vec<u8>, skinsets: SkinsetMask) {
+    let mut skinset_bits = vec![0u8; SkinsetId::iter_all().len().div_ceil(8)];
+
+    for (index, skinset_id) in SkinsetId::iter_all().enumerate() {
+        if skinsets.contains(skinset_id) {
+            skinset_bits[index / 8] |= 1 << (index % 8);
+        }
+    }
+
+    bytes.extend(skinset_bits);
+}
+
+/// Write a LEB128 varint -- 7 bits per byte, with the high bit set on every byte but the last.
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+
+    bytes.push(value as u8);
+}
+
+/// Encode bytes as unpadded base64url.
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        // Pack the chunk into the top 24 bits of a word.
+        let word = chunk.iter().enumerate().fold(0u32, |word, (index, byte)| word | (*byte as u32) << (16 - 8 * index));
+
+        // A chunk of n bytes needs n + 1 characters.
+        for index in 0..=chunk.len() {
+            encoded.push(BASE64_ALPHABET[(word >> (18 - 6 * index) & 0x3f) as usize] as char);
+        }
+    }
+
+    encoded
+}
+
+/// Decode unpadded base64url, returning `None` if it isn't valid.
+fn decode_base64(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 3 / 4);
+
+    for chunk in encoded.as_bytes().chunks(4) {
+        // A single leftover character can't encode a whole byte.
+        if chunk.len() == 1 {
+            return None;
+        }
+
+        // Unpack the chunk's characters into the top 24 bits of a word.
+        let mut word: u32 = 0;
+
+        for (index, character) in chunk.iter().enumerate() {
+            let value = BASE64_ALPHABET.iter().position(|c| c == character)? as u32;
+            word |= value << (18 - 6 * index);
+        }
+
+        // A chunk of n characters encodes n - 1 bytes.
+        for index in 0..chunk.len() - 1 {
+            bytes.push((word >> (16 - 8 * index)) as u8);
+        }
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Get a champ's ID by their exact name.
+    fn champ(name: &str) -> ChampId {
+        ChampId::from_name(name).expect("champ exists")
+    }
+
+    /// Make a state with every field set to something other than its default.
+    fn full_state() -> SavedState {
+        let mut first = PlayerRecord::new();
+        first.name = Some("Ash".to_owned());
+        first.champs = vec![
+            (champ("Ahri"), Lane::Mid.into(), PreferenceTier::Main),
+            (champ("Lux"), Lane::Mid | Lane::Support, PreferenceTier::Willing),
+        ];
+
+        let mut second = PlayerRecord::new();
+        second.champs = vec![(champ("Jinx"), Lane::Bot.into(), PreferenceTier::Comfortable)];
+
+        let mut lane_overrides = LaneOverrides::empty();
+        lane_overrides.insert(champ("Lux"), LaneOverride { added: Lane::Support.into(), removed: Lane::Bot.into() });
+
+        let some_skinsets: SkinsetMask = SkinsetId::iter_all().step_by(7).collect();
+
+        SavedState {
+            version: SchemaVersion,
+            players: vec![first, second],
+            skinsets_excluded: some_skinsets,
+            match_mode: MatchMode::Universe,
+            skinset_source: SkinsetSource::Official,
+            lane_overrides,
+            favourite_skinsets: SkinsetId::iter_all().skip(3).step_by(11).collect(),
+            score_weights: ScoreWeights {
+                shared_skinsets: 3,
+                champ_preference: 0,
+                lane_fit: 7,
+                favourite_skinsets: 10,
+            },
+            min_preference_tier: PreferenceTier::Comfortable,
+            min_themed_players: Some(2),
+            game_mode: GameMode::Arena,
+            team_setup: TeamSetup::TwoTeams(TeamThemes::Rival),
+        }
+    }
+
+    /// Start a link's bytes with a version and the current data's counts.
+    fn link_header(version: u8) -> Vec<u8> {
+        let mut bytes = vec![version];
+        write_varint(&mut bytes, ChampId::iter_all().len() as u64);
+        write_varint(&mut bytes, SkinsetId::iter_all().len() as u64);
+        bytes
+    }
+
+    /// Decode a link from its bytes.
+    fn decode(bytes: &[u8]) -> Result<Option<SavedState>, LinkError> {
+        SavedState::from_link_fragment(&format!("#{FRAGMENT_PREFIX}{}", encode_base64(bytes)))
+    }
+
+    #[test]
+    fn round_trips_every_field() {
+        let saved_state = full_state();
+        let decoded = SavedState::from_link_fragment(&saved_state.to_link_fragment()).expect("link decodes");
+
+        assert_eq!(decoded, Some(saved_state));
+    }
+
+    #[test]
+    fn round_trips_base64() {
+        for length in 0..10 {
+            let bytes: Vec<u8> = (0..length).map(|byte: u8| byte.wrapping_mul(97)).collect();
+            assert_eq!(decode_base64(&encode_base64(&bytes)), Some(bytes));
+        }
+    }
+
+    #[test]
+    fn ignores_fragments_without_a_team() {
+        assert_eq!(SavedState::from_link_fragment(""), Ok(None));
+        assert_eq!(SavedState::from_link_fragment("#about"), Ok(None));
+    }
+
+    #[test]
+    fn uses_defaults_for_missing_sections_and_skips_unknown_ones() {
+        let mut bytes = link_header(LINK_VERSION);
+        write_section(&mut bytes, tag::SEARCH_OPTIONS, |section| section.extend([1, 0]));
+        write_section(&mut bytes, tag::PLAYERS, |section| write_players(section, &full_state().players));
+        write_section(&mut bytes, 200, |section| section.extend([1, 2, 3]));
+
+        let decoded = decode(&bytes).expect("link decodes").expect("link has a team");
+
+        assert_eq!(decoded.match_mode, MatchMode::Universe);
+        assert_eq!(decoded.skinset_source, SkinsetSource::Wiki);
+        assert!(decoded.skinsets_excluded.is_empty());
+        assert_eq!(decoded.lane_overrides, LaneOverrides::default());
+        assert_eq!(decoded.score_weights, ScoreWeights::default());
+        assert_eq!(decoded.min_preference_tier, PreferenceTier::least());
+        assert_eq!(decoded.min_themed_players, None);
+        assert_eq!(decoded.game_mode, GameMode::SummonersRift);
+        assert_eq!(decoded.team_setup, TeamSetup::OneTeam);
+
+        // Every champ is in the default tier, since the link has no tiers.
+        let champs: Vec<_> = decoded.players.iter().flat_map(|player| &player.champs).collect();
+        assert!(champs.iter().all(|(_, _, tier)| *tier == PreferenceTier::default()));
+        assert_eq!(champs.len(), 3);
+    }
+
+    #[test]
+    fn rejects_malformed_sections() {
+        // A section repeated.
+        let mut bytes = link_header(LINK_VERSION);
+        write_section(&mut bytes, tag::SEARCH_OPTIONS, |section| section.extend([0, 0]));
+        write_section(&mut bytes, tag::SEARCH_OPTIONS, |section| section.extend([1, 1]));
+        assert_eq!(decode(&bytes), Err(LinkError::Malformed));
+
+        // A section with more in it than its contents use.
+        let mut bytes = link_header(LINK_VERSION);
+        write_section(&mut bytes, tag::SEARCH_OPTIONS, |section| section.extend([0, 0, 0]));
+        assert_eq!(decode(&bytes), Err(LinkError::Malformed));
+
+        // A section cut short.
+        let mut bytes = link_header(LINK_VERSION);
+        write_section(&mut bytes, tag::SKINSETS_EXCLUDED, |section| write_skinset_bits(section, SkinsetMask::ALL));
+        bytes.pop();
+        assert_eq!(decode(&bytes), Err(LinkError::Malformed));
+
+        // A game mode that doesn't exist.
+        let mut bytes = link_header(LINK_VERSION);
+        write_section(&mut bytes, tag::GAME_MODE, |section| section.push(3));
+        assert_eq!(decode(&bytes), Err(LinkError::Malformed));
+
+        // A team setup that doesn't exist.
+        let mut bytes = link_header(LINK_VERSION);
+        write_section(&mut bytes, tag::TEAM_SETUP, |section| section.push(4));
+        assert_eq!(decode(&bytes), Err(LinkError::Malformed));
+
+        // A preference tier that doesn't exist.
+        let mut bytes = link_header(LINK_VERSION);
+        write_section(&mut bytes, tag::PREFERENCE_TIERS, |section| section.push(PreferenceTier::ALL.len() as u8));
+        assert_eq!(decode(&bytes), Err(LinkError::Malformed));
+    }
+
+    #[test]
+    fn clamps_the_minimum_themed_players() {
+        let mut bytes = link_header(LINK_VERSION);
+        write_section(&mut bytes, tag::MIN_THEMED_PLAYERS, |section| section.push(200));
+
+        let decoded = decode(&bytes).expect("link decodes").expect("link has a team");
+        assert_eq!(decoded.min_themed_players, Some(TEAM_SIZE));
+    }
+
+    #[test]
+    fn rejects_other_versions_and_data() {
+        assert_eq!(decode(&link_header(LINK_VERSION + 1)), Err(LinkError::UnsupportedVersion));
+
+        let mut bytes = vec![LINK_VERSION];
+        write_varint(&mut bytes, ChampId::iter_all().len() as u64 + 1);
+        write_varint(&mut bytes, SkinsetId::iter_all().len() as u64);
+        assert_eq!(decode(&bytes), Err(LinkError::DataMismatch));
+    }
+
+    #[test]
+    fn round_trips_players_at_the_edges() {
+        let last_champ = ChampId::iter_all().last().expect("there are champs");
+
+        let mut named = PlayerRecord::new();
+        named.name = Some("Zoë 🌟, \"the best\"".to_owned());
+        named.champs = vec![
+            (last_champ, BitFlags::all(), PreferenceTier::Willing),
+            (champ("Ahri"), BitFlags::empty(), PreferenceTier::Main),
+        ];
+
+        let saved_state = SavedState { players: vec![named, PlayerRecord::new()], ..full_state() };
+        let fragment = format!("#{}", saved_state.to_link_fragment());
+
+        assert_eq!(SavedState::from_link_fragment(&fragment), Ok(Some(saved_state)));
+
+        // A link without any players still has a team in it.
+        let empty = SavedState { players: Vec::new(), ..full_state() };
+        assert_eq!(SavedState::from_link_fragment(&empty.to_link_fragment()), Ok(Some(empty)));
+    }
+
+    #[test]
+    fn round_trips_varints() {
+        for value in [0, 1, 0x7f, 0x80, 0x3fff, 0x4000, u32::MAX as u64, u64::MAX] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, value);
+
+            let mut reader = Reader { bytes: &bytes };
+            assert_eq!(reader.varint(), Ok(value));
+            assert!(reader.bytes.is_empty());
+        }
+
+        // Continuation bytes past the end, or past what fits in a u64, are malformed.
+        assert_eq!(Reader { bytes: &[0x80] }.varint(), Err(LinkError::Malformed));
+        assert_eq!(Reader { bytes: &[0xff; 11] }.varint(), Err(LinkError::Malformed));
+    }
+
+    #[test]
+    fn rejects_invalid_base64() {
+        assert_eq!(SavedState::from_link_fragment("#team=not+base64"), Err(LinkError::Malformed));
+        assert_eq!(SavedState::from_link_fragment("#team=AAAAA"), Err(LinkError::Malformed));
+        assert_eq!(SavedState::from_link_fragment("#team="), Err(LinkError::Malformed));
+    }
+}
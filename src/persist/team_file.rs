@@ -0,0 +1,489 @@
+//! Exporting and importing the app state as a JSON team file, so that teams can share and version their champ pools.
+//!
+//! Exports are just the [SavedState] as pretty-printed JSON. Imports are read leniently -- champs, lanes, and skinsets
+//! that don't match the current data are skipped and reported one by one rather than failing the whole file. Champs
+//! from files made before preference tiers (without a tier) get the default tier. Files can have up to
+//! [TEAM_SIZE] players, or twice that if they split the players into two teams.
+
+use super::{PlayerRecord, PreferenceTier, SavedState, SchemaVersion};
+use crate::constants::{ChampId, Lane, SkinsetId, SkinsetMask, SkinsetSource};
+use crate::lane_overrides::{LaneOverride, LaneOverrides};
+use crate::scoring::ScoreWeights;
+use crate::solver::{GameMode, MatchMode};
+use crate::teams::{TeamSetup, TEAM_SIZE};
+use derive_more::Display;
+use enumflags2::BitFlags;
+use serde::de::{value, IntoDeserializer};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// A problem with one entry of an imported team file. The entry is skipped and the rest of the file is imported.
+#[derive(Clone, Debug, Display, PartialEq, Eq)]
+pub enum ImportProblem {
+    /// A player's champ isn't a known champion.
+    #[display(fmt = "{player}: unknown champion {champ_name:?}")]
+    UnknownChamp {
+        /// The name of the player (or their number if they don't have one).
+        player: String,
+        /// The champ name in the file.
+        champ_name: String,
+    },
+
+    /// A player has the same champ more than once. Only the first entry is imported.
+    #[display(fmt = "{player}: {champ_name} is listed more than once")]
+    DuplicateChamp {
+        /// The name of the player (or their number if they don't have one).
+        player: String,
+        /// The name of the champ.
+        champ_name: &'static str,
+    },
+
+    /// A player's champ has a lane mask with bits that aren't a lane.
+    #[display(fmt = "{player}: {champ_name} has an invalid lane mask ({lanes})")]
+    InvalidLanes {
+        /// The name of the player (or their number if they don't have one).
+        player: String,
+        /// The name of the champ.
+        champ_name: &'static str,
+        /// The lane mask in the file.
+        lanes: u64,
+    },
+
+    /// A player's champ has a preference tier that isn't a known tier. The champ is imported with the default tier.
+    #[display(fmt = "{player}: {champ_name} has an unknown preference tier {tier:?}")]
+    UnknownTier {
+        /// The name of the player (or their number if they don't have one).
+        player: String,
+        /// The name of the champ.
+        champ_name: &'static str,
+        /// The tier in the file.
+        tier: String,
+    },
+
+    /// An excluded skinset isn't a known skinset.
+    #[display(fmt = "unknown skinset {skinset_name:?}")]
+    UnknownSkinset {
+        /// The skinset name in the file.
+        skinset_name: String,
+    },
+
+    /// A favourite skinset isn't a known skinset.
+    #[display(fmt = "unknown favourite skinset {skinset_name:?}")]
+    UnknownFavouriteSkinset {
+        /// The skinset name in the file.
+        skinset_name: String,
+    },
+
+    /// A lane override is for a champ that isn't a known champion.
+    #[display(fmt = "lane overrides: unknown champion {champ_name:?}")]
+    UnknownOverrideChamp {
+        /// The champ name in the file.
+        champ_name: String,
+    },
+
+    /// A lane override has a lane mask with bits that aren't a lane.
+    #[display(fmt = "lane overrides: {champ_name} has an invalid lane mask ({lanes})")]
+    InvalidOverrideLanes {
+        /// The name of the champ.
+        champ_name: &'static str,
+        /// The lane mask in the file.
+        lanes: u64,
+    },
+
+    /// The file has more players than its team setup allows.
+    #[display(fmt = "only the first {max_players} of {player_count} players were imported")]
+    TooManyPlayers {
+        /// The number of players in the file.
+        player_count: usize,
+        /// The most players the file's team setup allows.
+        max_players: usize,
+    },
+}
+
+/// The shape of a team file, with champs, lanes, and skinsets left unvalidated.
+#[derive(Deserialize)]
+struct RawTeamFile {
+    /// The schema version -- files from other versions fail to import.
+    version: SchemaVersion,
+    /// The players and their champ pools.
+    players: Vec<RawPlayer>,
+    /// The names of the skinsets excluded from consideration.
+    skinsets_excluded: Vec<String>,
+    /// Whether comps are matched by skinset or by universe.
+    #[serde(default)]
+    match_mode: MatchMode,
+    /// Whether the wiki or official skinset table is used.
+    #[serde(default)]
+    skinset_source: SkinsetSource,
+    /// The lane overrides by champ name -- files without any use the default overrides.
+    #[serde(default)]
+    lane_overrides: Option<BTreeMap<String, RawLaneOverride>>,
+    /// The names of the favourite skinsets.
+    #[serde(default)]
+    favourite_skinsets: Vec<String>,
+    /// How much each part of a comp's score counts.
+    #[serde(default)]
+    score_weights: ScoreWeights,
+    /// The least preferred tier of champ that comps are searched with.
+    #[serde(default = "PreferenceTier::least")]
+    min_preference_tier: PreferenceTier,
+    /// The fewest players that have to share a skinset in each comp, or `None` if every player has to.
+    #[serde(default)]
+    min_themed_players: Option<usize>,
+    /// The game mode comps are found for.
+    #[serde(default)]
+    game_mode: GameMode,
+    /// Whether the players are planned as one team or split into two.
+    #[serde(default)]
+    team_setup: TeamSetup,
+}
+
+/// The shape of a player in a team file, with champs and lanes left unvalidated.
+#[derive(Deserialize)]
+struct RawPlayer {
+    /// Player name.
+    name: Option<String>,
+    /// The name, lane mask, and (optionally) preference tier of each champ.
+    champs: Vec<RawChampEntry>,
+}
+
+/// The shape of a champ in a team file, with everything left unvalidated.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawChampEntry {
+    /// A champ with a preference tier.
+    WithTier(String, u64, String),
+    /// A champ from a file made before preference tiers were added.
+    WithoutTier(String, u64),
+}
+
+/// The shape of a lane override in a team file, with lanes left unvalidated.
+#[derive(Deserialize)]
+struct RawLaneOverride {
+    /// The lane mask of the lanes added.
+    added: u64,
+    /// The lane mask of the lanes removed.
+    removed: u64,
+}
+
+/// Convert a lane mask from a team file to lanes, if all of its bits are lanes.
+fn lanes_from_mask(lanes: u64) -> Option<BitFlags<Lane>> {
+    u8::try_from(lanes).ok().and_then(|bits| BitFlags::<Lane>::from_bits(bits).ok())
+}
+
+/// Look up a preference tier by the name it's saved as.
+fn tier_from_name(name: &str) -> Option<PreferenceTier> {
+    PreferenceTier::deserialize(IntoDeserializer::<value::Error>::into_deserializer(name)).ok()
+}
+
+impl SavedState {
+    /// Export this state as the JSON contents of a team file.
+    pub fn to_team_file(&self) -> String {
+        serde_json::to_string_pretty(self).expect("saved state serializes to JSON")
+    }
+
+    /// Export this state as a `data:` URL of a team file, for use as the link of a download button.
+    pub fn to_team_file_url(&self) -> String {
+        // Percent-encode everything other than unreserved characters.
+        let encoded: String = self
+            .to_team_file()
+            .bytes()
+            .map(|byte| match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+                _ => format!("%{byte:02X}"),
+            })
+            .collect();
+
+        format!("data:application/json;charset=utf-8,{encoded}")
+    }
+
+    /// Import a state from the JSON contents of a team file, along with a problem for each entry that was skipped.
+    /// This only fails if the file isn't a team file at all (or is from a different schema version).
+    pub fn from_team_file(json: &str) -> Result<(Self, Vec<ImportProblem>), serde_json::Error> {
+        let raw: RawTeamFile = serde_json::from_str(json)?;
+        let mut problems = Vec::new();
+
+        // Only import as many players as the UI allows.
+        let max_players = raw.team_setup.max_players();
+
+        if raw.players.len() > max_players {
+            problems.push(ImportProblem::TooManyPlayers { player_count: raw.players.len(), max_players });
+        }
+
+        let players = raw
+            .players
+            .into_iter()
+            .take(max_players)
+            .enumerate()
+            .map(|(index, raw_player)| {
+                // Refer to players by name in problems, or their number if they don't have one.
+                let player_label = raw_player.name.clone().unwrap_or_else(|| format!("Player {}", index + 1));
+
+                let mut player = PlayerRecord::new();
+                player.name = raw_player.name.filter(|name| !name.is_empty());
+
+                for raw_entry in raw_player.champs {
+                    let (champ_name, lanes, raw_tier) = match raw_entry {
+                        RawChampEntry::WithTier(champ_name, lanes, tier) => (champ_name, lanes, Some(tier)),
+                        RawChampEntry::WithoutTier(champ_name, lanes) => (champ_name, lanes, None),
+                    };
+
+                    let Some(champ_id) = ChampId::from_name(&champ_name) else {
+                        problems.push(ImportProblem::UnknownChamp { player: player_label.clone(), champ_name });
+                        continue;
+                    };
+
+                    // Champs can be listed under different names (e.g. "kogmaw" and "Kog'Maw"), so check by ID.
+                    if player.champs.iter().any(|(other, _, _)| *other == champ_id) {
+                        problems.push(ImportProblem::DuplicateChamp {
+                            player: player_label.clone(),
+                            champ_name: champ_id.champ_name(),
+                        });
+                        continue;
+                    }
+
+                    let Some(lanes) = lanes_from_mask(lanes) else {
+                        problems.push(ImportProblem::InvalidLanes {
+                            player: player_label.clone(),
+                            champ_name: champ_id.champ_name(),
+                            lanes,
+                        });
+                        continue;
+                    };
+
+                    let tier = match raw_tier {
+                        None => PreferenceTier::default(),
+                        Some(tier) => tier_from_name(&tier).unwrap_or_else(|| {
+                            problems.push(ImportProblem::UnknownTier {
+                                player: player_label.clone(),
+                                champ_name: champ_id.champ_name(),
+                                tier,
+                            });
+
+                            PreferenceTier::default()
+                        }),
+                    };
+
+                    player.champs.push((champ_id, lanes, tier));
+                }
+
+                player
+            })
+            .collect();
+
+        let skinsets_excluded: SkinsetMask = raw
+            .skinsets_excluded
+            .into_iter()
+            .filter_map(|skinset_name| {
+                let skinset_id = SkinsetId::from_name(&skinset_name);
+
+                if skinset_id.is_none() {
+                    problems.push(ImportProblem::UnknownSkinset { skinset_name });
+                }
+
+                skinset_id
+            })
+            .collect();
+
+        let favourite_skinsets: SkinsetMask = raw
+            .favourite_skinsets
+            .into_iter()
+            .filter_map(|skinset_name| {
+                let skinset_id = SkinsetId::from_name(&skinset_name);
+
+                if skinset_id.is_none() {
+                    problems.push(ImportProblem::UnknownFavouriteSkinset { skinset_name });
+                }
+
+                skinset_id
+            })
+            .collect();
+
+        let lane_overrides = match raw.lane_overrides {
+            None => LaneOverrides::default(),
+            Some(raw_overrides) => {
+                let mut lane_overrides = LaneOverrides::empty();
+
+                for (champ_name, raw_override) in raw_overrides {
+                    let Some(champ_id) = ChampId::from_name(&champ_name) else {
+                        problems.push(ImportProblem::UnknownOverrideChamp { champ_name });
+                        continue;
+                    };
+
+                    let Some(added) = lanes_from_mask(raw_override.added) else {
+                        let champ_name = champ_id.champ_name();
+                        problems.push(ImportProblem::InvalidOverrideLanes { champ_name, lanes: raw_override.added });
+                        continue;
+                    };
+
+                    let Some(removed) = lanes_from_mask(raw_override.removed) else {
+                        let champ_name = champ_id.champ_name();
+                        problems.push(ImportProblem::InvalidOverrideLanes { champ_name, lanes: raw_override.removed });
+                        continue;
+                    };
+
+                    lane_overrides.insert(champ_id, LaneOverride { added, removed });
+                }
+
+                lane_overrides
+            }
+        };
+
+        let saved_state = SavedState {
+            version: raw.version,
+            players,
+            skinsets_excluded,
+            match_mode: raw.match_mode,
+            skinset_source: raw.skinset_source,
+            lane_overrides,
+            favourite_skinsets,
+            score_weights: raw.score_weights,
+            min_preference_tier: raw.min_preference_tier,
+            min_themed_players: raw.min_themed_players.map(|min_themed_players| min_themed_players.clamp(1, TEAM_SIZE)),
+            game_mode: raw.game_mode,
+            team_setup: raw.team_setup,
+        };
+
+        Ok((saved_state, problems))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persist::SCHEMA_VERSION;
+    use serde_json::{json, Value};
+
+    /// Make a team file with the given players' champ entries, and any other fields set.
+    fn team_file(players: Value, extra: Value) -> String {
+        let mut file = json!({ "version": SCHEMA_VERSION, "players": players, "skinsets_excluded": [] });
+
+        if let (Some(file), Some(extra)) = (file.as_object_mut(), extra.as_object()) {
+            file.extend(extra.clone());
+        }
+
+        file.to_string()
+    }
+
+    /// Import a team file that's expected to be valid.
+    fn import(json: &str) -> (SavedState, Vec<ImportProblem>) {
+        SavedState::from_team_file(json).expect("team file imports")
+    }
+
+    /// Get a champ's ID by their exact name.
+    fn champ(name: &str) -> ChampId {
+        ChampId::from_name(name).expect("champ exists")
+    }
+
+    #[test]
+    fn round_trips_exported_state() {
+        let (saved_state, _) = import(&team_file(json!([{ "name": "Ash", "champs": [["Ahri", 4, "Main"]] }]), json!({})));
+        let (reimported, problems) = import(&saved_state.to_team_file());
+
+        assert!(problems.is_empty());
+        assert_eq!(reimported, saved_state);
+    }
+
+    #[test]
+    fn skips_unknown_champs() {
+        let players = json!([{ "name": null, "champs": [["Ahri", 4], ["Not A Champ", 4]] }]);
+        let (saved_state, problems) = import(&team_file(players, json!({})));
+
+        assert_eq!(saved_state.players[0].champs, vec![(champ("Ahri"), Lane::Mid.into(), PreferenceTier::default())]);
+        assert_eq!(
+            problems,
+            vec![ImportProblem::UnknownChamp { player: "Player 1".to_owned(), champ_name: "Not A Champ".to_owned() }]
+        );
+    }
+
+    #[test]
+    fn skips_champs_with_invalid_lanes() {
+        let players = json!([{ "name": "Ash", "champs": [["Ahri", 32], ["Lux", 300]] }]);
+        let (saved_state, problems) = import(&team_file(players, json!({})));
+
+        assert!(saved_state.players[0].champs.is_empty());
+        assert_eq!(
+            problems,
+            vec![
+                ImportProblem::InvalidLanes { player: "Ash".to_owned(), champ_name: "Ahri", lanes: 32 },
+                ImportProblem::InvalidLanes { player: "Ash".to_owned(), champ_name: "Lux", lanes: 300 },
+            ]
+        );
+    }
+
+    #[test]
+    fn uses_the_default_tier_for_unknown_tiers() {
+        let players = json!([{ "name": null, "champs": [["Ahri", 4, "Favourite"], ["Lux", 4, "Willing"]] }]);
+        let (saved_state, problems) = import(&team_file(players, json!({})));
+
+        assert_eq!(
+            saved_state.players[0].champs,
+            vec![
+                (champ("Ahri"), Lane::Mid.into(), PreferenceTier::default()),
+                (champ("Lux"), Lane::Mid.into(), PreferenceTier::Willing),
+            ]
+        );
+        assert_eq!(
+            problems,
+            vec![ImportProblem::UnknownTier {
+                player: "Player 1".to_owned(),
+                champ_name: "Ahri",
+                tier: "Favourite".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reads_champs_from_before_preference_tiers() {
+        let players = json!([{ "name": null, "champs": [["Ahri", 4], ["Lux", 12, "Main"]] }]);
+        let (saved_state, problems) = import(&team_file(players, json!({})));
+
+        assert!(problems.is_empty());
+        assert_eq!(
+            saved_state.players[0].champs,
+            vec![
+                (champ("Ahri"), Lane::Mid.into(), PreferenceTier::default()),
+                (champ("Lux"), Lane::Mid | Lane::Bot, PreferenceTier::Main),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_duplicate_champs() {
+        let players = json!([{ "name": null, "champs": [["Kog'Maw", 8, "Main"], ["kogmaw", 4], ["Lux", 4]] }]);
+        let (saved_state, problems) = import(&team_file(players, json!({})));
+
+        assert_eq!(
+            saved_state.players[0].champs,
+            vec![
+                (champ("Kog'Maw"), Lane::Bot.into(), PreferenceTier::Main),
+                (champ("Lux"), Lane::Mid.into(), PreferenceTier::default()),
+            ]
+        );
+        assert_eq!(
+            problems,
+            vec![ImportProblem::DuplicateChamp { player: "Player 1".to_owned(), champ_name: "Kog'Maw" }]
+        );
+    }
+
+    #[test]
+    fn limits_players_to_the_team_setup() {
+        let players: Vec<Value> = (0..12).map(|_| json!({ "name": null, "champs": [] })).collect();
+
+        let (saved_state, problems) = import(&team_file(json!(players), json!({})));
+        assert_eq!(saved_state.players.len(), TEAM_SIZE);
+        assert_eq!(problems, vec![ImportProblem::TooManyPlayers { player_count: 12, max_players: TEAM_SIZE }]);
+
+        let (saved_state, problems) = import(&team_file(json!(players), json!({ "team_setup": { "TwoTeams": "Any" } })));
+        assert_eq!(saved_state.players.len(), 2 * TEAM_SIZE);
+        assert_eq!(problems, vec![ImportProblem::TooManyPlayers { player_count: 12, max_players: 2 * TEAM_SIZE }]);
+    }
+
+    #[test]
+    fn rejects_other_schema_versions() {
+        let mut file: Value = serde_json::from_str(&team_file(json!([]), json!({}))).expect("team file is JSON");
+        file["version"] = json!(SCHEMA_VERSION + 1);
+
+        assert!(SavedState::from_team_file(&file.to_string()).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+//! Named champ pools and skinset blacklists that can be applied to a player or to the excluded skinsets.
+//!
+//! A few presets are built in, and users can save their own. Saved presets are kept in local storage separately from
+//! the rest of the app state, so that opening a shared link or importing a team file doesn't replace them.
+
+use crate::constants::{ChampId, SkinsetId, SkinsetMask};
+use crate::lane_overrides::LaneOverrides;
+use crate::persist::{deserialize_champ_entries, ChampEntry, PreferenceTier, SchemaVersion};
+use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+/// The local storage key saved presets are stored under.
+const STORAGE_KEY: &str = "league-skinset-finder.presets";
+
+/// A built-in champ pool, using each champ's lanes after any lane overrides and the default preference tier.
+struct BuiltInChampPool {
+    /// The name of the preset.
+    name: &'static str,
+    /// The champs in the pool.
+    champs: &'static [ChampId],
+}
+
+/// A built-in skinset blacklist.
+struct BuiltInBlacklist {
+    /// The name of the preset.
+    name: &'static str,
+    /// The skinsets in the blacklist.
+    skinsets: &'static [SkinsetId],
+}
+
+/// Champ pools that ship with the app.
+const BUILT_IN_CHAMP_POOLS: &[BuiltInChampPool] = &[
+    BuiltInChampPool {
+        name: "Toni",
+        champs: &[
+            ChampId::id_of_champ("Vel'Koz"),
+            ChampId::id_of_champ("Evelynn"),
+            ChampId::id_of_champ("Cho'Gath"),
+            ChampId::id_of_champ("Briar"),
+            ChampId::id_of_champ("Morgana"),
+            ChampId::id_of_champ("Kindred"),
+        ],
+    },
+    BuiltInChampPool {
+        name: "Venus",
+        champs: &[
+            ChampId::id_of_champ("Mordekaiser"),
+            ChampId::id_of_champ("Blitzcrank"),
+            ChampId::id_of_champ("Lux"),
+            ChampId::id_of_champ("Pantheon"),
+            ChampId::id_of_champ("Illaoi"),
+            ChampId::id_of_champ("Gangplank"),
+            ChampId::id_of_champ("Rakan"),
+            ChampId::id_of_champ("Nasus"),
+            ChampId::id_of_champ("Garen"),
+            ChampId::id_of_champ("Karthus"),
+            ChampId::id_of_champ("Hecarim"),
+        ],
+    },
+    BuiltInChampPool {
+        name: "Emma",
+        champs: &[
+            ChampId::id_of_champ("Diana"),
+            ChampId::id_of_champ("Pyke"),
+            ChampId::id_of_champ("Akali"),
+            ChampId::id_of_champ("Fizz"),
+            ChampId::id_of_champ("Ahri"),
+            ChampId::id_of_champ("Jinx"),
+            ChampId::id_of_champ("Kalista"),
+            ChampId::id_of_champ("LeBlanc"),
+            ChampId::id_of_champ("Lux"),
+            ChampId::id_of_champ("Gwen"),
+            ChampId::id_of_champ("Ezreal"),
+            ChampId::id_of_champ("Soraka"),
+            ChampId::id_of_champ("Renata Glasc"),
+            ChampId::id_of_champ("Yuumi"),
+            ChampId::id_of_champ("Seraphine"),
+            ChampId::id_of_champ("Kindred"),
+            ChampId::id_of_champ("Irelia"),
+            ChampId::id_of_champ("Azir"),
+            ChampId::id_of_champ("Kai'Sa"),
+            ChampId::id_of_champ("Karma"),
+            ChampId::id_of_champ("Kennen"),
+            ChampId::id_of_champ("Mordekaiser"),
+            ChampId::id_of_champ("Nami"),
+            ChampId::id_of_champ("Quinn"),
+            ChampId::id_of_champ("Senna"),
+            ChampId::id_of_champ("Sivir"),
+            ChampId::id_of_champ("Shyvana"),
+            ChampId::id_of_champ("Taliyah"),
+            ChampId::id_of_champ("Varus"),
+            ChampId::id_of_champ("Viego"),
+            ChampId::id_of_champ("Xayah"),
+            ChampId::id_of_champ("Vayne"),
+            ChampId::id_of_champ("Aphelios"),
+            ChampId::id_of_champ("Trundle"),
+            ChampId::id_of_champ("Aatrox"),
+            ChampId::id_of_champ("Rakan"),
+        ],
+    },
+    BuiltInChampPool {
+        name: "Maddie",
+        champs: &[
+            ChampId::id_of_champ("Caitlyn"),
+            ChampId::id_of_champ("Jinx"),
+            ChampId::id_of_champ("Ahri"),
+            ChampId::id_of_champ("Ashe"),
+        ],
+    },
+];
+
+/// Skinset blacklists that ship with the app.
+const BUILT_IN_BLACKLISTS: &[BuiltInBlacklist] = &[
+    BuiltInBlacklist {
+        name: "Not visually cohesive",
+        skinsets: SkinsetId::DEFAULT_EXCLUDED_SKINSETS,
+    },
+    BuiltInBlacklist {
+        name: "Aesthetically incoherent sets",
+        skinsets: &[
+            SkinsetId::id_of_skinset("Legacy"),
+            SkinsetId::id_of_skinset("Battlecast"),
+            SkinsetId::id_of_skinset("Infernal"),
+        ],
+    },
+];
+
+/// A named list of champs with the lanes they're played in and their preference tiers, which can replace a player's
+/// champs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChampPoolPreset {
+    /// The name of the preset.
+    pub name: String,
+    /// The champs with their lanes and tiers, in order.
+    #[serde(deserialize_with = "deserialize_champ_entries")]
+    pub champs: Vec<ChampEntry>,
+}
+
+/// A named set of skinsets, which can be added to the excluded skinsets.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlacklistPreset {
+    /// The name of the preset.
+    pub name: String,
+    /// The skinsets to exclude.
+    pub skinsets: SkinsetMask,
+}
+
+/// The presets a user has saved.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Presets {
+    /// The schema version of the saved presets.
+    pub version: SchemaVersion,
+    /// Saved champ pools.
+    pub champ_pools: Vec<ChampPoolPreset>,
+    /// Saved skinset blacklists.
+    pub blacklists: Vec<BlacklistPreset>,
+}
+
+impl Presets {
+    /// Get the built-in champ pools, with each champ's lanes resolved through the given lane overrides.
+    pub fn built_in_champ_pools(lane_overrides: &LaneOverrides) -> impl Iterator<Item = ChampPoolPreset> + '_ {
+        BUILT_IN_CHAMP_POOLS.iter().map(|pool| ChampPoolPreset {
+            name: pool.name.to_owned(),
+            champs: pool
+                .champs
+                .iter()
+                .map(|champ_id| (*champ_id, lane_overrides.lanes_for(*champ_id), PreferenceTier::default()))
+                .collect(),
+        })
+    }
+
+    /// Get the built-in skinset blacklists.
+    pub fn built_in_blacklists() -> impl Iterator<Item = BlacklistPreset> {
+        BUILT_IN_BLACKLISTS.iter().map(|blacklist| BlacklistPreset {
+            name: blacklist.name.to_owned(),
+            skinsets: SkinsetMask::from_skinsets(blacklist.skinsets),
+        })
+    }
+
+    /// Save a champ pool, replacing any saved pool with the same name.
+    pub fn save_champ_pool(&mut self, preset: ChampPoolPreset) {
+        match self.champ_pools.iter_mut().find(|saved| saved.name == preset.name) {
+            Some(saved) => *saved = preset,
+            None => self.champ_pools.push(preset),
+        }
+    }
+
+    /// Save a skinset blacklist, replacing any saved blacklist with the same name.
+    pub fn save_blacklist(&mut self, preset: BlacklistPreset) {
+        match self.blacklists.iter_mut().find(|saved| saved.name == preset.name) {
+            Some(saved) => *saved = preset,
+            None => self.blacklists.push(preset),
+        }
+    }
+
+    /// Delete the saved champ pool with a given name.
+    pub fn delete_champ_pool(&mut self, name: &str) {
+        self.champ_pools.retain(|saved| saved.name != name);
+    }
+
+    /// Delete the saved skinset blacklist with a given name.
+    pub fn delete_blacklist(&mut self, name: &str) {
+        self.blacklists.retain(|saved| saved.name != name);
+    }
+
+    /// Load the saved presets from local storage, or no presets if none have been saved.
+    pub fn load() -> Result<Self, StorageError> {
+        match LocalStorage::get(STORAGE_KEY) {
+            Ok(presets) => Ok(presets),
+            Err(StorageError::KeyNotFound(_)) => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Save these presets to local storage, logging (rather than failing on) any error.
+    pub fn save(&self) {
+        if let Err(err) = LocalStorage::set(STORAGE_KEY, self) {
+            log::warn!("Failed to save presets: {err}");
+        }
+    }
+
+    /// Copy the saved presets in local storage to a backup key, so that presets that failed to load aren't lost when
+    /// the app next saves over them.
+    pub fn back_up_unreadable() {
+        let storage = LocalStorage::raw();
+
+        if let Ok(Some(raw_presets)) = storage.get_item(STORAGE_KEY) {
+            if storage.set_item(&format!("{STORAGE_KEY}.unreadable"), &raw_presets).is_err() {
+                log::warn!("Failed to back up unreadable presets");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::Lane;
+
+    /// Get a champ's ID by their exact name.
+    fn champ(name: &str) -> ChampId {
+        ChampId::from_name(name).expect("champ exists")
+    }
+
+    /// Make a champ pool preset with each champ in their default lanes and the default tier, ignoring lane overrides.
+    fn champ_pool(name: &str, champs: &[&str]) -> ChampPoolPreset {
+        let entry = |champ_id: ChampId| (champ_id, champ_id.default_lanes(), PreferenceTier::default());
+        let champs = champs.iter().map(|champ_name| entry(champ(champ_name))).collect();
+        ChampPoolPreset { name: name.to_owned(), champs }
+    }
+
+    /// Make a skinset blacklist preset.
+    fn blacklist(name: &str, skinsets: &[&str]) -> BlacklistPreset {
+        BlacklistPreset {
+            name: name.to_owned(),
+            skinsets: skinsets.iter().map(|skinset| SkinsetId::from_name(skinset).expect("skinset exists")).collect(),
+        }
+    }
+
+    #[test]
+    fn replaces_saved_presets_with_the_same_name() {
+        let mut presets = Presets::default();
+        presets.save_champ_pool(champ_pool("Mids", &["Ahri"]));
+        presets.save_champ_pool(champ_pool("Bots", &["Jinx"]));
+        presets.save_champ_pool(champ_pool("Mids", &["Lux", "Ahri"]));
+
+        presets.save_blacklist(blacklist("Old", &["Legacy"]));
+        presets.save_blacklist(blacklist("Old", &["Legacy", "Infernal"]));
+
+        // A replaced preset keeps its place in the list.
+        assert_eq!(presets.champ_pools, vec![champ_pool("Mids", &["Lux", "Ahri"]), champ_pool("Bots", &["Jinx"])]);
+        assert_eq!(presets.blacklists, vec![blacklist("Old", &["Legacy", "Infernal"])]);
+    }
+
+    #[test]
+    fn deletes_saved_presets_by_name() {
+        let mut presets = Presets {
+            champ_pools: vec![champ_pool("Mids", &["Ahri"]), champ_pool("Bots", &["Jinx"])],
+            blacklists: vec![blacklist("Old", &["Legacy"]), blacklist("Dark", &["Dark Star"])],
+            ..Presets::default()
+        };
+
+        presets.delete_champ_pool("Mids");
+        presets.delete_blacklist("Dark");
+
+        // Deleting a name that isn't saved does nothing.
+        presets.delete_champ_pool("Tops");
+        presets.delete_blacklist("Bots");
+
+        assert_eq!(presets.champ_pools, vec![champ_pool("Bots", &["Jinx"])]);
+        assert_eq!(presets.blacklists, vec![blacklist("Old", &["Legacy"])]);
+    }
+
+    #[test]
+    fn builds_the_built_in_presets() {
+        for pool in Presets::built_in_champ_pools(&LaneOverrides::empty()) {
+            assert!(pool.champs.iter().all(|(champ_id, lanes, _)| *lanes == champ_id.default_lanes()));
+        }
+
+        // Lane overrides apply to the champs in built-in pools.
+        let caitlyn = champ("Caitlyn");
+        let lane_overrides = LaneOverrides::default();
+        let pools: Vec<ChampPoolPreset> = Presets::built_in_champ_pools(&lane_overrides).collect();
+        let (_, lanes, _) = pools
+            .iter()
+            .flat_map(|pool| &pool.champs)
+            .find(|(champ_id, _, _)| *champ_id == caitlyn)
+            .expect("a built-in pool has Caitlyn");
+
+        assert_eq!(*lanes, lane_overrides.lanes_for(caitlyn));
+        assert!(lanes.contains(Lane::Top));
+
+        let cohesive = Presets::built_in_blacklists().next().expect("there are built-in blacklists");
+        assert_eq!(cohesive.skinsets, SkinsetMask::from_skinsets(SkinsetId::DEFAULT_EXCLUDED_SKINSETS));
+    }
+
+    #[test]
+    fn reads_champ_pools_saved_before_preference_tiers() {
+        let (ahri, lux) = (champ("Ahri"), champ("Lux"));
+        let json = serde_json::json!({
+            "name": "Mids",
+            "champs": [[ahri, ahri.default_lanes()], [lux, Lane::Support | Lane::Mid, PreferenceTier::Willing]],
+        });
+
+        let preset: ChampPoolPreset = serde_json::from_value(json).expect("preset deserializes");
+
+        // Entries without a tier get the default one.
+        assert_eq!(
+            preset.champs,
+            vec![
+                (ahri, ahri.default_lanes(), PreferenceTier::default()),
+                (lux, Lane::Support | Lane::Mid, PreferenceTier::Willing),
+            ]
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! Scoring of the comps found by the solver, so that they can be ranked by how good a pick they are rather than the
+//! order they were found in.
+//!
+//! A comp's score is a weighted sum of:
+//!
+//! - The number of non-excluded skinsets (or universes, in universe mode) the comp shares.
+//! - How preferred each player's champ is, by their [preference tier](PreferenceTier::preference).
+//! - The number of champs played in one of their [default lanes](crate::constants::ChampId::default_lanes), in game
+//!   modes with lanes.
+//! - The number of the user's favourite skinsets the comp shares.
+
+use crate::constants::{SkinsetMask, SkinsetSource};
+use crate::persist::{PlayerRecord, PreferenceTier};
+use crate::solver::{Comp, GameMode, MatchMode};
+use serde::{Deserialize, Serialize};
+
+/// The highest weight the web app lets each part of the score be set to.
+pub const MAX_WEIGHT: u8 = 10;
+
+/// How much each part of a comp's score counts towards its total.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoreWeights {
+    /// The weight of each shared skinset (or universe).
+    pub shared_skinsets: u8,
+    /// The weight of each player's champ preference.
+    pub champ_preference: u8,
+    /// The weight of each champ played in one of their default lanes.
+    pub lane_fit: u8,
+    /// The weight of each shared favourite skinset.
+    pub favourite_skinsets: u8,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            shared_skinsets: 2,
+            champ_preference: 1,
+            lane_fit: 1,
+            favourite_skinsets: 5,
+        }
+    }
+}
+
+/// The score of a comp, with the unweighted value of each part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct CompScore {
+    /// The number of skinsets (or universes) the comp shares.
+    pub shared_skinsets: usize,
+    /// The sum of each player's preference for their champ, from 0 to 1 each.
+    pub champ_preference: f64,
+    /// The number of champs played in one of their default lanes.
+    pub lane_fit: usize,
+    /// The number of favourite skinsets the comp shares.
+    pub favourite_skinsets: usize,
+    /// The weighted sum of the parts.
+    pub total: f64,
+}
+
+/// Scores comps found for a team.
+#[derive(Clone, Copy, Debug)]
+pub struct Scorer<'a> {
+    /// The players the comps were found for, in the same order as the picks in each comp.
+    pub players: &'a [PlayerRecord],
+    /// The skinsets the user has pinned as favourites.
+    pub favourite_skinsets: SkinsetMask,
+    /// Whether the comps were matched by skinset or by universe.
+    pub match_mode: MatchMode,
+    /// The skinset table the comps were found with.
+    pub skinset_source: SkinsetSource,
+    /// The game mode the comps were found for.
+    pub game_mode: GameMode,
+    /// How much each part of the score counts.
+    pub weights: ScoreWeights,
+}
+
+impl Scorer<'_> {
+    /// Score a comp, given the skinsets it shares (as returned by the solver).
+    pub fn score(&self, comp: &Comp, shared: SkinsetMask) -> CompScore {
+        let shared_skinsets = match self.match_mode {
+            MatchMode::Skinset => shared.count(),
+            MatchMode::Universe => shared.universes(self.skinset_source).len(),
+        };
+
+        let champ_preference = comp
+            .iter()
+            .zip(self.players)
+            .filter_map(|((champ_id, _), player)| player.tier_of(*champ_id))
+            .map(PreferenceTier::preference)
+            .sum();
+
+        // Comps found without lanes don't have real lanes to fit.
+        let lane_fit = if self.game_mode.has_lanes() {
+            comp.iter().filter(|(champ_id, lane)| champ_id.default_lanes().contains(*lane)).count()
+        } else {
+            0
+        };
+        let favourite_skinsets = (shared & self.favourite_skinsets).count();
+
+        let weights = self.weights;
+        let total = weights.shared_skinsets as f64 * shared_skinsets as f64
+            + weights.champ_preference as f64 * champ_preference
+            + weights.lane_fit as f64 * lane_fit as f64
+            + weights.favourite_skinsets as f64 * favourite_skinsets as f64;
+
+        CompScore { shared_skinsets, champ_preference, lane_fit, favourite_skinsets, total }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{ChampId, Lane, SkinsetId, UniverseId};
+    use enumflags2::BitFlags;
+
+    /// Get a champ's ID by their exact name.
+    fn champ(name: &str) -> ChampId {
+        ChampId::from_name(name).expect("champ exists")
+    }
+
+    /// Make a player with one champ in a tier.
+    fn player(champ_id: ChampId, tier: PreferenceTier) -> PlayerRecord {
+        PlayerRecord { name: None, champs: vec![(champ_id, BitFlags::all(), tier)] }
+    }
+
+    /// Make a scorer for players with the given weights.
+    fn scorer(players: &[PlayerRecord], game_mode: GameMode, weights: ScoreWeights) -> Scorer<'_> {
+        Scorer {
+            players,
+            favourite_skinsets: SkinsetMask::EMPTY,
+            match_mode: MatchMode::Skinset,
+            skinset_source: SkinsetSource::Wiki,
+            game_mode,
+            weights,
+        }
+    }
+
+    /// Get one of a champ's default lanes, or one of the lanes they aren't played in by default.
+    fn lane_of(champ_id: ChampId, default: bool) -> Lane {
+        let lanes = if default { champ_id.default_lanes() } else { !champ_id.default_lanes() };
+        lanes.iter().next().expect("champ has a lane")
+    }
+
+    #[test]
+    fn weighs_each_part_of_the_score() {
+        let (ahri, jinx, lux) = (champ("Ahri"), champ("Jinx"), champ("Lux"));
+        let players = [
+            player(ahri, PreferenceTier::Main),
+            player(jinx, PreferenceTier::Comfortable),
+            player(lux, PreferenceTier::Willing),
+        ];
+        let comp = vec![(ahri, lane_of(ahri, true)), (jinx, lane_of(jinx, true)), (lux, lane_of(lux, false))];
+
+        let shared: SkinsetMask = SkinsetId::iter_all().take(3).collect();
+        let weights = ScoreWeights { shared_skinsets: 2, champ_preference: 3, lane_fit: 5, favourite_skinsets: 7 };
+        let scorer = Scorer {
+            favourite_skinsets: SkinsetId::iter_all().skip(2).take(5).collect(),
+            ..scorer(&players, GameMode::SummonersRift, weights)
+        };
+
+        let score = scorer.score(&comp, shared);
+
+        assert_eq!(score.shared_skinsets, 3);
+        assert_eq!(score.champ_preference, 1.5);
+        assert_eq!(score.lane_fit, 2);
+        assert_eq!(score.favourite_skinsets, 1);
+        assert_eq!(score.total, 2.0 * 3.0 + 3.0 * 1.5 + 5.0 * 2.0 + 7.0 * 1.0);
+    }
+
+    #[test]
+    fn counts_universes_in_universe_mode() {
+        let universe = UniverseId::iter_all()
+            .find(|universe| universe.skinset_mask(SkinsetSource::Wiki).count() > 1)
+            .expect("a universe has several skinsets");
+        let players = [player(champ("Ahri"), PreferenceTier::Main)];
+        let scorer =
+            Scorer { match_mode: MatchMode::Universe, ..scorer(&players, GameMode::Aram, ScoreWeights::default()) };
+
+        let score = scorer.score(&vec![(champ("Ahri"), Lane::Top)], universe.skinset_mask(SkinsetSource::Wiki));
+
+        assert_eq!(score.shared_skinsets, 1);
+    }
+
+    #[test]
+    fn ignores_lanes_in_game_modes_without_them() {
+        let ahri = champ("Ahri");
+        let players = [player(ahri, PreferenceTier::Main)];
+        let comp = vec![(ahri, lane_of(ahri, true))];
+
+        let score_in = |game_mode| scorer(&players, game_mode, ScoreWeights::default()).score(&comp, SkinsetMask::EMPTY);
+        let (on_the_rift, in_aram) = (score_in(GameMode::SummonersRift), score_in(GameMode::Aram));
+
+        assert_eq!(on_the_rift.lane_fit, 1);
+        assert_eq!(in_aram.lane_fit, 0);
+        assert_eq!(on_the_rift.total - in_aram.total, ScoreWeights::default().lane_fit as f64);
+    }
+
+    #[test]
+    fn scores_nothing_with_zero_weights() {
+        let ahri = champ("Ahri");
+        let players = [player(ahri, PreferenceTier::Main)];
+        let weights = ScoreWeights { shared_skinsets: 0, champ_preference: 0, lane_fit: 0, favourite_skinsets: 0 };
+
+        let comp = vec![(ahri, lane_of(ahri, true))];
+        let score = scorer(&players, GameMode::SummonersRift, weights).score(&comp, SkinsetMask::ALL);
+
+        assert_eq!(score.total, 0.0);
+        assert_eq!(score.shared_skinsets, SkinsetMask::ALL.count());
+    }
+}
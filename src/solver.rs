@@ -0,0 +1,1014 @@
+//! Team comp solver used to find every comp that shares at least one skinset.
+//!
+//! Rather than building every champ/lane permutation up front and then throwing out the ones with no shared skinsets,
+//! comps are found lazily with a depth-first search over the players' champ pools. Each partial comp tracks the set of
+//! skinsets its champs still share, and is pruned as soon as that set can't be shared with the remaining players or
+//! the remaining players can't be matched to distinct lanes.
+//!
+//! When a full team rarely shares a skinset, the partial search finds comps where only some of the players share one
+//! (e.g. 4 of 5), leaving the others off-theme. Each partial comp instead tracks the skinsets shared by every subset of
+//! its picks, and is pruned once no subset can grow into a big enough group with the remaining players.
+//!
+//! Game modes without positions (ARAM and Arena) don't change the search itself -- each player's champs are just put
+//! in a lane of their own (see [ignore_lanes]), so that any comp of distinct champs that shares a skinset is found.
+
+use crate::constants::{ChampId, Lane, SkinsetMask, SkinsetSource};
+use derive_more::Display;
+use enumflags2::BitFlags;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+/// A team comp -- a champ and lane for each player, in the same order as the players passed to the solver.
+pub type Comp = Vec<(ChampId, Lane)>;
+
+/// A player's champ pool -- each champ they can play, with the lanes they would play them in.
+pub type ChampPool = Vec<(ChampId, BitFlags<Lane>)>;
+
+/// What a comp's champs have to share for the comp to count as themed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MatchMode {
+    /// Every champ has a skin in the same skinset.
+    #[default]
+    Skinset,
+
+    /// Every champ has a skin in the same universe, though not necessarily the same skinset (e.g. Crime City and
+    /// Debonair).
+    Universe,
+}
+
+impl MatchMode {
+    /// Get the set of non-excluded skinsets a champ can use towards a comp's theme in this mode.
+    fn champ_mask(self, champ: ChampId, skinsets_searched: SkinsetMask, source: SkinsetSource) -> SkinsetMask {
+        // Get the searched skinsets the champ has a skin in.
+        let champ_skinsets = champ.skinset_mask(source) & skinsets_searched;
+
+        match self {
+            MatchMode::Skinset => champ_skinsets,
+            // Each universe is a group of skinsets, so a champ can count towards every skinset in their universes.
+            MatchMode::Universe => champ_skinsets.expand_to_universes(source) & skinsets_searched,
+        }
+    }
+}
+
+/// The game mode comps are found for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Summoner's Rift, where every player is in a different lane.
+    #[default]
+    SummonersRift,
+
+    /// ARAM, where there are no lanes.
+    Aram,
+
+    /// Arena, where there are no lanes and players are paired up into 2-player teams.
+    Arena,
+}
+
+impl GameMode {
+    /// Check whether comps in this mode have to put every player in a different lane.
+    pub fn has_lanes(self) -> bool {
+        self == GameMode::SummonersRift
+    }
+
+    /// Get the players on each team that comps are found for separately, as ranges of player indices. In Arena, the
+    /// players are paired up in order (the last player is on their own if there's an odd number of them).
+    pub fn teams(self, player_count: usize) -> Vec<Range<usize>> {
+        match self {
+            GameMode::SummonersRift | GameMode::Aram => std::iter::once(0..player_count).collect(),
+            GameMode::Arena => (0..player_count).step_by(2).map(|start| start..(start + 2).min(player_count)).collect(),
+        }
+    }
+}
+
+/// The error returned by [ignore_lanes] and [resolve_partial_comps] when there are more players than lanes to put them
+/// in.
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq)]
+#[display(fmt = "{player_count} players can't be searched together (at most {max_players} can, one per lane)")]
+pub struct TooManyPlayers {
+    /// The number of champ pools passed in.
+    pub player_count: usize,
+    /// The most players that can be searched, one per lane.
+    pub max_players: usize,
+}
+
+impl std::error::Error for TooManyPlayers {}
+
+/// Put each player's champs in a lane of their own, so that the solver only requires distinct champs that share a
+/// skinset. This is used for game modes without lanes. Every player needs a lane of their own, so this fails (leaving
+/// the pools unchanged) if there are more players than lanes.
+pub fn ignore_lanes(champ_pools: &mut [ChampPool]) -> Result<(), TooManyPlayers> {
+    let lanes = BitFlags::<Lane>::all();
+
+    if champ_pools.len() > lanes.len() {
+        return Err(TooManyPlayers { player_count: champ_pools.len(), max_players: lanes.len() });
+    }
+
+    for (pool, lane) in champ_pools.iter_mut().zip(lanes.iter()) {
+        for (_, lanes) in pool.iter_mut() {
+            *lanes = lane.into();
+        }
+    }
+
+    Ok(())
+}
+
+/// Get a lazy iterator over every comp that this set of champ pools could queue that shares at least one non-excluded
+/// skinset (or universe, depending on the match mode), along with the set of non-excluded skinsets shared by each
+/// comp. In universe mode, this is every non-excluded skinset in the shared universes. Only skinsets in the given
+/// skinset source's table are searched.
+///
+/// Each pool is a player's list of champs and the lanes they would play them in. Comps are returned in the order of
+/// the players' champ lists (the first player's first champ in their first lane comes first, and so on).
+pub fn resolve_comps<'a>(
+    champ_pools: &'a [&'a [(ChampId, BitFlags<Lane>)]],
+    skinsets_excluded: SkinsetMask,
+    match_mode: MatchMode,
+    skinset_source: SkinsetSource,
+) -> CompIter<'a> {
+    // Get the set of skinsets we're searching.
+    let skinsets_searched: SkinsetMask = !skinsets_excluded & skinset_source.skinset_mask();
+
+    // Get the set of skinsets each champ in each pool can use.
+    let pool_masks: Vec<Vec<SkinsetMask>> = champ_pools
+        .iter()
+        .map(|pool| pool.iter().map(|(champ, _)| match_mode.champ_mask(*champ, skinsets_searched, skinset_source)).collect())
+        .collect();
+
+    // Get the set of skinsets each player has at least one champ for.
+    let pool_skinsets = pool_masks
+        .iter()
+        .map(|masks| masks.iter().fold(SkinsetMask::EMPTY, |union, mask| union | *mask));
+
+    // Get the set of searched skinsets shared by the pools of each player and all the players after them.
+    let mut suffix_skinsets: Vec<SkinsetMask> = pool_skinsets
+        .rev()
+        .scan(skinsets_searched, |shared, mask| {
+            *shared &= mask;
+            Some(*shared)
+        })
+        .collect();
+
+    suffix_skinsets.reverse();
+    suffix_skinsets.push(skinsets_searched);
+
+    CompIter {
+        champ_pools,
+        pool_masks,
+        suffix_skinsets,
+        stack: Vec::with_capacity(champ_pools.len()),
+        finished: champ_pools.is_empty(),
+    }
+}
+
+/// A champ and lane picked for a player in a partial comp.
+#[derive(Clone, Copy, Debug)]
+struct Pick {
+    /// The position of the champ in the player's pool.
+    position: usize,
+    /// The lane the champ is played in.
+    lane: Lane,
+    /// The lanes for this champ that come after [Pick::lane] and haven't been tried yet.
+    untried_lanes: BitFlags<Lane>,
+    /// The lanes used by this pick and all the picks before it.
+    used_lanes: BitFlags<Lane>,
+    /// The skinsets shared by this pick and all the picks before it that could still be shared by the remaining
+    /// players.
+    shared: SkinsetMask,
+}
+
+/// Lazy iterator over the comps found by [resolve_comps].
+#[derive(Clone, Debug)]
+pub struct CompIter<'a> {
+    /// The champ pools of each player.
+    champ_pools: &'a [&'a [(ChampId, BitFlags<Lane>)]],
+    /// The set of searched skinsets each champ in each pool can use.
+    pool_masks: Vec<Vec<SkinsetMask>>,
+    /// The searched skinsets shared by the pools of each player and the players after them, with an extra entry at the
+    /// end containing all searched skinsets.
+    suffix_skinsets: Vec<SkinsetMask>,
+    /// The picks for the partial comp currently being searched.
+    stack: Vec<Pick>,
+    /// Has the search been exhausted?
+    finished: bool,
+}
+
+impl CompIter<'_> {
+    /// Find the first valid pick for a player at or after a position in their pool. If `first_lanes` is provided, only
+    /// those lanes are tried for the champ at the starting position.
+    fn find_pick(&self, player: usize, start: usize, first_lanes: Option<BitFlags<Lane>>) -> Option<Pick> {
+        // Get the state left by the previous player's pick.
+        let (parent_used, parent_shared) = self
+            .stack
+            .last()
+            .map(|pick| (pick.used_lanes, pick.shared))
+            .unwrap_or((BitFlags::empty(), self.suffix_skinsets[0]));
+
+        for (position, (champ, lanes)) in self.champ_pools[player].iter().enumerate().skip(start) {
+            // Skip champs that an earlier player is already on.
+            if self.stack.iter().enumerate().any(|(other, pick)| self.champ_pools[other][pick.position].0 == *champ) {
+                continue;
+            }
+
+            // Get the skinsets that could still be shared if this champ is picked.
+            let shared = parent_shared & self.pool_masks[player][position] & self.suffix_skinsets[player + 1];
+
+            if shared.is_empty() {
+                continue;
+            }
+
+            // Get the lanes each remaining player could play while still sharing a skinset.
+            let remaining_lanes: Vec<BitFlags<Lane>> = self.champ_pools[player + 1..]
+                .iter()
+                .zip(&self.pool_masks[player + 1..])
+                .map(|(pool, masks)| {
+                    pool.iter()
+                        .zip(masks)
+                        .filter(|(_, mask)| !(**mask & shared).is_empty())
+                        .fold(BitFlags::empty(), |lanes, ((_, other_lanes), _)| lanes | *other_lanes)
+                })
+                .collect();
+
+            // Get the lanes to try for this champ.
+            let mut untried_lanes = *lanes & !parent_used;
+
+            if position == start {
+                untried_lanes &= first_lanes.unwrap_or(BitFlags::all());
+            }
+
+            while let Some(lane) = untried_lanes.iter().next() {
+                untried_lanes.remove(lane);
+
+                // Only pick this lane if the rest of the players can still be matched to the remaining lanes.
+                if lanes_matchable(&remaining_lanes, !(parent_used | lane)) {
+                    return Some(Pick {
+                        position,
+                        lane,
+                        untried_lanes,
+                        used_lanes: parent_used | lane,
+                        shared,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Replace the last pick with the next valid pick for that player, backing up to earlier players when there are
+    /// none left.
+    fn backtrack(&mut self) {
+        while let Some(pick) = self.stack.pop() {
+            // Get the player who made this pick.
+            let player = self.stack.len();
+
+            // Try the rest of the lanes for this pick's champ, then the rest of the player's champs.
+            if let Some(next_pick) = self.find_pick(player, pick.position, Some(pick.untried_lanes)) {
+                self.stack.push(next_pick);
+                return;
+            }
+        }
+
+        // If the stack is empty then we've exhausted every comp.
+        self.finished = true;
+    }
+}
+
+impl Iterator for CompIter<'_> {
+    type Item = (Comp, SkinsetMask);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Move on from the last comp returned, if there was one.
+        if !self.finished && self.stack.len() == self.champ_pools.len() {
+            self.backtrack();
+        }
+
+        while !self.finished {
+            // Return the comp once every player has a pick.
+            if self.stack.len() == self.champ_pools.len() {
+                let comp: Comp = self
+                    .stack
+                    .iter()
+                    .enumerate()
+                    .map(|(player, pick)| (self.champ_pools[player][pick.position].0, pick.lane))
+                    .collect();
+
+                // The last pick's shared set is exactly the searched skinsets shared by the whole comp.
+                let shared = self.stack.last().expect("at least one player").shared;
+
+                return Some((comp, shared));
+            }
+
+            // Otherwise make the first valid pick for the next player.
+            match self.find_pick(self.stack.len(), 0, None) {
+                Some(pick) => self.stack.push(pick),
+                None => self.backtrack(),
+            }
+        }
+
+        None
+    }
+}
+
+impl FusedIterator for CompIter<'_> {}
+
+/// A group of players in a comp whose champs share skinsets that none of the other champs in the comp have.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThemedGroup {
+    /// The indices of the players in the group, in order.
+    pub players: Vec<usize>,
+    /// The non-excluded skinsets shared by the group's champs and no others in the comp. In universe mode, this is
+    /// every non-excluded skinset in the universes they share.
+    pub shared: SkinsetMask,
+}
+
+/// The groups of players in a comp that share skinsets.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompThemes {
+    /// Each group with at least the minimum number of themed players, largest first.
+    pub groups: Vec<ThemedGroup>,
+}
+
+impl CompThemes {
+    /// Get the themes of a comp where every player shares the same skinsets.
+    pub fn full(player_count: usize, shared: SkinsetMask) -> Self {
+        CompThemes {
+            groups: vec![ThemedGroup { players: (0..player_count).collect(), shared }],
+        }
+    }
+
+    /// Get the number of players in the largest themed group.
+    pub fn largest_group(&self) -> usize {
+        self.groups.first().map_or(0, |group| group.players.len())
+    }
+
+    /// Check whether a player is in at least one themed group.
+    pub fn is_in_theme(&self, player: usize) -> bool {
+        self.groups.iter().any(|group| group.players.contains(&player))
+    }
+
+    /// Get every skinset shared by at least one themed group.
+    pub fn shared(&self) -> SkinsetMask {
+        self.groups.iter().fold(SkinsetMask::EMPTY, |union, group| union | group.shared)
+    }
+}
+
+/// Get a lazy iterator over the comps found by either [resolve_comps] or [resolve_partial_comps], along with the
+/// groups of players sharing skinsets in each comp. If `min_themed_players` is `None` (or at least the number of
+/// players), every player has to share a skinset. Like [resolve_partial_comps], this fails if only some of the players
+/// have to share a skinset and there are more players than lanes.
+pub fn resolve_themed_comps<'a>(
+    champ_pools: &'a [&'a [(ChampId, BitFlags<Lane>)]],
+    skinsets_excluded: SkinsetMask,
+    match_mode: MatchMode,
+    skinset_source: SkinsetSource,
+    min_themed_players: Option<usize>,
+) -> Result<Box<dyn Iterator<Item = (Comp, CompThemes)> + 'a>, TooManyPlayers> {
+    match min_themed_players {
+        Some(min_themed_players) if min_themed_players < champ_pools.len() => Ok(Box::new(resolve_partial_comps(
+            champ_pools,
+            skinsets_excluded,
+            match_mode,
+            skinset_source,
+            min_themed_players,
+        )?)),
+
+        _ => Ok(Box::new(
+            resolve_comps(champ_pools, skinsets_excluded, match_mode, skinset_source)
+                .map(|(comp, shared)| (comp, CompThemes::full(champ_pools.len(), shared))),
+        )),
+    }
+}
+
+/// Get a lazy iterator over every comp that this set of champ pools could queue where at least `min_themed_players`
+/// of the players share a non-excluded skinset (or universe), along with the groups of players sharing skinsets in
+/// each comp. The players outside every group are off-theme.
+///
+/// Comps are returned with the largest themed group first: every comp where all the players share a skinset, then
+/// every comp where all but one of them do, and so on. Within each group size, comps are returned in the order of the
+/// players' champ lists, as with [resolve_comps].
+///
+/// Every group of players is searched, so this fails (rather than searching groups of players that could never all
+/// have a lane of their own) if there are more players than lanes.
+pub fn resolve_partial_comps<'a>(
+    champ_pools: &'a [&'a [(ChampId, BitFlags<Lane>)]],
+    skinsets_excluded: SkinsetMask,
+    match_mode: MatchMode,
+    skinset_source: SkinsetSource,
+    min_themed_players: usize,
+) -> Result<impl FusedIterator<Item = (Comp, CompThemes)> + 'a, TooManyPlayers> {
+    let player_count = champ_pools.len();
+    let max_players = BitFlags::<Lane>::all().len();
+
+    if player_count > max_players {
+        return Err(TooManyPlayers { player_count, max_players });
+    }
+
+    // Get the set of skinsets we're searching.
+    let skinsets_searched: SkinsetMask = !skinsets_excluded & skinset_source.skinset_mask();
+
+    // Get the set of skinsets each champ in each pool can use.
+    let pool_masks: Vec<Vec<SkinsetMask>> = champ_pools
+        .iter()
+        .map(|pool| pool.iter().map(|(champ, _)| match_mode.champ_mask(*champ, skinsets_searched, skinset_source)).collect())
+        .collect();
+
+    // Get the lanes each player could play any of their champs in.
+    let pool_lanes: Vec<BitFlags<Lane>> = champ_pools
+        .iter()
+        .map(|pool| pool.iter().fold(BitFlags::empty(), |union, (_, lanes)| union | *lanes))
+        .collect();
+
+    // Get the set of searched skinsets each group of players could share, indexed by the bitset of the group's
+    // players.
+    let mut group_skinsets = vec![skinsets_searched; 1 << player_count];
+
+    for group in 1..group_skinsets.len() {
+        let player = group.trailing_zeros() as usize;
+        let pool_skinsets = pool_masks[player].iter().fold(SkinsetMask::EMPTY, |union, mask| union | *mask);
+        group_skinsets[group] = group_skinsets[group & (group - 1)] & pool_skinsets;
+    }
+
+    // Search for the comps with each size of largest group in turn, from the whole team down.
+    let min_themed_players = min_themed_players.clamp(1, player_count.max(1));
+
+    Ok((min_themed_players..=player_count).rev().flat_map(move |themed_players| PartialCompIter {
+        champ_pools,
+        pool_masks: pool_masks.clone(),
+        pool_lanes: pool_lanes.clone(),
+        group_skinsets: group_skinsets.clone(),
+        themed_players,
+        min_themed_players,
+        stack: Vec::with_capacity(player_count),
+        finished: false,
+    }))
+}
+
+/// A champ and lane picked for a player in a partial comp searched by [resolve_partial_comps].
+#[derive(Clone, Debug)]
+struct PartialPick {
+    /// The position of the champ in the player's pool.
+    position: usize,
+    /// The lane the champ is played in.
+    lane: Lane,
+    /// The lanes for this champ that come after [PartialPick::lane] and haven't been tried yet.
+    untried_lanes: BitFlags<Lane>,
+    /// The lanes used by this pick and all the picks before it.
+    used_lanes: BitFlags<Lane>,
+    /// The searched skinsets shared by each subset of this pick and the picks before it, indexed by the bitset of the
+    /// subset's players.
+    subset_skinsets: Vec<SkinsetMask>,
+}
+
+/// Lazy iterator over the comps found by [resolve_partial_comps] whose largest themed group has a given size.
+#[derive(Clone, Debug)]
+struct PartialCompIter<'a> {
+    /// The champ pools of each player.
+    champ_pools: &'a [&'a [(ChampId, BitFlags<Lane>)]],
+    /// The set of searched skinsets each champ in each pool can use.
+    pool_masks: Vec<Vec<SkinsetMask>>,
+    /// The lanes each player could play any of their champs in.
+    pool_lanes: Vec<BitFlags<Lane>>,
+    /// The searched skinsets each group of players could share, indexed by the bitset of the group's players.
+    group_skinsets: Vec<SkinsetMask>,
+    /// The exact size of the largest themed group in the comps returned.
+    themed_players: usize,
+    /// The minimum size of the groups returned with each comp.
+    min_themed_players: usize,
+    /// The picks for the partial comp currently being searched.
+    stack: Vec<PartialPick>,
+    /// Has the search been exhausted?
+    finished: bool,
+}
+
+impl PartialCompIter<'_> {
+    /// Check whether a partial comp, given the skinsets shared by each subset of its picks, could still end up with a
+    /// largest themed group of exactly the size being searched for.
+    fn viable(&self, subset_skinsets: &[SkinsetMask]) -> bool {
+        // Every group among the picks so far is final, so none of them can be too big already.
+        let too_big = subset_skinsets
+            .iter()
+            .enumerate()
+            .any(|(subset, shared)| subset.count_ones() as usize > self.themed_players && !shared.is_empty());
+
+        if too_big {
+            return false;
+        }
+
+        // Get the bitset of the players who haven't picked yet.
+        let remaining = (self.group_skinsets.len() - 1) & !(subset_skinsets.len() - 1);
+
+        // Check if some subset of the picks can be joined by some subset of the remaining players to make a group that
+        // is big enough.
+        subset_skinsets.iter().enumerate().any(|(subset, shared)| {
+            let mut others = remaining;
+
+            loop {
+                let group_size = (subset.count_ones() + others.count_ones()) as usize;
+
+                if group_size >= self.themed_players && !(*shared & self.group_skinsets[others]).is_empty() {
+                    return true;
+                }
+
+                if others == 0 {
+                    return false;
+                }
+
+                // Move on to the next subset of the remaining players.
+                others = (others - 1) & remaining;
+            }
+        })
+    }
+
+    /// Find the first valid pick for a player at or after a position in their pool. If `first_lanes` is provided, only
+    /// those lanes are tried for the champ at the starting position.
+    fn find_pick(&self, player: usize, start: usize, first_lanes: Option<BitFlags<Lane>>) -> Option<PartialPick> {
+        // Get the state left by the previous player's pick.
+        let (parent_used, parent_subsets) = match self.stack.last() {
+            Some(pick) => (pick.used_lanes, pick.subset_skinsets.as_slice()),
+            None => (BitFlags::empty(), &self.group_skinsets[..1]),
+        };
+
+        // The remaining players could play any of their lanes.
+        let remaining_lanes = &self.pool_lanes[player + 1..];
+
+        for (position, (champ, lanes)) in self.champ_pools[player].iter().enumerate().skip(start) {
+            // Skip champs that an earlier player is already on.
+            if self.stack.iter().enumerate().any(|(other, pick)| self.champ_pools[other][pick.position].0 == *champ) {
+                continue;
+            }
+
+            // The subsets with this player come after the subsets without them.
+            let mask = self.pool_masks[player][position];
+            let subset_skinsets: Vec<SkinsetMask> =
+                parent_subsets.iter().copied().chain(parent_subsets.iter().map(|shared| *shared & mask)).collect();
+
+            if !self.viable(&subset_skinsets) {
+                continue;
+            }
+
+            // Get the lanes to try for this champ.
+            let mut untried_lanes = *lanes & !parent_used;
+
+            if position == start {
+                untried_lanes &= first_lanes.unwrap_or(BitFlags::all());
+            }
+
+            while let Some(lane) = untried_lanes.iter().next() {
+                untried_lanes.remove(lane);
+
+                // Only pick this lane if the rest of the players can still be matched to the remaining lanes.
+                if lanes_matchable(remaining_lanes, !(parent_used | lane)) {
+                    return Some(PartialPick {
+                        position,
+                        lane,
+                        untried_lanes,
+                        used_lanes: parent_used | lane,
+                        subset_skinsets,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Replace the last pick with the next valid pick for that player, backing up to earlier players when there are
+    /// none left.
+    fn backtrack(&mut self) {
+        while let Some(pick) = self.stack.pop() {
+            // Get the player who made this pick.
+            let player = self.stack.len();
+
+            // Try the rest of the lanes for this pick's champ, then the rest of the player's champs.
+            if let Some(next_pick) = self.find_pick(player, pick.position, Some(pick.untried_lanes)) {
+                self.stack.push(next_pick);
+                return;
+            }
+        }
+
+        // If the stack is empty then we've exhausted every comp.
+        self.finished = true;
+    }
+
+    /// Get the themed groups of the complete comp on the stack.
+    fn themes(&self) -> CompThemes {
+        let subset_skinsets = &self.stack.last().expect("at least one player").subset_skinsets;
+        let everyone = subset_skinsets.len() - 1;
+
+        // Get the skinsets each player's champ can use.
+        let player_masks: Vec<SkinsetMask> = (0..self.stack.len()).map(|player| subset_skinsets[1 << player]).collect();
+
+        let mut groups: Vec<ThemedGroup> = subset_skinsets
+            .iter()
+            .enumerate()
+            .filter(|(group, _)| group.count_ones() as usize >= self.min_themed_players)
+            .filter_map(|(group, shared)| {
+                // Only keep the skinsets that no one outside the group has, so each skinset is listed once.
+                let outside = player_masks
+                    .iter()
+                    .enumerate()
+                    .filter(|(player, _)| (everyone & !group) & (1 << player) != 0)
+                    .fold(SkinsetMask::EMPTY, |union, (_, mask)| union | *mask);
+
+                let shared = *shared & !outside;
+
+                (!shared.is_empty()).then(|| ThemedGroup {
+                    players: (0..self.stack.len()).filter(|player| group & (1 << player) != 0).collect(),
+                    shared,
+                })
+            })
+            .collect();
+
+        groups.sort_by_key(|group| Reverse(group.players.len()));
+        CompThemes { groups }
+    }
+}
+
+impl Iterator for PartialCompIter<'_> {
+    type Item = (Comp, CompThemes);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Move on from the last comp returned, if there was one.
+        if !self.finished && self.stack.len() == self.champ_pools.len() {
+            self.backtrack();
+        }
+
+        while !self.finished {
+            // Return the comp once every player has a pick.
+            if self.stack.len() == self.champ_pools.len() {
+                let comp: Comp = self
+                    .stack
+                    .iter()
+                    .enumerate()
+                    .map(|(player, pick)| (self.champ_pools[player][pick.position].0, pick.lane))
+                    .collect();
+
+                return Some((comp, self.themes()));
+            }
+
+            // Otherwise make the first valid pick for the next player.
+            match self.find_pick(self.stack.len(), 0, None) {
+                Some(pick) => self.stack.push(pick),
+                None => self.backtrack(),
+            }
+        }
+
+        None
+    }
+}
+
+impl FusedIterator for PartialCompIter<'_> {}
+
+/// Check whether each player can be matched to a distinct lane, given the lanes available to each player and the
+/// lanes that are free.
+fn lanes_matchable(player_lanes: &[BitFlags<Lane>], free_lanes: BitFlags<Lane>) -> bool {
+    // The player (if any) matched to each lane, indexed by the lane's bit position.
+    let mut lane_owners: [Option<usize>; 8] = [None; 8];
+
+    // Try to find an augmenting path for each player.
+    player_lanes.iter().enumerate().all(|(player, _)| {
+        let mut visited: BitFlags<Lane> = BitFlags::empty();
+        augment(player, player_lanes, free_lanes, &mut lane_owners, &mut visited)
+    })
+}
+
+/// Try to match a player to a lane, re-matching other players if necessary (Kuhn's algorithm).
+fn augment(
+    player: usize,
+    player_lanes: &[BitFlags<Lane>],
+    free_lanes: BitFlags<Lane>,
+    lane_owners: &mut [Option<usize>; 8],
+    visited: &mut BitFlags<Lane>,
+) -> bool {
+    for lane in (player_lanes[player] & free_lanes & !*visited).iter() {
+        visited.insert(lane);
+        // Get the index of this lane's bit.
+        let lane_index = BitFlags::from(lane).bits().trailing_zeros() as usize;
+
+        // Take the lane if it's unowned or its owner can move to a different lane.
+        let available = match lane_owners[lane_index] {
+            None => true,
+            Some(owner) => augment(owner, player_lanes, free_lanes, lane_owners, visited),
+        };
+
+        if available {
+            lane_owners[lane_index] = Some(player);
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SkinsetId;
+    use std::collections::HashMap;
+
+    /// Champs with skins in lots of skinsets, so that random pools often share some.
+    const CHAMPS: &[&str] = &[
+        "Ahri", "Lux", "Ezreal", "Jinx", "Lulu", "Miss Fortune", "Ashe", "Garen", "Katarina", "Leona", "Diana", "Yasuo",
+        "Sona", "Vayne", "Annie", "Caitlyn", "Thresh", "Varus", "Xerath", "Karma",
+    ];
+
+    /// The number of random sets of pools each test checks.
+    const TRIALS: u64 = 400;
+
+    /// Small xorshift generator, so that every run checks the same pools without a dependency.
+    struct Rng(u64);
+
+    impl Rng {
+        /// Get a random number below `bound`.
+        fn below(&mut self, bound: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % bound as u64) as usize
+        }
+    }
+
+    /// Make 1 to 5 random pools, each with 1 to 4 distinct champs in random (possibly no) lanes.
+    fn random_pools(rng: &mut Rng) -> Vec<Vec<(ChampId, BitFlags<Lane>)>> {
+        (0..1 + rng.below(5))
+            .map(|_| {
+                let mut pool: Vec<(ChampId, BitFlags<Lane>)> = Vec::new();
+
+                for _ in 0..1 + rng.below(4) {
+                    let champ = ChampId::from_name(CHAMPS[rng.below(CHAMPS.len())]).expect("champ exists");
+                    let lanes = BitFlags::from_bits_truncate(rng.below(32) as u8);
+
+                    if pool.iter().all(|(other, _)| *other != champ) {
+                        pool.push((champ, lanes));
+                    }
+                }
+
+                pool
+            })
+            .collect()
+    }
+
+    /// Get every comp of distinct champs in distinct lanes, in pool order. This is the nested-loop algorithm the
+    /// solver replaced.
+    fn every_comp(champ_pools: &[Vec<(ChampId, BitFlags<Lane>)>]) -> Vec<Comp> {
+        let Some((pool, rest)) = champ_pools.split_first() else {
+            return vec![Vec::new()];
+        };
+
+        let others = every_comp(rest);
+        let mut result = Vec::new();
+
+        for (champ, lanes) in pool {
+            for lane in lanes.iter() {
+                for other in &others {
+                    if other.iter().all(|(other_champ, other_lane)| other_champ != champ && *other_lane != lane) {
+                        result.push(std::iter::once((*champ, lane)).chain(other.iter().copied()).collect());
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Get the searched skinsets each champ in some pools can use, checking each champ's skinsets directly.
+    fn champ_skinsets(
+        champ_pools: &[&[(ChampId, BitFlags<Lane>)]],
+        skinsets_excluded: SkinsetMask,
+        match_mode: MatchMode,
+        source: SkinsetSource,
+    ) -> HashMap<ChampId, SkinsetMask> {
+        let skinsets_searched = !skinsets_excluded & source.skinset_mask();
+
+        champ_pools
+            .iter()
+            .flat_map(|pool| pool.iter())
+            .map(|(champ, _)| {
+                let skinsets = champ.skinset_mask(source) & skinsets_searched;
+
+                let usable = match match_mode {
+                    MatchMode::Skinset => skinsets,
+                    MatchMode::Universe => skinsets.expand_to_universes(source) & skinsets_searched,
+                };
+
+                (*champ, usable)
+            })
+            .collect()
+    }
+
+    /// Run a check against random pools for every match mode and source, with and without the default exclusions.
+    fn for_random_pools(mut check: impl FnMut(&[&[(ChampId, BitFlags<Lane>)]], SkinsetMask, MatchMode, SkinsetSource)) {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for trial in 0..TRIALS {
+            let owned_pools = random_pools(&mut rng);
+            let champ_pools: Vec<&[(ChampId, BitFlags<Lane>)]> = owned_pools.iter().map(Vec::as_slice).collect();
+
+            let skinsets_excluded = if trial % 2 == 0 {
+                SkinsetMask::EMPTY
+            } else {
+                SkinsetMask::from_skinsets(SkinsetId::DEFAULT_EXCLUDED_SKINSETS)
+            };
+
+            for match_mode in [MatchMode::Skinset, MatchMode::Universe] {
+                for source in [SkinsetSource::Wiki, SkinsetSource::Official] {
+                    check(&champ_pools, skinsets_excluded, match_mode, source);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finds_the_same_comps_as_the_nested_loop_algorithm() {
+        for_random_pools(|champ_pools, skinsets_excluded, match_mode, source| {
+            let owned_pools: Vec<Vec<_>> = champ_pools.iter().map(|pool| pool.to_vec()).collect();
+            let skinsets = champ_skinsets(champ_pools, skinsets_excluded, match_mode, source);
+            let skinsets_searched = !skinsets_excluded & source.skinset_mask();
+
+            let expected: Vec<(Comp, SkinsetMask)> = every_comp(&owned_pools)
+                .into_iter()
+                .map(|comp| {
+                    let shared = comp.iter().fold(skinsets_searched, |shared, (champ, _)| shared & skinsets[champ]);
+                    (comp, shared)
+                })
+                .filter(|(_, shared)| !shared.is_empty())
+                .collect();
+
+            let found: Vec<(Comp, SkinsetMask)> =
+                resolve_comps(champ_pools, skinsets_excluded, match_mode, source).collect();
+
+            assert_eq!(found, expected, "pools {owned_pools:?} in {match_mode:?} mode with {source:?} skinsets");
+        });
+    }
+
+    #[test]
+    fn finds_the_same_partial_comps_as_the_nested_loop_algorithm() {
+        for_random_pools(|champ_pools, skinsets_excluded, match_mode, source| {
+            let owned_pools: Vec<Vec<_>> = champ_pools.iter().map(|pool| pool.to_vec()).collect();
+            let player_count = champ_pools.len();
+            let skinsets = champ_skinsets(champ_pools, skinsets_excluded, match_mode, source);
+            let skinsets_searched = !skinsets_excluded & source.skinset_mask();
+
+            // Get the skinsets shared by a group of players in a comp, given the bitset of the group's players.
+            let shared_by = |comp: &Comp, group: usize| {
+                comp.iter()
+                    .enumerate()
+                    .filter(|(player, _)| group & (1 << player) != 0)
+                    .fold(skinsets_searched, |shared, (_, (champ, _))| shared & skinsets[champ])
+            };
+
+            // Get the size of the largest group of players in each comp that shares a skinset.
+            let comps: Vec<(Comp, usize)> = every_comp(&owned_pools)
+                .into_iter()
+                .map(|comp| {
+                    let largest_group = (1..1usize << player_count)
+                        .filter(|group| !shared_by(&comp, *group).is_empty())
+                        .map(|group| group.count_ones() as usize)
+                        .max()
+                        .unwrap_or(0);
+
+                    (comp, largest_group)
+                })
+                .collect();
+
+            for min_themed_players in 1..=player_count {
+                // The largest groups come first, and the sort is stable so each size stays in pool order.
+                let mut expected: Vec<(Comp, usize)> =
+                    comps.iter().filter(|(_, largest_group)| *largest_group >= min_themed_players).cloned().collect();
+                expected.sort_by_key(|(_, largest_group)| Reverse(*largest_group));
+
+                let found: Vec<(Comp, CompThemes)> =
+                    resolve_partial_comps(champ_pools, skinsets_excluded, match_mode, source, min_themed_players)
+                        .expect("at most one player per lane")
+                        .collect();
+
+                // Every group has to be big enough and actually share its skinsets.
+                for (comp, themes) in &found {
+                    for group in &themes.groups {
+                        let shared = shared_by(comp, group.players.iter().map(|player| 1 << player).sum());
+
+                        assert!(group.players.len() >= min_themed_players);
+                        assert!(!group.shared.is_empty() && (group.shared & !shared).is_empty());
+                    }
+                }
+
+                let found: Vec<(Comp, usize)> =
+                    found.into_iter().map(|(comp, themes)| (comp, themes.largest_group())).collect();
+
+                assert_eq!(
+                    found, expected,
+                    "pools {owned_pools:?} in {match_mode:?} mode with {source:?} skinsets, at least \
+                     {min_themed_players} in theme"
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn ignores_lanes_for_up_to_one_player_per_lane() {
+        let ahri = ChampId::from_name("Ahri").expect("champ exists");
+        let mut champ_pools: Vec<ChampPool> = vec![vec![(ahri, Lane::Mid.into())]; 5];
+
+        ignore_lanes(&mut champ_pools).expect("five players fit in the lanes");
+
+        // Every player gets a different lane, so only distinct champs are required.
+        let lanes: BitFlags<Lane> = champ_pools.iter().map(|pool| pool[0].1).collect();
+        assert_eq!(lanes, BitFlags::all());
+
+        let mut champ_pools: Vec<ChampPool> = vec![vec![(ahri, Lane::Mid.into())]; 6];
+
+        assert_eq!(ignore_lanes(&mut champ_pools), Err(TooManyPlayers { player_count: 6, max_players: 5 }));
+        assert!(champ_pools.iter().all(|pool| pool[0].1 == Lane::Mid));
+    }
+
+    #[test]
+    fn only_searches_partial_comps_for_up_to_one_player_per_lane() {
+        let ahri = ChampId::from_name("Ahri").expect("champ exists");
+        let pool: &[(ChampId, BitFlags<Lane>)] = &[(ahri, Lane::Mid.into())];
+        let champ_pools = [pool; 6];
+        let (source, mode) = (SkinsetSource::Wiki, MatchMode::Skinset);
+
+        let too_many = Some(TooManyPlayers { player_count: 6, max_players: 5 });
+        assert_eq!(resolve_partial_comps(&champ_pools, SkinsetMask::EMPTY, mode, source, 2).err(), too_many);
+        assert_eq!(resolve_themed_comps(&champ_pools, SkinsetMask::EMPTY, mode, source, Some(2)).err(), too_many);
+
+        // Full comps don't search groups of players, so they only find nothing.
+        let found = resolve_themed_comps(&champ_pools, SkinsetMask::EMPTY, mode, source, None);
+        assert_eq!(found.expect("full comps are searched").count(), 0);
+    }
+
+    #[test]
+    fn finds_themed_groups_in_comps() {
+        let skinset = |name| SkinsetMask::from_skinsets(&[SkinsetId::from_name(name).expect("skinset exists")]);
+
+        let themes = CompThemes::full(3, skinset("Legacy"));
+        assert_eq!(themes.largest_group(), 3);
+        assert!((0..3).all(|player| themes.is_in_theme(player)));
+        assert_eq!(themes.shared(), skinset("Legacy"));
+
+        let themes = CompThemes {
+            groups: vec![
+                ThemedGroup { players: vec![0, 1, 3], shared: skinset("Legacy") },
+                ThemedGroup { players: vec![1, 4], shared: skinset("Dark Star") },
+            ],
+        };
+        assert_eq!(themes.largest_group(), 3);
+        assert!(!themes.is_in_theme(2));
+        assert!(themes.is_in_theme(4));
+        assert_eq!(themes.shared(), skinset("Legacy") | skinset("Dark Star"));
+
+        let themes = CompThemes::default();
+        assert_eq!(themes.largest_group(), 0);
+        assert!(!themes.is_in_theme(0));
+        assert!(themes.shared().is_empty());
+    }
+
+    #[test]
+    fn finds_partial_comps_with_off_theme_players() {
+        let champ = |name| ChampId::from_name(name).expect("champ exists");
+        let legacy = SkinsetMask::from_skinsets(&[SkinsetId::from_name("Legacy").expect("skinset exists")]);
+
+        // Ahri, Lux and Teemo only share Legacy skins, and Singed shares no skinsets with Ahri or Lux.
+        let owned_pools: Vec<Vec<(ChampId, BitFlags<Lane>)>> = vec![
+            vec![(champ("Ahri"), Lane::Mid.into())],
+            vec![(champ("Lux"), Lane::Support.into())],
+            vec![(champ("Teemo"), Lane::Top.into()), (champ("Singed"), Lane::Top.into())],
+        ];
+        let champ_pools: Vec<&[(ChampId, BitFlags<Lane>)]> = owned_pools.iter().map(Vec::as_slice).collect();
+        let source = SkinsetSource::Wiki;
+
+        let full_comp: Comp =
+            vec![(champ("Ahri"), Lane::Mid), (champ("Lux"), Lane::Support), (champ("Teemo"), Lane::Top)];
+        let partial_comp: Comp =
+            vec![(champ("Ahri"), Lane::Mid), (champ("Lux"), Lane::Support), (champ("Singed"), Lane::Top)];
+
+        // The comp where everyone is in theme comes first, then the one with Singed off-theme.
+        let found: Vec<(Comp, CompThemes)> =
+            resolve_partial_comps(&champ_pools, SkinsetMask::EMPTY, MatchMode::Skinset, source, 2)
+                .expect("at most one player per lane")
+                .collect();
+        let comps: Vec<&Comp> = found.iter().map(|(comp, _)| comp).collect();
+        assert_eq!(comps, vec![&full_comp, &partial_comp]);
+
+        // Smaller groups sharing other skinsets come after the whole team.
+        let full_themes = &found[0].1;
+        assert_eq!(full_themes.groups[0], CompThemes::full(3, legacy).groups[0]);
+        assert!(full_themes.groups.windows(2).all(|pair| pair[0].players.len() >= pair[1].players.len()));
+
+        let partial_themes = CompThemes { groups: vec![ThemedGroup { players: vec![0, 1], shared: legacy }] };
+        assert_eq!(found[1].1, partial_themes);
+        assert!(!found[1].1.is_in_theme(2));
+
+        // Requiring every player to be in theme is the same as searching for full comps.
+        for min_themed_players in [None, Some(3), Some(4)] {
+            let found: Vec<(Comp, CompThemes)> =
+                resolve_themed_comps(&champ_pools, SkinsetMask::EMPTY, MatchMode::Skinset, source, min_themed_players)
+                    .expect("at most one player per lane")
+                    .collect();
+
+            assert_eq!(found, vec![(full_comp.clone(), CompThemes::full(3, legacy))]);
+        }
+    }
+}
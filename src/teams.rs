@@ -0,0 +1,544 @@
+//! Planning two teams at once for custom games, where the players are split into two teams that each share a skinset,
+//! optionally with the two teams on different or rival skinsets (e.g. Star Guardian vs Dark Star).
+//!
+//! Every way of splitting the players in two is tried. Each possible team is solved once with [solver::resolve_comps],
+//! keeping only its best-scoring comps, and the best comps of the two teams in each split are then paired up. So that
+//! the cut can't drop every comp that fits the team themes, comps that can't be paired at all are left out first, and
+//! the best comps are kept for each group of comps that pair with the same comps of the other team (see
+//! [TeamThemes::pairing_key]). Only comps that clash with the other team's champs can still push a matchup out.
+//!
+//! So that planning stays quick, each team only scans the first [MAX_COMPS_SCANNED_PER_TEAM] comps it finds, and a
+//! [TeamCache] can keep each team's best comps between plans so that only teams whose players changed are solved again.
+
+use crate::constants::{ChampId, Lane, SkinsetId, SkinsetMask, SkinsetSource};
+use crate::persist::{ChampEntry, PlayerRecord, PreferenceTier};
+use crate::scoring::{CompScore, ScoreWeights, Scorer};
+use crate::solver::{self, Comp, GameMode, MatchMode};
+use enumflags2::BitFlags;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The most players on one team.
+pub const TEAM_SIZE: usize = 5;
+
+/// The number of best-scoring comps kept for each possible team (and pairing key) before the teams are paired up.
+const COMPS_PER_TEAM: usize = 50;
+
+/// The most comps scanned for each possible team, so the comps kept are the best of the first ones found.
+pub const MAX_COMPS_SCANNED_PER_TEAM: usize = 5_000;
+
+/// Rival skinsets, as pairs of groups of skinset names (from either skinset table) where every skinset in one group is
+/// a rival of every skinset in the other. Names that aren't in the current data are ignored.
+pub const RIVAL_SKINSETS: &[(&[&str], &[&str])] = &[
+    (
+        &[
+            "Star Guardian",
+            "Star Guardian Season 1",
+            "Star Guardian Season 2",
+            "Star Guardian Season 3",
+            "Star Guardian Season 4",
+            "Cosmic",
+        ],
+        &["Dark Star"],
+    ),
+    (&["Arcade: Heroes"], &["Arcade: Battle Bosses"]),
+    (&["Sentinels of Light"], &["Ruined", "Ruination", "The Ruined King Saga"]),
+];
+
+/// How the players are split into teams.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TeamSetup {
+    /// Every player is on one team.
+    #[default]
+    OneTeam,
+
+    /// The players are split into two teams that each share a skinset.
+    TwoTeams(TeamThemes),
+}
+
+impl TeamSetup {
+    /// Get the most players this setup allows.
+    pub fn max_players(self) -> usize {
+        match self {
+            TeamSetup::OneTeam => TEAM_SIZE,
+            TeamSetup::TwoTeams(_) => 2 * TEAM_SIZE,
+        }
+    }
+}
+
+/// How the skinsets shared by each of two teams have to relate to each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TeamThemes {
+    /// The teams can share any skinsets, including the same one.
+    #[default]
+    Any,
+
+    /// The teams have to be on different skinsets (or universes, in universe mode).
+    Different,
+
+    /// The teams have to be on rival skinsets (see [RIVAL_SKINSETS]).
+    Rival,
+}
+
+impl TeamThemes {
+    /// Get the skinsets each of two teams would theme on, given the skinsets each team shares, or `None` if the teams
+    /// can't be themed this way. `theme_count` counts the themes in a set of skinsets (skinsets or universes).
+    fn themes(
+        self,
+        shared: [SkinsetMask; 2],
+        rivals: &[(SkinsetMask, SkinsetMask)],
+        theme_count: impl Fn(SkinsetMask) -> usize,
+    ) -> Option<[SkinsetMask; 2]> {
+        let [first, second] = shared;
+
+        match self {
+            TeamThemes::Any => Some(shared),
+
+            TeamThemes::Different => {
+                // Each team prefers the skinsets the other team doesn't share, if it has any.
+                let prefer_own = |own: SkinsetMask, other: SkinsetMask| {
+                    let own_only = own & !other;
+                    if own_only.is_empty() { own } else { own_only }
+                };
+
+                // Two teams that only share the same single theme can't be on different ones.
+                (first != second || theme_count(first) > 1).then(|| [prefer_own(first, second), prefer_own(second, first)])
+            }
+
+            TeamThemes::Rival => {
+                let mut themes = [SkinsetMask::EMPTY; 2];
+
+                // Rivalries go both ways, so check each team against both sides.
+                for (side, other_side) in rivals.iter().flat_map(|(left, right)| [(*left, *right), (*right, *left)]) {
+                    if !(first & side).is_empty() && !(second & other_side).is_empty() {
+                        themes[0] |= first & side;
+                        themes[1] |= second & other_side;
+                    }
+                }
+
+                (!themes[0].is_empty()).then_some(themes)
+            }
+        }
+    }
+
+    /// Get a key for a team's comp, given the skinsets it shares, such that comps with the same key can be paired with
+    /// exactly the same comps of the other team by [TeamThemes::themes]. This is `None` if the comp can't be paired
+    /// with any comp.
+    fn pairing_key(
+        self,
+        shared: SkinsetMask,
+        rivals: &[(SkinsetMask, SkinsetMask)],
+        theme_count: impl Fn(SkinsetMask) -> usize,
+    ) -> Option<SkinsetMask> {
+        match self {
+            TeamThemes::Any => Some(SkinsetMask::EMPTY),
+
+            // Only teams on the same single theme clash, so every comp with more than one theme pairs the same way.
+            TeamThemes::Different => Some(if theme_count(shared) == 1 { shared } else { SkinsetMask::EMPTY }),
+
+            // Pairing only depends on which rival skinsets the comp shares.
+            TeamThemes::Rival => {
+                let rival_skinsets =
+                    rivals.iter().fold(SkinsetMask::EMPTY, |union, (left, right)| union | *left | *right);
+                let key = shared & rival_skinsets;
+                (!key.is_empty()).then_some(key)
+            }
+        }
+    }
+}
+
+/// Get the rival skinsets in a skinset table, as pairs of masks.
+fn rival_masks(source: SkinsetSource) -> Vec<(SkinsetMask, SkinsetMask)> {
+    let mask = |names: &[&str]| -> SkinsetMask {
+        names.iter().filter_map(|name| SkinsetId::from_name(name)).collect::<SkinsetMask>() & source.skinset_mask()
+    };
+
+    RIVAL_SKINSETS
+        .iter()
+        .map(|(left, right)| (mask(left), mask(right)))
+        .filter(|(left, right)| !left.is_empty() && !right.is_empty())
+        .collect()
+}
+
+/// One team's comp in a matchup.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TeamComp {
+    /// The indices of the players on the team, in order.
+    pub players: Vec<usize>,
+    /// The champ and lane for each player on the team.
+    pub comp: Comp,
+    /// The non-excluded skinsets the team would theme on.
+    pub themes: SkinsetMask,
+    /// The score of the team's comp.
+    pub score: CompScore,
+}
+
+/// Two teams' comps planned together.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matchup {
+    /// The comps of the two teams. The first team is the larger one if there's an odd number of players.
+    pub teams: [TeamComp; 2],
+    /// The sum of the two teams' scores.
+    pub total: f64,
+}
+
+/// The best comps found for each team, kept between plans so that teams whose players haven't changed aren't solved
+/// again. Teams are keyed by their players' champs, so a cache is only valid while the planner's other settings stay
+/// the same; start a new one when they change.
+#[derive(Clone, Debug, Default)]
+pub struct TeamCache {
+    /// The best comps of each team solved in the last plan, keyed by the champs of the team's players.
+    comps: HashMap<Vec<Vec<ChampEntry>>, Vec<TeamComp>>,
+}
+
+/// Plans matchups for a set of players.
+#[derive(Clone, Copy, Debug)]
+pub struct TeamPlanner<'a> {
+    /// Every player, to be split into two teams.
+    pub players: &'a [PlayerRecord],
+    /// The set of skinsets excluded from consideration.
+    pub skinsets_excluded: SkinsetMask,
+    /// Whether comps are matched by skinset or by universe.
+    pub match_mode: MatchMode,
+    /// The skinset table comps are searched with.
+    pub skinset_source: SkinsetSource,
+    /// The game mode comps are found for.
+    pub game_mode: GameMode,
+    /// The least preferred tier of champ that comps are searched with.
+    pub min_preference_tier: PreferenceTier,
+    /// The skinsets the user has pinned as favourites.
+    pub favourite_skinsets: SkinsetMask,
+    /// How much each part of a comp's score counts.
+    pub score_weights: ScoreWeights,
+    /// How the two teams' skinsets have to relate to each other.
+    pub team_themes: TeamThemes,
+}
+
+impl TeamPlanner<'_> {
+    /// Find the best comps for a team, given the bitset of its players, best first. Comps that can't be paired with
+    /// any comp of the other team are left out, and the best ones are kept for each
+    /// [pairing key](TeamThemes::pairing_key).
+    fn best_comps(
+        &self,
+        team: usize,
+        rivals: &[(SkinsetMask, SkinsetMask)],
+        theme_count: impl Fn(SkinsetMask) -> usize,
+    ) -> Vec<TeamComp> {
+        let players: Vec<usize> = (0..self.players.len()).filter(|player| team & (1 << player) != 0).collect();
+        let team_players: Vec<PlayerRecord> = players.iter().map(|player| self.players[*player].clone()).collect();
+
+        // Only search the champs in the tiers searched, ignoring lanes in game modes without them.
+        let mut owned_pools: Vec<Vec<(ChampId, BitFlags<Lane>)>> =
+            team_players.iter().map(|player| player.champ_pool(self.min_preference_tier)).collect();
+
+        if !self.game_mode.has_lanes() {
+            solver::ignore_lanes(&mut owned_pools).expect("teams have at most one player per lane");
+        }
+
+        let champ_pools: Vec<&[(ChampId, BitFlags<Lane>)]> = owned_pools.iter().map(Vec::as_slice).collect();
+
+        let scorer = Scorer {
+            players: &team_players,
+            favourite_skinsets: self.favourite_skinsets,
+            match_mode: self.match_mode,
+            skinset_source: self.skinset_source,
+            game_mode: self.game_mode,
+            weights: self.score_weights,
+        };
+
+        // Rank comps by score, only keeping the best ones found so far. The sort is stable, so ties are kept in the
+        // order they were found in (which each comp is numbered with).
+        let rank = |best: &mut Vec<(usize, TeamComp)>| {
+            best.sort_by(|(_, a), (_, b)| b.score.total.total_cmp(&a.score.total));
+            best.truncate(COMPS_PER_TEAM);
+        };
+
+        let mut best_by_key: HashMap<SkinsetMask, Vec<(usize, TeamComp)>> = HashMap::new();
+
+        let comps = solver::resolve_comps(&champ_pools, self.skinsets_excluded, self.match_mode, self.skinset_source);
+
+        for (index, (comp, shared)) in comps.take(MAX_COMPS_SCANNED_PER_TEAM).enumerate() {
+            let Some(key) = self.team_themes.pairing_key(shared, rivals, &theme_count) else {
+                continue;
+            };
+
+            let score = scorer.score(&comp, shared);
+            let best = best_by_key.entry(key).or_default();
+            best.push((index, TeamComp { players: players.clone(), comp, themes: shared, score }));
+
+            if best.len() >= 2 * COMPS_PER_TEAM {
+                rank(best);
+            }
+        }
+
+        let mut best: Vec<(usize, TeamComp)> = best_by_key
+            .into_values()
+            .flat_map(|mut best| {
+                rank(&mut best);
+                best
+            })
+            .collect();
+
+        best.sort_by(|(a_index, a), (b_index, b)| b.score.total.total_cmp(&a.score.total).then(a_index.cmp(b_index)));
+        best.into_iter().map(|(_, comp)| comp).collect()
+    }
+
+    /// Find the best matchups, up to a limit, best first. This is empty if there are fewer than two players.
+    pub fn plan(&self, limit: usize) -> Vec<Matchup> {
+        self.plan_cached(limit, &mut TeamCache::default())
+    }
+
+    /// Find the best matchups like [TeamPlanner::plan], reusing the best comps of any team already in the cache. The
+    /// cache is left holding only the teams solved for this plan.
+    pub fn plan_cached(&self, limit: usize, cache: &mut TeamCache) -> Vec<Matchup> {
+        let player_count = self.players.len().min(2 * TEAM_SIZE);
+
+        if player_count < 2 {
+            return Vec::new();
+        }
+
+        let everyone = (1 << player_count) - 1;
+        let first_team_size = player_count.div_ceil(2);
+        let rivals = rival_masks(self.skinset_source);
+
+        // Count themes the same way comps are matched.
+        let theme_count = |shared: SkinsetMask| match self.match_mode {
+            MatchMode::Skinset => shared.count(),
+            MatchMode::Universe => shared.universes(self.skinset_source).len(),
+        };
+
+        // Get every way of splitting the players. When the teams are the same size, the first player is always on the
+        // first team so that each split is only tried once.
+        let splits = (0..=everyone).filter(|team: &usize| {
+            team.count_ones() as usize == first_team_size && (player_count % 2 == 1 || team & 1 != 0)
+        });
+
+        // Each team can be in several splits, so only solve it once, reusing the comps of teams solved for the last plan.
+        let mut team_comps: HashMap<usize, Vec<TeamComp>> = HashMap::new();
+        let mut cached = std::mem::take(&mut cache.comps);
+
+        // Rank matchups by their total score, only keeping the best ones found so far. The sort is stable, so ties are
+        // kept in the order they were found in.
+        let rank = |best: &mut Vec<Matchup>| {
+            best.sort_by(|a, b| b.total.total_cmp(&a.total));
+            best.truncate(limit);
+        };
+
+        let mut best = Vec::new();
+
+        // The lowest total a matchup can have and still be kept, once there are enough matchups to fill the limit.
+        let mut min_total = f64::NEG_INFINITY;
+
+        for first_team in splits {
+            let second_team = everyone & !first_team;
+
+            for team in [first_team, second_team] {
+                team_comps.entry(team).or_insert_with(|| {
+                    let players: Vec<usize> = (0..player_count).filter(|player| team & (1 << player) != 0).collect();
+                    let key: Vec<Vec<ChampEntry>> =
+                        players.iter().map(|player| self.players[*player].champs.clone()).collect();
+
+                    // The cached comps may be for the same players at other indices.
+                    let comps = cached.remove(&key).unwrap_or_else(|| self.best_comps(team, &rivals, theme_count));
+                    let comps: Vec<TeamComp> =
+                        comps.into_iter().map(|comp| TeamComp { players: players.clone(), ..comp }).collect();
+
+                    cache.comps.insert(key, comps.clone());
+                    comps
+                });
+            }
+
+            let second_comps = &team_comps[&second_team];
+
+            // Each team's comps are sorted best first, so once a pair's total is too low, every later pair's is too.
+            for first in &team_comps[&first_team] {
+                if second_comps.first().is_none_or(|second| first.score.total + second.score.total < min_total) {
+                    break;
+                }
+
+                for second in second_comps {
+                    if first.score.total + second.score.total < min_total {
+                        break;
+                    }
+
+                    // A champ can only be picked once in a game.
+                    if first.comp.iter().any(|(champ_id, _)| second.comp.iter().any(|(other, _)| other == champ_id)) {
+                        continue;
+                    }
+
+                    let shared = [first.themes, second.themes];
+
+                    let Some([first_themes, second_themes]) = self.team_themes.themes(shared, &rivals, theme_count) else {
+                        continue;
+                    };
+
+                    best.push(Matchup {
+                        teams: [
+                            TeamComp { themes: first_themes, ..first.clone() },
+                            TeamComp { themes: second_themes, ..second.clone() },
+                        ],
+                        total: first.score.total + second.score.total,
+                    });
+
+                    if best.len() >= limit.max(1).saturating_mul(2) {
+                        rank(&mut best);
+
+                        if let Some(last_kept) = limit.checked_sub(1).and_then(|last| best.get(last)) {
+                            min_total = last_kept.total;
+                        }
+                    }
+                }
+            }
+        }
+
+        rank(&mut best);
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Make a player with the given champs (in any lane) and tiers.
+    fn player(champs: impl IntoIterator<Item = (ChampId, PreferenceTier)>) -> PlayerRecord {
+        PlayerRecord {
+            name: None,
+            champs: champs.into_iter().map(|(champ_id, tier)| (champ_id, BitFlags::all(), tier)).collect(),
+        }
+    }
+
+    /// Make a planner for ARAM, scoring comps by champ preference alone.
+    fn planner(players: &[PlayerRecord], team_themes: TeamThemes) -> TeamPlanner<'_> {
+        TeamPlanner {
+            players,
+            skinsets_excluded: SkinsetMask::EMPTY,
+            match_mode: MatchMode::Skinset,
+            skinset_source: SkinsetSource::Wiki,
+            game_mode: GameMode::Aram,
+            min_preference_tier: PreferenceTier::least(),
+            favourite_skinsets: SkinsetMask::EMPTY,
+            score_weights: ScoreWeights { shared_skinsets: 0, champ_preference: 1, lane_fit: 0, favourite_skinsets: 0 },
+            team_themes,
+        }
+    }
+
+    /// Find champs with a skin in some of a set of skinsets and none of another.
+    fn champs_in(skinsets: SkinsetMask, not_in: SkinsetMask) -> impl Iterator<Item = ChampId> {
+        ChampId::iter_all().filter(move |champ_id| {
+            let champ_skinsets = champ_id.skinset_mask(SkinsetSource::Wiki);
+            !(champ_skinsets & skinsets).is_empty() && (champ_skinsets & not_in).is_empty()
+        })
+    }
+
+    #[test]
+    fn finds_rival_matchups_past_the_best_comps_of_each_team() {
+        let rivals = rival_masks(SkinsetSource::Wiki);
+        let (star_guardian, dark_star) = rivals[0];
+        let rival_skinsets = rivals.iter().fold(SkinsetMask::EMPTY, |union, (left, right)| union | *left | *right);
+
+        // The first player's only rival champ is their least preferred, behind more mains than are kept per team.
+        let mains = champs_in(SkinsetSource::Wiki.skinset_mask(), rival_skinsets).take(COMPS_PER_TEAM + 10);
+        let star_guardian_champ = champs_in(star_guardian, dark_star).next().expect("a champ is only in Star Guardian");
+        let dark_star_champ = champs_in(dark_star, star_guardian).next().expect("a champ is only in Dark Star");
+
+        let first_pool = mains
+            .map(|champ_id| (champ_id, PreferenceTier::Main))
+            .chain([(star_guardian_champ, PreferenceTier::Willing)]);
+        let players = [player(first_pool), player([(dark_star_champ, PreferenceTier::Main)])];
+
+        let matchups = planner(&players, TeamThemes::Rival).plan(10);
+
+        assert_eq!(matchups.len(), 1);
+        let [first, second] = &matchups[0].teams;
+        assert_eq!(first.comp[0].0, star_guardian_champ);
+        assert_eq!(second.comp[0].0, dark_star_champ);
+        assert!(!(first.themes & star_guardian).is_empty());
+        assert!(!(second.themes & dark_star).is_empty());
+
+        // Without the rivalry, the mains make the best matchups.
+        let matchups = planner(&players, TeamThemes::Any).plan(1);
+        assert_ne!(matchups[0].teams[0].comp[0].0, star_guardian_champ);
+    }
+
+    #[test]
+    fn themes_teams_on_any_different_or_rival_skinsets() {
+        let skinset = |name| SkinsetMask::from_skinsets(&[SkinsetId::from_name(name).expect("skinset exists")]);
+        let rivals = rival_masks(SkinsetSource::Wiki);
+        let (legacy, dark_star) = (skinset("Legacy"), skinset("Dark Star"));
+        let star_guardian = skinset("Star Guardian Season 1");
+
+        let themes = |team_themes: TeamThemes, shared| team_themes.themes(shared, &rivals, SkinsetMask::count);
+
+        assert_eq!(themes(TeamThemes::Any, [legacy, legacy]), Some([legacy, legacy]));
+
+        // Teams on the same single skinset aren't different, but each can take its own of several.
+        assert_eq!(themes(TeamThemes::Different, [legacy, legacy]), None);
+        assert_eq!(themes(TeamThemes::Different, [legacy | dark_star, legacy]), Some([dark_star, legacy]));
+        assert_eq!(themes(TeamThemes::Different, [legacy | dark_star; 2]), Some([legacy | dark_star; 2]));
+
+        // Rival skinsets only theme the rival parts, on either team.
+        assert_eq!(themes(TeamThemes::Rival, [star_guardian | legacy, dark_star]), Some([star_guardian, dark_star]));
+        assert_eq!(themes(TeamThemes::Rival, [dark_star, star_guardian]), Some([dark_star, star_guardian]));
+        assert_eq!(themes(TeamThemes::Rival, [star_guardian, star_guardian]), None);
+        assert_eq!(themes(TeamThemes::Rival, [legacy, dark_star]), None);
+    }
+
+    #[test]
+    fn allows_twice_as_many_players_for_two_teams() {
+        assert_eq!(TeamSetup::OneTeam.max_players(), TEAM_SIZE);
+        assert_eq!(TeamSetup::TwoTeams(TeamThemes::Rival).max_players(), 2 * TEAM_SIZE);
+    }
+
+    #[test]
+    fn splits_players_into_two_teams_without_sharing_champs() {
+        let champ = |name| ChampId::from_name(name).expect("champ exists");
+
+        // Every player can play the same champs, so the teams have to share them out.
+        let champs = ["Ahri", "Lux", "Teemo", "Garen", "Shen", "Malphite"];
+        let pool = || champs.map(|name| (champ(name), PreferenceTier::Main));
+        let players = [player(pool()), player(pool()), player(pool())];
+
+        assert!(planner(&players[..1], TeamThemes::Any).plan(10).is_empty());
+
+        let matchups = planner(&players, TeamThemes::Any).plan(10);
+        assert_eq!(matchups.len(), 10);
+        assert!(matchups.windows(2).all(|pair| pair[0].total >= pair[1].total));
+
+        for matchup in &matchups {
+            let [first, second] = &matchup.teams;
+
+            // With an odd number of players, the first team is the larger one.
+            assert_eq!((first.players.len(), second.players.len()), (2, 1));
+
+            let mut players: Vec<usize> = first.players.iter().chain(&second.players).copied().collect();
+            players.sort_unstable();
+            assert_eq!(players, vec![0, 1, 2]);
+
+            assert!(first.comp.iter().all(|(champ_id, _)| second.comp.iter().all(|(other, _)| other != champ_id)));
+            assert_eq!(matchup.total, first.score.total + second.score.total);
+        }
+    }
+
+    #[test]
+    fn plans_the_same_matchups_with_teams_from_the_cache() {
+        let champ = |name| ChampId::from_name(name).expect("champ exists");
+        let pool = |names: &[&'static str]| player(names.iter().map(|name| (champ(name), PreferenceTier::Main)));
+
+        let ahri = pool(&["Ahri", "Lux", "Teemo"]);
+        let garen = pool(&["Garen", "Shen", "Lux"]);
+        let malphite = pool(&["Malphite", "Ahri", "Shen"]);
+        let mut cache = TeamCache::default();
+
+        let players = [ahri.clone(), garen.clone(), malphite.clone()];
+        let matchups = planner(&players, TeamThemes::Any).plan_cached(10, &mut cache);
+        assert_eq!(matchups, planner(&players, TeamThemes::Any).plan(10));
+        assert_eq!(cache.comps.len(), 6);
+
+        // Teams of the same players at other indices come from the cache, with their players renumbered.
+        let reordered = [malphite, ahri, garen];
+        let matchups = planner(&reordered, TeamThemes::Any).plan_cached(10, &mut cache);
+        assert_eq!(matchups, planner(&reordered, TeamThemes::Any).plan(10));
+        assert_eq!(cache.comps.len(), 6);
+    }
+}
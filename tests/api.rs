@@ -0,0 +1,321 @@
+//! Integration tests for the JSON API, run against a server on localhost.
+
+use league_skinset_finder::api;
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use tiny_http::Server;
+
+/// Start a server on a free localhost port, returning its address.
+fn start_server() -> String {
+    let server = Arc::new(Server::http("127.0.0.1:0").expect("server starts"));
+    let address = server.server_addr().to_ip().expect("server has an IP address").to_string();
+
+    std::thread::spawn(move || api::serve(&server));
+    address
+}
+
+/// Send a request to the server and return the response status and JSON body.
+fn request(address: &str, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(address).expect("server accepts connections");
+
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: {address}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len(),
+    )
+    .expect("request is sent");
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("response is received");
+
+    let (head, body) = response.split_once("\r\n\r\n").expect("response has a body");
+    let status = head.split(' ').nth(1).and_then(|status| status.parse().ok()).expect("response has a status");
+
+    (status, serde_json::from_str(body).expect("body is JSON"))
+}
+
+/// Make a team file with the given players' champs and lane masks.
+fn team_file(players: &[&[(&str, u8)]], match_mode: &str) -> String {
+    json!({
+        "version": 1,
+        "players": players
+            .iter()
+            .map(|champs| json!({ "name": null, "champs": champs }))
+            .collect::<Vec<_>>(),
+        "skinsets_excluded": [],
+        "match_mode": match_mode,
+    })
+    .to_string()
+}
+
+#[test]
+fn lists_champions_with_default_lanes() {
+    let address = start_server();
+    let (status, body) = request(&address, "GET", "/champions", "");
+
+    assert_eq!(status, 200);
+
+    let champs = body.as_array().expect("champions are a list");
+    let ahri = champs.iter().find(|champ| champ["name"] == "Ahri").expect("Ahri is listed");
+    assert_eq!(ahri["default_lanes"], json!(["Mid"]));
+}
+
+#[test]
+fn lists_skinsets_for_each_source() {
+    let address = start_server();
+
+    for source in ["wiki", "official"] {
+        let (status, body) = request(&address, "GET", &format!("/skinsets?source={source}"), "");
+        assert_eq!(status, 200);
+
+        let skinsets = body.as_array().expect("skinsets are a list");
+        assert!(!skinsets.is_empty());
+        assert!(skinsets.iter().all(|skinset| skinset["universe"].is_string() && skinset["champs"].is_array()));
+    }
+
+    let (status, body) = request(&address, "GET", "/skinsets?source=nope", "");
+    assert_eq!(status, 400);
+    assert!(body["error"].is_string());
+}
+
+#[test]
+fn ranks_comps_by_score() {
+    let address = start_server();
+    let team = team_file(&[&[("Ahri", 4), ("Jinx", 8), ("Lux", 4)], &[("Ezreal", 8), ("Caitlyn", 8)]], "Skinset");
+    let (status, body) = request(&address, "POST", "/comps?limit=3", &team);
+
+    assert_eq!(status, 200);
+
+    // Comps come back with the best score first.
+    let scores: Vec<f64> = body["comps"]
+        .as_array()
+        .expect("comps are a list")
+        .iter()
+        .map(|comp| comp["score"]["total"].as_f64().expect("total score is a number"))
+        .collect();
+    assert!(!scores.is_empty());
+    assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+}
+
+#[test]
+fn ranks_comps_by_shared_skinsets() {
+    let address = start_server();
+    let mut team: Value = serde_json::from_str(&team_file(
+        &[&[("Ahri", 4), ("Jinx", 8), ("Lux", 4)], &[("Ezreal", 8), ("Caitlyn", 8)]],
+        "Skinset",
+    ))
+    .expect("team file is JSON");
+
+    // Only score the shared skinsets.
+    team["score_weights"] = json!({ "shared_skinsets": 1, "champ_preference": 0, "lane_fit": 0, "favourite_skinsets": 0 });
+    let (status, body) = request(&address, "POST", "/comps?limit=3", &team.to_string());
+
+    assert_eq!(status, 200);
+    assert!(body["problems"].as_array().expect("problems are a list").is_empty());
+
+    let comps = body["comps"].as_array().expect("comps are a list");
+    assert!(comps.len() <= 3);
+    assert!(body["total"].as_u64().expect("total is a number") >= comps.len() as u64);
+
+    // Comps come back with the most shared skinsets first.
+    let shared_counts: Vec<usize> = comps
+        .iter()
+        .map(|comp| comp["shared"].as_array().expect("shared is a list").len())
+        .collect();
+    assert!(shared_counts.windows(2).all(|pair| pair[0] >= pair[1]));
+
+    for comp in comps {
+        let picks = comp["picks"].as_array().expect("picks are a list");
+        assert_eq!(picks.len(), 2);
+        assert_eq!(picks[0]["player"], "Player 1");
+    }
+}
+
+#[test]
+fn ranks_comps_sharing_favourite_skinsets_first() {
+    let address = start_server();
+    let players: &[&[(&str, u8)]] = &[&[("Ahri", 4), ("Jinx", 8), ("Lux", 4)], &[("Ezreal", 8), ("Caitlyn", 8)]];
+    let (_, body) = request(&address, "POST", "/comps?limit=1000", &team_file(players, "Skinset"));
+
+    // Pin a skinset shared by the last comp found as a favourite.
+    let comps = body["comps"].as_array().expect("comps are a list");
+    let favourite = comps.last().expect("some comps are found")["shared"][0].clone();
+
+    let mut team: Value = serde_json::from_str(&team_file(players, "Skinset")).expect("team file is JSON");
+    team["favourite_skinsets"] = json!([favourite]);
+    team["score_weights"] = json!({ "shared_skinsets": 0, "champ_preference": 0, "lane_fit": 0, "favourite_skinsets": 1 });
+
+    let (status, body) = request(&address, "POST", "/comps?limit=1", &team.to_string());
+    assert_eq!(status, 200);
+
+    let best = &body["comps"][0];
+    assert_eq!(best["score"]["favourite_skinsets"], 1);
+    assert!(best["shared"].as_array().expect("shared is a list").contains(&favourite));
+}
+
+#[test]
+fn only_searches_champs_in_the_minimum_tier() {
+    let address = start_server();
+    let team = json!({
+        "version": 1,
+        "players": [
+            { "name": null, "champs": [["Ahri", 4, "Main"], ["Lux", 4, "Willing"]] },
+            { "name": null, "champs": [["Ezreal", 8, "Comfortable"], ["Caitlyn", 8, "Main"]] },
+        ],
+        "skinsets_excluded": [],
+        "min_preference_tier": "Comfortable",
+    });
+
+    let (status, body) = request(&address, "POST", "/comps?limit=1000", &team.to_string());
+    assert_eq!(status, 200);
+
+    let comps = body["comps"].as_array().expect("comps are a list");
+    assert!(!comps.is_empty());
+    assert!(comps.iter().all(|comp| comp["picks"][0]["champ"] == "Ahri"));
+}
+
+#[test]
+fn finds_comps_where_only_some_players_share_a_skinset() {
+    let address = start_server();
+    let players: &[&[(&str, u8)]] = &[&[("Ahri", 4), ("Lux", 4)], &[("Ezreal", 8), ("Jinx", 8)], &[("Thresh", 16), ("Nami", 16)]];
+    let (_, body) = request(&address, "POST", "/comps?limit=1000", &team_file(players, "Skinset"));
+    let full_total = body["total"].as_u64().expect("total is a number");
+
+    let mut team: Value = serde_json::from_str(&team_file(players, "Skinset")).expect("team file is JSON");
+    team["min_themed_players"] = json!(2);
+
+    let (status, body) = request(&address, "POST", "/comps?limit=1000", &team.to_string());
+    assert_eq!(status, 200);
+    assert!(body["total"].as_u64().expect("total is a number") > full_total);
+
+    // Comps with more players in theme come first, and only the players outside every themed group are off-theme.
+    let comps = body["comps"].as_array().expect("comps are a list");
+    let themed: Vec<u64> = comps.iter().map(|comp| comp["themed_players"].as_u64().expect("themed players is a number")).collect();
+    assert!(themed.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert!(themed.iter().all(|themed_players| (2..=3).contains(themed_players)));
+
+    for (comp, themed_players) in comps.iter().zip(&themed) {
+        let picks = comp["picks"].as_array().expect("picks are a list");
+        let off_theme = picks.iter().filter(|pick| pick["off_theme"] == true).count() as u64;
+        assert!(off_theme <= 3 - themed_players);
+    }
+}
+
+#[test]
+fn ignores_lanes_in_aram() {
+    let address = start_server();
+    let players: &[&[(&str, u8)]] = &[&[("Ahri", 4)], &[("Lux", 4)]];
+
+    // Both champs are only played mid, so they can't be on the same Summoner's Rift team.
+    let (_, body) = request(&address, "POST", "/comps", &team_file(players, "Skinset"));
+    assert_eq!(body["total"], 0);
+
+    let mut team: Value = serde_json::from_str(&team_file(players, "Skinset")).expect("team file is JSON");
+    team["game_mode"] = json!("Aram");
+
+    let (status, body) = request(&address, "POST", "/comps", &team.to_string());
+    assert_eq!(status, 200);
+
+    let comps = body["comps"].as_array().expect("comps are a list");
+    assert!(!comps.is_empty());
+    assert!(comps.iter().flat_map(|comp| comp["picks"].as_array().expect("picks are a list")).all(|pick| pick["lane"].is_null()));
+    assert!(comps.iter().all(|comp| comp["score"]["lane_fit"] == 0));
+}
+
+#[test]
+fn reports_skipped_team_file_entries() {
+    let address = start_server();
+    let team = team_file(&[&[("Ahri", 4), ("Not A Champ", 4)], &[("Ezreal", 8)]], "Universe");
+    let (status, body) = request(&address, "POST", "/comps", &team);
+
+    assert_eq!(status, 200);
+    assert_eq!(body["match_mode"], "Universe");
+    assert_eq!(body["problems"], json!(["Player 1: unknown champion \"Not A Champ\""]));
+}
+
+#[test]
+fn rejects_bad_requests() {
+    let address = start_server();
+
+    assert_eq!(request(&address, "POST", "/comps", "not json").0, 400);
+    assert_eq!(request(&address, "POST", "/comps?limit=many", &team_file(&[], "Skinset")).0, 400);
+    assert_eq!(request(&address, "POST", &format!("/comps?limit={}", usize::MAX), &team_file(&[], "Skinset")).0, 400);
+    assert_eq!(request(&address, "POST", &format!("/matchups?limit={}", api::MAX_COMP_LIMIT + 1), &team_file(&[], "Skinset")).0, 400);
+    assert_eq!(request(&address, "GET", "/comps", "").0, 405);
+    assert_eq!(request(&address, "GET", "/matchups", "").0, 405);
+    assert_eq!(request(&address, "GET", "/nowhere", "").0, 404);
+
+    // The server keeps answering after bad requests.
+    assert_eq!(request(&address, "POST", &format!("/comps?limit={}", api::MAX_COMP_LIMIT), &team_file(&[], "Skinset")).0, 200);
+}
+
+#[test]
+fn sends_two_team_files_to_matchups() {
+    let address = start_server();
+
+    let mut team: Value = serde_json::from_str(&team_file(&[&[("Ahri", 31)], &[("Lux", 31)]], "Skinset")).expect("team file is JSON");
+    team["team_setup"] = json!({ "TwoTeams": "Any" });
+
+    let (status, body) = request(&address, "POST", "/comps", &team.to_string());
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().expect("error is a string").contains("/matchups"));
+
+    assert_eq!(request(&address, "POST", "/matchups", &team.to_string()).0, 200);
+}
+
+#[test]
+fn plans_matchups_on_rival_skinsets() {
+    let address = start_server();
+    let players: &[&[(&str, u8)]] =
+        &[&[("Ahri", 31), ("Thresh", 31)], &[("Lux", 31), ("Varus", 31)], &[("Ezreal", 31), ("Xerath", 31)], &[("Jinx", 31), ("Karma", 31)]];
+
+    let mut team: Value = serde_json::from_str(&team_file(players, "Skinset")).expect("team file is JSON");
+    team["game_mode"] = json!("Aram");
+    team["team_setup"] = json!({ "TwoTeams": "Rival" });
+
+    let (status, body) = request(&address, "POST", "/matchups?limit=10", &team.to_string());
+    assert_eq!(status, 200);
+    assert_eq!(body["team_themes"], "Rival");
+
+    let matchups = body["matchups"].as_array().expect("matchups are a list");
+    assert!(!matchups.is_empty() && matchups.len() <= 10);
+
+    for matchup in matchups {
+        let teams = matchup["teams"].as_array().expect("teams are a list");
+        assert_eq!(teams.len(), 2);
+
+        // Every player is on exactly one team, and no champ is picked twice.
+        let picks: Vec<&Value> = teams.iter().flat_map(|team| team["picks"].as_array().expect("picks are a list")).collect();
+        assert_eq!(picks.len(), 4);
+        for (index, pick) in picks.iter().enumerate() {
+            assert!(picks[index + 1..].iter().all(|other| other["champ"] != pick["champ"] && other["player"] != pick["player"]));
+        }
+
+        let on_dark_star = |team: &Value| team["themes"] == json!(["Dark Star"]);
+        assert!(on_dark_star(&teams[0]) != on_dark_star(&teams[1]));
+    }
+}
+
+#[test]
+fn searches_each_arena_duo_separately() {
+    let address = start_server();
+    let players: &[&[(&str, u8)]] = &[&[("Ahri", 4)], &[("Lux", 4)], &[("Ahri", 4)], &[("Lux", 4)]];
+
+    let mut team: Value = serde_json::from_str(&team_file(players, "Skinset")).expect("team file is JSON");
+    team["game_mode"] = json!("Arena");
+
+    let (status, body) = request(&address, "POST", "/comps?limit=1", &team.to_string());
+    assert_eq!(status, 200);
+
+    // Each duo gets its own comps, with only its own players' picks.
+    let comps = body["comps"].as_array().expect("comps are a list");
+    assert_eq!(comps.iter().map(|comp| comp["team"].clone()).collect::<Vec<_>>(), vec![json!(0), json!(1)]);
+
+    for (comp, player_names) in comps.iter().zip([["Player 1", "Player 2"], ["Player 3", "Player 4"]]) {
+        let picks = comp["picks"].as_array().expect("picks are a list");
+        assert_eq!(picks.iter().map(|pick| pick["player"].as_str()).collect::<Vec<_>>(), player_names.map(Some));
+        assert!(picks.iter().all(|pick| pick["lane"].is_null()));
+    }
+}